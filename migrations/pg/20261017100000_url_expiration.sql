@@ -0,0 +1,30 @@
+-- +goose Up
+-- +goose StatementBegin
+ALTER TABLE url ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ;
+ALTER TABLE url ADD COLUMN IF NOT EXISTS max_clicks BIGINT;
+ALTER TABLE url ADD COLUMN IF NOT EXISTS clicks BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_url_expires_at ON url(expires_at) WHERE expires_at IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS url_archive(
+    id UUID PRIMARY KEY,
+    alias TEXT NOT NULL,
+    url TEXT NOT NULL,
+    user_id UUID NOT NULL,
+    created_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    max_clicks BIGINT,
+    clicks BIGINT NOT NULL DEFAULT 0,
+    data JSONB NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TABLE IF EXISTS url_archive;
+DROP INDEX IF EXISTS idx_url_expires_at;
+ALTER TABLE url DROP COLUMN IF EXISTS clicks;
+ALTER TABLE url DROP COLUMN IF EXISTS max_clicks;
+ALTER TABLE url DROP COLUMN IF EXISTS expires_at;
+-- +goose StatementEnd
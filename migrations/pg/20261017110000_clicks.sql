@@ -0,0 +1,19 @@
+-- +goose Up
+-- +goose StatementBegin
+CREATE TABLE IF NOT EXISTS clicks(
+    id BIGSERIAL PRIMARY KEY,
+    url_id UUID REFERENCES url(id) ON DELETE SET NULL,
+    clicked_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    referrer TEXT,
+    user_agent TEXT,
+    country TEXT,
+    ip_hash TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_clicks_url_id_clicked_at ON clicks(url_id, clicked_at);
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TABLE IF EXISTS clicks;
+-- +goose StatementEnd
@@ -0,0 +1,20 @@
+-- +goose Up
+-- +goose StatementBegin
+ALTER TABLE url ADD COLUMN IF NOT EXISTS enabled BOOLEAN NOT NULL DEFAULT true;
+
+CREATE TABLE IF NOT EXISTS url_history(
+    id BIGSERIAL PRIMARY KEY,
+    url_id UUID NOT NULL,
+    changed_by UUID NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    changes JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_url_history_url_id ON url_history(url_id, changed_at);
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TABLE IF EXISTS url_history;
+ALTER TABLE url DROP COLUMN IF EXISTS enabled;
+-- +goose StatementEnd
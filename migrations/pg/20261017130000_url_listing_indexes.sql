@@ -0,0 +1,17 @@
+-- +goose Up
+-- +goose StatementBegin
+UPDATE url SET created_at = now() WHERE created_at IS NULL;
+ALTER TABLE url ALTER COLUMN created_at SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_url_created_at_id ON url(created_at, id);
+CREATE INDEX IF NOT EXISTS idx_url_user_id_created_at_id ON url(user_id, created_at, id);
+CREATE INDEX IF NOT EXISTS idx_url_clicks_id ON url(clicks, id);
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP INDEX IF EXISTS idx_url_clicks_id;
+DROP INDEX IF EXISTS idx_url_user_id_created_at_id;
+DROP INDEX IF EXISTS idx_url_created_at_id;
+ALTER TABLE url ALTER COLUMN created_at DROP NOT NULL;
+-- +goose StatementEnd
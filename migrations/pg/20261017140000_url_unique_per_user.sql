@@ -0,0 +1,11 @@
+-- +goose Up
+-- +goose StatementBegin
+ALTER TABLE url DROP CONSTRAINT IF EXISTS url_url_unique;
+ALTER TABLE url ADD CONSTRAINT url_user_id_url_key UNIQUE (user_id, url);
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+ALTER TABLE url DROP CONSTRAINT IF EXISTS url_user_id_url_key;
+ALTER TABLE url ADD CONSTRAINT url_url_unique UNIQUE (url);
+-- +goose StatementEnd
@@ -0,0 +1,11 @@
+-- +goose Up
+-- +goose StatementBegin
+ALTER TABLE url ADD COLUMN IF NOT EXISTS interstitial BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE url ADD COLUMN IF NOT EXISTS countdown_seconds INTEGER;
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+ALTER TABLE url DROP COLUMN IF EXISTS countdown_seconds;
+ALTER TABLE url DROP COLUMN IF EXISTS interstitial;
+-- +goose StatementEnd
@@ -0,0 +1,15 @@
+-- +goose Up
+-- +goose StatementBegin
+CREATE TABLE IF NOT EXISTS blocked_domains(
+    id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+    domain TEXT NOT NULL UNIQUE,
+    reason TEXT,
+    created_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TABLE IF EXISTS blocked_domains;
+-- +goose StatementEnd
@@ -0,0 +1,34 @@
+-- +goose Up
+-- +goose StatementBegin
+CREATE TABLE IF NOT EXISTS domains(
+    id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+    host TEXT NOT NULL,
+    owner_id UUID NOT NULL,
+    verification_token TEXT NOT NULL,
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (owner_id, host)
+);
+CREATE UNIQUE INDEX IF NOT EXISTS domains_verified_host_key ON domains(host) WHERE verified_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_domains_owner_id ON domains(owner_id);
+
+ALTER TABLE url ADD COLUMN IF NOT EXISTS domain_id UUID REFERENCES domains(id) ON DELETE RESTRICT;
+ALTER TABLE url DROP CONSTRAINT IF EXISTS url_alias_key;
+ALTER TABLE url ADD CONSTRAINT url_domain_id_alias_key UNIQUE (domain_id, alias);
+CREATE UNIQUE INDEX IF NOT EXISTS url_default_domain_alias_key ON url(alias) WHERE domain_id IS NULL;
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM url WHERE domain_id IS NOT NULL) THEN
+        RAISE EXCEPTION 'links on custom domains exist, move or delete them before rolling back';
+    END IF;
+END $$;
+DROP INDEX IF EXISTS url_default_domain_alias_key;
+ALTER TABLE url DROP CONSTRAINT IF EXISTS url_domain_id_alias_key;
+ALTER TABLE url ADD CONSTRAINT url_alias_key UNIQUE (alias);
+ALTER TABLE url DROP COLUMN IF EXISTS domain_id;
+DROP TABLE IF EXISTS domains;
+-- +goose StatementEnd
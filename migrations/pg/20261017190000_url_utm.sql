@@ -0,0 +1,19 @@
+-- +goose Up
+-- +goose StatementBegin
+ALTER TABLE url ADD COLUMN IF NOT EXISTS utm_source TEXT;
+ALTER TABLE url ADD COLUMN IF NOT EXISTS utm_medium TEXT;
+ALTER TABLE url ADD COLUMN IF NOT EXISTS utm_campaign TEXT;
+ALTER TABLE url ADD COLUMN IF NOT EXISTS utm_term TEXT;
+ALTER TABLE url ADD COLUMN IF NOT EXISTS utm_content TEXT;
+ALTER TABLE url ADD COLUMN IF NOT EXISTS forward_query BOOLEAN NOT NULL DEFAULT false;
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+ALTER TABLE url DROP COLUMN IF EXISTS forward_query;
+ALTER TABLE url DROP COLUMN IF EXISTS utm_content;
+ALTER TABLE url DROP COLUMN IF EXISTS utm_term;
+ALTER TABLE url DROP COLUMN IF EXISTS utm_campaign;
+ALTER TABLE url DROP COLUMN IF EXISTS utm_medium;
+ALTER TABLE url DROP COLUMN IF EXISTS utm_source;
+-- +goose StatementEnd
@@ -0,0 +1,17 @@
+-- +goose Up
+-- +goose StatementBegin
+CREATE TABLE IF NOT EXISTS redirect_rules(
+    id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+    url_id UUID NOT NULL REFERENCES url(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    conditions JSONB NOT NULL,
+    destination TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_redirect_rules_url_id_position ON redirect_rules(url_id, position);
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+DROP TABLE IF EXISTS redirect_rules;
+-- +goose StatementEnd
@@ -0,0 +1,20 @@
+-- +goose Up
+-- +goose StatementBegin
+CREATE TABLE IF NOT EXISTS url_variants(
+    id UUID DEFAULT uuid_generate_v4() PRIMARY KEY,
+    url_id UUID NOT NULL REFERENCES url(id) ON DELETE CASCADE,
+    destination TEXT NOT NULL,
+    weight INTEGER NOT NULL CHECK (weight > 0),
+    clicks BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS idx_url_variants_url_id ON url_variants(url_id);
+
+ALTER TABLE clicks ADD COLUMN IF NOT EXISTS variant_id UUID REFERENCES url_variants(id) ON DELETE SET NULL;
+-- +goose StatementEnd
+
+-- +goose Down
+-- +goose StatementBegin
+ALTER TABLE clicks DROP COLUMN IF EXISTS variant_id;
+DROP TABLE IF EXISTS url_variants;
+-- +goose StatementEnd
@@ -1,13 +1,40 @@
+use crate::utils::secret::load_material;
 use humantime;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tokio::fs;
 use toml;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct Config {
     pub database: Option<DatabaseConfig>,
     pub server: Option<HTTPServerConfig>,
+    #[serde(default)]
+    pub alias: AliasConfig,
+    #[serde(default)]
+    pub sweeper: SweeperConfig,
+    #[serde(default)]
+    pub analytics: AnalyticsConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub bulk: BulkConfig,
+    #[serde(default)]
+    pub url: UrlConfig,
+    #[serde(default)]
+    pub policy: PolicyConfig,
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+    #[serde(default)]
+    pub domains: DomainsConfig,
+    #[serde(default)]
+    pub jwt: JwtConfig,
+    #[serde(default)]
+    pub oauth: OAuthConfig,
+    /// Shorthand for the `google` OAuth provider, kept for configs written
+    /// before `[[oauth.providers]]`.
+    #[serde(default)]
+    pub google: Option<GoogleConfig>,
 }
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DatabaseConfig {
@@ -28,6 +55,497 @@ pub struct HTTPServerConfig {
     pub debug: bool,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AliasStrategy {
+    Random,
+    Counter,
+    Sqids,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct AliasConfig {
+    pub strategy: AliasStrategy,
+    pub length: usize,
+    pub alphabet: String,
+    pub salt: String,
+    pub max_attempts: u32,
+    pub grow_after: u32,
+}
+
+impl Default for AliasConfig {
+    fn default() -> Self {
+        Self {
+            strategy: AliasStrategy::Random,
+            length: 6,
+            alphabet: "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".to_string(),
+            salt: String::new(),
+            max_attempts: 5,
+            grow_after: 2,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SweeperMode {
+    Delete,
+    Archive,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct SweeperConfig {
+    pub enabled: bool,
+    pub interval: String,
+    pub mode: SweeperMode,
+    pub batch_size: i64,
+}
+
+impl Default for SweeperConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: "5m".to_string(),
+            mode: SweeperMode::Archive,
+            batch_size: 1000,
+        }
+    }
+}
+
+impl SweeperConfig {
+    pub fn get_interval(&self) -> Duration {
+        self.interval
+            .parse::<humantime::Duration>()
+            .map(|d| d.into())
+            .unwrap_or(Duration::from_secs(300))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GeoIpBackend {
+    None,
+    Static,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GeoIpRange {
+    pub cidr: String,
+    pub country: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct AnalyticsConfig {
+    pub batch_size: usize,
+    pub flush_interval: String,
+    pub channel_capacity: usize,
+    pub ip_hash_salt: String,
+    pub geoip: GeoIpBackend,
+    pub geoip_ranges: Vec<GeoIpRange>,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 500,
+            flush_interval: "2s".to_string(),
+            channel_capacity: 10_000,
+            ip_hash_salt: "env:IP_HASH_SALT".to_string(),
+            geoip: GeoIpBackend::None,
+            geoip_ranges: Vec::new(),
+        }
+    }
+}
+
+impl AnalyticsConfig {
+    pub fn get_flush_interval(&self) -> Duration {
+        self.flush_interval
+            .parse::<humantime::Duration>()
+            .map(|d| d.into())
+            .unwrap_or(Duration::from_secs(2))
+    }
+
+    /// Resolves `ip_hash_salt` like the JWT keys; an empty or placeholder
+    /// salt would make the stored IP hashes trivially reversible.
+    pub fn load_ip_hash_salt(&self) -> Result<String, String> {
+        let salt = load_material(&self.ip_hash_salt)?;
+        match salt.trim() {
+            "" => Err("ip_hash_salt is empty".to_string()),
+            "change-me" => Err("ip_hash_salt is still the placeholder value".to_string()),
+            _ => Ok(salt),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    None,
+    Memory,
+    Redis,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub backend: CacheBackend,
+    pub capacity: usize,
+    pub ttl: String,
+    pub redis_url: String,
+    pub prefix: String,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            backend: CacheBackend::Memory,
+            capacity: 10_000,
+            ttl: "10m".to_string(),
+            redis_url: "redis://localhost:6379".to_string(),
+            prefix: "url:".to_string(),
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn get_ttl(&self) -> Duration {
+        self.ttl
+            .parse::<humantime::Duration>()
+            .map(|d| d.into())
+            .unwrap_or(Duration::from_secs(600))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct BulkConfig {
+    pub max_rows: usize,
+    pub chunk_size: usize,
+}
+
+impl Default for BulkConfig {
+    fn default() -> Self {
+        Self {
+            max_rows: 10_000,
+            chunk_size: 500,
+        }
+    }
+}
+
+/// What happens when an owner shortens a destination they already have.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateMode {
+    /// Return the existing link.
+    Reuse,
+    /// Reject the request; other users may still shorten the same URL.
+    PerUser,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct UrlConfig {
+    pub duplicates: DuplicateMode,
+    /// Origin the short links are served from, e.g. `https://sho.rt`.
+    pub public_base_url: String,
+    pub password_max_attempts: u32,
+    /// Failed attempts on one link from all clients together before it is
+    /// locked, so that rotating client addresses does not help guessing.
+    pub password_max_alias_attempts: u32,
+    pub password_lockout: String,
+    /// Most clients and links the password limiter keeps counts for.
+    pub password_limiter_capacity: usize,
+    /// How long a visitor keeps the A/B variant they were assigned.
+    pub variant_cookie_ttl: String,
+}
+
+impl Default for UrlConfig {
+    fn default() -> Self {
+        Self {
+            duplicates: DuplicateMode::Reuse,
+            public_base_url: "http://localhost:4200".to_string(),
+            password_max_attempts: 5,
+            password_max_alias_attempts: 50,
+            password_lockout: "15m".to_string(),
+            password_limiter_capacity: 100_000,
+            variant_cookie_ttl: "30d".to_string(),
+        }
+    }
+}
+
+impl UrlConfig {
+    pub fn get_password_lockout(&self) -> Duration {
+        self.password_lockout
+            .parse::<humantime::Duration>()
+            .map(|d| d.into())
+            .unwrap_or(Duration::from_secs(900))
+    }
+
+    pub fn get_variant_cookie_ttl(&self) -> Duration {
+        self.variant_cookie_ttl
+            .parse::<humantime::Duration>()
+            .map(|d| d.into())
+            .unwrap_or(Duration::from_secs(30 * 24 * 3600))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct PolicyConfig {
+    pub allowed_schemes: Vec<String>,
+    pub block_private_networks: bool,
+    /// Resolve destination hosts and reject those pointing at private addresses.
+    pub resolve_dns: bool,
+    /// Extra hosts that serve this shortener, besides `url.public_base_url`.
+    pub self_hosts: Vec<String>,
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        Self {
+            allowed_schemes: vec!["http".to_string(), "https".to_string()],
+            block_private_networks: true,
+            resolve_dns: false,
+            self_hosts: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct ProxyConfig {
+    /// Peers, as addresses or CIDR ranges, whose `X-Forwarded-*` headers are
+    /// trusted. Forwarded headers from anyone else are ignored.
+    pub trusted_proxies: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TxtResolverBackend {
+    Doh,
+    Static,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct StaticTxtRecord {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct DomainsConfig {
+    pub resolver: TxtResolverBackend,
+    /// DNS-over-HTTPS endpoint speaking the `application/dns-json` format.
+    pub doh_url: String,
+    pub resolver_timeout: String,
+    /// Label prepended to the custom host for the verification TXT record.
+    pub txt_record_prefix: String,
+    /// Scheme of short links served from custom domains.
+    pub link_scheme: String,
+    /// Records answered by the `static` resolver, for local development.
+    pub static_records: Vec<StaticTxtRecord>,
+}
+
+impl Default for DomainsConfig {
+    fn default() -> Self {
+        Self {
+            resolver: TxtResolverBackend::Doh,
+            doh_url: "https://cloudflare-dns.com/dns-query".to_string(),
+            resolver_timeout: "5s".to_string(),
+            txt_record_prefix: "_shortener-verify".to_string(),
+            link_scheme: "https".to_string(),
+            static_records: Vec::new(),
+        }
+    }
+}
+
+impl DomainsConfig {
+    pub fn get_resolver_timeout(&self) -> Duration {
+        self.resolver_timeout
+            .parse::<humantime::Duration>()
+            .map(|d| d.into())
+            .unwrap_or(Duration::from_secs(5))
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum JwtAlgorithm {
+    HS256,
+    RS256,
+    EdDSA,
+}
+
+/// Key material may be given inline, as `env:NAME` or as `file:PATH`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct JwtKeyConfig {
+    pub kid: String,
+    pub algorithm: JwtAlgorithm,
+    /// HS256 shared secret, at least 32 bytes.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// PEM private key for RS256/EdDSA; only the active key needs one.
+    #[serde(default)]
+    pub private_key: Option<String>,
+    /// PEM public key for RS256/EdDSA, published in the JWKS.
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct JwtConfig {
+    /// Key that signs new tokens; the other keys only verify, which keeps
+    /// tokens issued before a rotation valid until they expire.
+    pub active_kid: String,
+    pub keys: Vec<JwtKeyConfig>,
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            active_kid: "default".to_string(),
+            keys: vec![JwtKeyConfig {
+                kid: "default".to_string(),
+                algorithm: JwtAlgorithm::HS256,
+                secret: Some("env:JWT_SECRET".to_string()),
+                private_key: None,
+                public_key: None,
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OAuthProviderKind {
+    /// OpenID Connect: the identity comes from a verified ID token.
+    #[default]
+    Oidc,
+    /// GitHub OAuth apps, which have no ID token; the identity is read from
+    /// the user API.
+    Github,
+}
+
+/// Credentials may be given inline, as `env:NAME` or as `file:PATH`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct OAuthProviderConfig {
+    /// Used in `/auth/{provider}/url` and `/auth/{provider}/callback`.
+    pub name: String,
+    #[serde(default)]
+    pub kind: OAuthProviderKind,
+    /// Expected `iss` of ID tokens; discovery defaults to
+    /// `{issuer}/.well-known/openid-configuration`.
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// Other accepted `iss` values, e.g. `accounts.google.com`.
+    #[serde(default)]
+    pub extra_issuers: Vec<String>,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    #[serde(default = "default_oauth_scopes")]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub discovery_url: Option<String>,
+    /// Endpoints set here take precedence over discovered ones, so providers
+    /// without discovery (or a mock server) can be configured explicitly.
+    #[serde(default)]
+    pub authorization_url: Option<String>,
+    #[serde(default)]
+    pub token_url: Option<String>,
+    #[serde(default)]
+    pub jwks_url: Option<String>,
+    #[serde(default)]
+    pub userinfo_url: Option<String>,
+    #[serde(default = "default_pkce")]
+    pub pkce: bool,
+}
+
+pub fn default_oauth_scopes() -> Vec<String> {
+    vec![
+        "openid".to_string(),
+        "email".to_string(),
+        "profile".to_string(),
+    ]
+}
+
+fn default_pkce() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct OAuthConfig {
+    pub timeout: String,
+    /// Lifetime of the cookie holding the CSRF `state` and PKCE verifier.
+    pub state_ttl: String,
+    pub providers: Vec<OAuthProviderConfig>,
+}
+
+impl Default for OAuthConfig {
+    fn default() -> Self {
+        Self {
+            timeout: "10s".to_string(),
+            state_ttl: "10m".to_string(),
+            providers: Vec::new(),
+        }
+    }
+}
+
+impl OAuthConfig {
+    pub fn get_timeout(&self) -> Duration {
+        self.timeout
+            .parse::<humantime::Duration>()
+            .map(|d| d.into())
+            .unwrap_or(Duration::from_secs(10))
+    }
+
+    pub fn get_state_ttl(&self) -> Duration {
+        self.state_ttl
+            .parse::<humantime::Duration>()
+            .map(|d| d.into())
+            .unwrap_or(Duration::from_secs(600))
+    }
+}
+
+/// Google OAuth endpoints, turned into the `google` entry of
+/// `[[oauth.providers]]` unless one is configured there.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct GoogleConfig {
+    pub auth_url: String,
+    /// Authorization code exchange endpoint; tests point it at a mock server.
+    pub token_url: String,
+    /// JWKS that signs the returned ID tokens.
+    pub certs_url: String,
+    /// Accepted `iss` values of ID tokens.
+    pub issuers: Vec<String>,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+}
+
+impl Default for GoogleConfig {
+    fn default() -> Self {
+        Self {
+            auth_url: "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
+            token_url: "https://oauth2.googleapis.com/token".to_string(),
+            certs_url: "https://www.googleapis.com/oauth2/v3/certs".to_string(),
+            issuers: vec![
+                "https://accounts.google.com".to_string(),
+                "accounts.google.com".to_string(),
+            ],
+            client_id: "env:GOOGLE_CLIENT_ID".to_string(),
+            client_secret: "env:GOOGLE_SECRET".to_string(),
+            redirect_uri: "env:GOOGLE_URI_REDIRECT".to_string(),
+        }
+    }
+}
+
 impl HTTPServerConfig {
     pub fn get_timeout(&self) -> Duration {
         self.timeout
@@ -67,11 +585,33 @@ impl Config {
     }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            database: None,
-            server: None,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analytics_with_salt(salt: &str) -> AnalyticsConfig {
+        AnalyticsConfig {
+            ip_hash_salt: salt.to_string(),
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn placeholder_or_empty_ip_hash_salt_is_rejected() {
+        assert!(
+            analytics_with_salt("change-me")
+                .load_ip_hash_salt()
+                .is_err()
+        );
+        assert!(analytics_with_salt(" ").load_ip_hash_salt().is_err());
+        assert!(
+            analytics_with_salt("env:IP_HASH_SALT_THAT_IS_NOT_SET")
+                .load_ip_hash_salt()
+                .is_err()
+        );
+        assert_eq!(
+            analytics_with_salt("s3cr3t-salt").load_ip_hash_salt(),
+            Ok("s3cr3t-salt".to_string())
+        );
+    }
 }
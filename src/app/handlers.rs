@@ -1,21 +1,80 @@
+use crate::app::config::Config;
 use crate::app::services::Services;
+use crate::feature::analytics::handler::AnalyticsHandler;
+use crate::feature::api_key::handler::ApiKeyHandler;
+use crate::feature::api_key::service::ApiKeyService;
 use crate::feature::auth::handler::UserHandler;
-use crate::feature::url::handler::UrlHandler;
+use crate::feature::blocklist::handler::BlocklistHandler;
+use crate::feature::custom_domain::handler::DomainHandler;
+use crate::feature::oauth::handler::OAuthHandler;
+use crate::feature::redirect_rule::handler::RedirectRuleHandler;
+use crate::feature::url::handler::{UrlHandler, UrlHandlerOptions};
+use crate::feature::variant::handler::VariantHandler;
 use crate::metrics::PrometheusMetrics;
 use std::sync::Arc;
 
 pub struct Handlers {
     pub url_handler: Arc<UrlHandler>,
     pub user_handle: Arc<UserHandler>,
+    pub analytics_handler: Arc<AnalyticsHandler>,
+    pub blocklist_handler: Arc<BlocklistHandler>,
+    pub domain_handler: Arc<DomainHandler>,
+    pub rule_handler: Arc<RedirectRuleHandler>,
+    pub variant_handler: Arc<VariantHandler>,
+    pub oauth_handler: Arc<OAuthHandler>,
+    pub api_key_handler: Arc<ApiKeyHandler>,
+    /// Shared with `auth_middleware` to resolve bearer API keys.
+    pub api_key_service: Arc<ApiKeyService>,
 }
 impl Handlers {
-    pub fn new(services: Arc<Services>, metrics: Arc<PrometheusMetrics>) -> Self {
+    pub fn new(services: Arc<Services>, metrics: Arc<PrometheusMetrics>, config: &Config) -> Self {
         Self {
             url_handler: Arc::new(UrlHandler::new_handler(
                 services.url_service.clone(),
+                services.analytics_service.clone(),
+                services.rule_service.clone(),
+                services.variant_service.clone(),
                 metrics.clone(),
+                UrlHandlerOptions::from_config(config),
             )),
-            user_handle: Arc::new(UserHandler::new_handler(services.user_service.clone())),
+            user_handle: Arc::new(UserHandler::new_handler(
+                services.user_service.clone(),
+                services.token_service.clone(),
+            )),
+            analytics_handler: Arc::new(AnalyticsHandler::new_handler(
+                services.analytics_service.clone(),
+                services.url_service.clone(),
+                metrics.clone(),
+            )),
+            blocklist_handler: Arc::new(BlocklistHandler::new_handler(
+                services.blocklist_service.clone(),
+                metrics.clone(),
+            )),
+            domain_handler: Arc::new(DomainHandler::new_handler(
+                services.domain_service.clone(),
+                metrics.clone(),
+            )),
+            rule_handler: Arc::new(RedirectRuleHandler::new_handler(
+                services.rule_service.clone(),
+                services.url_service.clone(),
+                metrics.clone(),
+            )),
+            variant_handler: Arc::new(VariantHandler::new_handler(
+                services.variant_service.clone(),
+                services.url_service.clone(),
+                metrics.clone(),
+            )),
+            oauth_handler: Arc::new(OAuthHandler::new_handler(
+                services.oauth_service.clone(),
+                services.token_service.clone(),
+                metrics.clone(),
+                config.oauth.get_state_ttl(),
+            )),
+            api_key_handler: Arc::new(ApiKeyHandler::new_handler(
+                services.api_key_service.clone(),
+                metrics.clone(),
+            )),
+            api_key_service: services.api_key_service.clone(),
         }
     }
 }
@@ -1,5 +1,13 @@
+use crate::feature::analytics::repository::ClickRepository;
+use crate::feature::api_key::repository::ApiKeyRepository;
 use crate::feature::auth::repository::UserRepository;
+use crate::feature::auth::token_repository::RefreshTokenRepository;
+use crate::feature::blocklist::repository::BlocklistRepository;
+use crate::feature::custom_domain::repository::DomainRepository;
+use crate::feature::oauth::repository::OAuthIdentityRepository;
+use crate::feature::redirect_rule::repository::RedirectRuleRepository;
 use crate::feature::url::repository::UrlRepository;
+use crate::feature::variant::repository::VariantRepository;
 use sqlx::{Pool, Postgres};
 use std::sync::Arc;
 
@@ -7,6 +15,14 @@ use std::sync::Arc;
 pub struct Repositories {
     pub url_repository: Arc<UrlRepository>,
     pub user_repository: Arc<UserRepository>,
+    pub token_repository: Arc<RefreshTokenRepository>,
+    pub click_repository: Arc<ClickRepository>,
+    pub blocklist_repository: Arc<BlocklistRepository>,
+    pub domain_repository: Arc<DomainRepository>,
+    pub rule_repository: Arc<RedirectRuleRepository>,
+    pub variant_repository: Arc<VariantRepository>,
+    pub oauth_identity_repository: Arc<OAuthIdentityRepository>,
+    pub api_key_repository: Arc<ApiKeyRepository>,
 }
 
 impl Repositories {
@@ -14,6 +30,22 @@ impl Repositories {
         Self {
             url_repository: Arc::new(UrlRepository::new_url_repository(pg.clone())),
             user_repository: Arc::new(UserRepository::new_user_repository(pg.clone())),
+            token_repository: Arc::new(RefreshTokenRepository::new_refresh_token_repository(
+                pg.clone(),
+            )),
+            click_repository: Arc::new(ClickRepository::new_click_repository(pg.clone())),
+            blocklist_repository: Arc::new(BlocklistRepository::new_blocklist_repository(
+                pg.clone(),
+            )),
+            domain_repository: Arc::new(DomainRepository::new_domain_repository(pg.clone())),
+            rule_repository: Arc::new(RedirectRuleRepository::new_redirect_rule_repository(
+                pg.clone(),
+            )),
+            variant_repository: Arc::new(VariantRepository::new_variant_repository(pg.clone())),
+            oauth_identity_repository: Arc::new(
+                OAuthIdentityRepository::new_oauth_identity_repository(pg.clone()),
+            ),
+            api_key_repository: Arc::new(ApiKeyRepository::new_api_key_repository(pg.clone())),
         }
     }
 }
@@ -1,19 +1,110 @@
+use crate::app::config::Config;
 use crate::app::repositories::Repositories;
+use crate::feature::analytics::geoip::new_geoip_provider;
+use crate::feature::analytics::service::AnalyticsService;
+use crate::feature::analytics::writer::ClickWriter;
+use crate::feature::api_key::service::ApiKeyService;
 use crate::feature::auth::service::UserService;
+use crate::feature::auth::token_service::TokenService;
+use crate::feature::blocklist::service::BlocklistService;
+use crate::feature::custom_domain::resolver::new_txt_resolver;
+use crate::feature::custom_domain::service::DomainService;
+use crate::feature::oauth::provider::OAuthProviderRegistry;
+use crate::feature::oauth::service::OAuthService;
+use crate::feature::redirect_rule::service::RedirectRuleService;
+use crate::feature::url::generator::AliasGenerator;
+use crate::feature::url::policy::new_url_policy_chain;
 use crate::feature::url::service::UrlService;
+use crate::feature::variant::service::VariantService;
+use crate::metrics::PrometheusMetrics;
+use crate::utils::cache::new_cache;
+use crate::utils::constants::{RULES_CACHE_NAMESPACE, VARIANTS_CACHE_NAMESPACE};
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct Services {
     pub url_service: Arc<UrlService>,
     pub user_service: Arc<UserService>,
+    pub token_service: Arc<TokenService>,
+    pub analytics_service: Arc<AnalyticsService>,
+    pub blocklist_service: Arc<BlocklistService>,
+    pub domain_service: Arc<DomainService>,
+    pub rule_service: Arc<RedirectRuleService>,
+    pub variant_service: Arc<VariantService>,
+    pub oauth_service: Arc<OAuthService>,
+    pub api_key_service: Arc<ApiKeyService>,
 }
 
 impl Services {
-    pub fn new(repo: Arc<Repositories>) -> Self {
+    pub async fn new(
+        repo: Arc<Repositories>,
+        config: &Config,
+        metrics: Arc<PrometheusMetrics>,
+    ) -> Self {
+        let alias_generator = Arc::new(AliasGenerator::new(
+            config.alias.clone(),
+            repo.url_repository.clone(),
+        ));
+        let click_writer = Arc::new(ClickWriter::spawn(
+            &config.analytics,
+            repo.click_repository.clone(),
+            metrics.clone(),
+        ));
+        let url_cache = new_cache(&config.cache, "").await;
+        let url_policy = Arc::new(new_url_policy_chain(
+            &config.policy,
+            &config.url,
+            repo.blocklist_repository.clone(),
+            repo.domain_repository.clone(),
+        ));
+        let rule_service = Arc::new(RedirectRuleService::new(
+            repo.rule_repository.clone(),
+            url_policy.clone(),
+            new_cache(&config.cache, RULES_CACHE_NAMESPACE).await,
+        ));
+        let variant_service = Arc::new(VariantService::new(
+            repo.variant_repository.clone(),
+            url_policy.clone(),
+            new_cache(&config.cache, VARIANTS_CACHE_NAMESPACE).await,
+        ));
         Self {
-            url_service: Arc::new(UrlService::new(repo.url_repository.clone())),
+            url_service: Arc::new(UrlService::new(
+                repo.url_repository.clone(),
+                alias_generator,
+                url_cache,
+                metrics,
+                url_policy,
+                repo.domain_repository.clone(),
+                config,
+            )),
             user_service: Arc::new(UserService::new_service(repo.user_repository.clone())),
+            token_service: Arc::new(TokenService::new(
+                repo.token_repository.clone(),
+                repo.user_repository.clone(),
+            )),
+            analytics_service: Arc::new(AnalyticsService::new(
+                repo.click_repository.clone(),
+                click_writer,
+                new_geoip_provider(&config.analytics),
+                config
+                    .analytics
+                    .load_ip_hash_salt()
+                    .expect("Could not load the IP hash salt"),
+            )),
+            blocklist_service: Arc::new(BlocklistService::new(repo.blocklist_repository.clone())),
+            domain_service: Arc::new(DomainService::new(
+                repo.domain_repository.clone(),
+                new_txt_resolver(&config.domains),
+                config.domains.clone(),
+            )),
+            rule_service,
+            variant_service,
+            oauth_service: Arc::new(OAuthService::new(
+                OAuthProviderRegistry::from_config(&config.oauth, config.google.as_ref()),
+                repo.oauth_identity_repository.clone(),
+                repo.user_repository.clone(),
+            )),
+            api_key_service: Arc::new(ApiKeyService::new(repo.api_key_repository.clone())),
         }
     }
 }
@@ -1,11 +1,184 @@
+use crate::utils::constants::LINK_PASSWORD_PARAM;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Debug, FromRow, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
 pub struct Url {
     pub id: Uuid,
     pub alias: String,
     pub url: String,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_clicks: Option<i64>,
+    pub clicks: i64,
+    pub enabled: bool,
+    #[serde(default, skip_serializing)]
+    #[schema(ignore)]
+    pub password_hash: Option<String>,
+    pub interstitial: bool,
+    pub countdown_seconds: Option<i32>,
+    /// Custom domain serving the link; `None` for the shortener's own host.
+    pub domain_id: Option<Uuid>,
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub utm_term: Option<String>,
+    pub utm_content: Option<String>,
+    /// Forward the query string of the short-link request to the destination.
+    pub forward_query: bool,
+}
+
+impl Url {
+    pub fn is_expired(&self) -> bool {
+        let expired_by_time = self.expires_at.is_some_and(|at| at <= Utc::now());
+        let expired_by_clicks = self.max_clicks.is_some_and(|max| self.clicks >= max);
+        expired_by_time || expired_by_clicks
+    }
+
+    pub fn is_protected(&self) -> bool {
+        self.password_hash.is_some()
+    }
+
+    fn utm_params(&self) -> Vec<(String, String)> {
+        [
+            ("utm_source", &self.utm_source),
+            ("utm_medium", &self.utm_medium),
+            ("utm_campaign", &self.utm_campaign),
+            ("utm_term", &self.utm_term),
+            ("utm_content", &self.utm_content),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.clone().map(|value| (key.to_string(), value)))
+        .collect()
+    }
+
+    /// Redirect target with the forwarded request query and the UTM fields
+    /// merged in. UTM fields win over forwarded parameters, which win over the
+    /// destination's own; the unlock `password` parameter is never forwarded.
+    pub fn destination(&self, request_query: Option<&str>) -> String {
+        self.destination_with(&self.url, request_query)
+    }
+
+    /// Same as `destination`, but for another target such as the
+    /// destination of a matched redirect rule.
+    pub fn destination_with(&self, target: &str, request_query: Option<&str>) -> String {
+        let utm = self.utm_params();
+        let forwarded: Vec<(String, String)> = request_query
+            .filter(|_| self.forward_query)
+            .map(|query| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .into_owned()
+                    .filter(|(key, _)| {
+                        key != LINK_PASSWORD_PARAM && !utm.iter().any(|(utm_key, _)| utm_key == key)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        if utm.is_empty() && forwarded.is_empty() {
+            return target.to_string();
+        }
+        let Ok(mut parsed) = url::Url::parse(target) else {
+            return target.to_string();
+        };
+        let overrides: Vec<(String, String)> = forwarded.into_iter().chain(utm).collect();
+        let kept: Vec<(String, String)> = parsed
+            .query_pairs()
+            .into_owned()
+            .filter(|(key, _)| {
+                !overrides
+                    .iter()
+                    .any(|(override_key, _)| override_key == key)
+            })
+            .collect();
+        parsed
+            .query_pairs_mut()
+            .clear()
+            .extend_pairs(kept)
+            .extend_pairs(overrides);
+        parsed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(target: &str) -> Url {
+        Url {
+            id: Uuid::new_v4(),
+            alias: "abc".to_string(),
+            url: target.to_string(),
+            user_id: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: None,
+            expires_at: None,
+            max_clicks: None,
+            clicks: 0,
+            enabled: true,
+            password_hash: None,
+            interstitial: false,
+            countdown_seconds: None,
+            domain_id: None,
+            utm_source: None,
+            utm_medium: None,
+            utm_campaign: None,
+            utm_term: None,
+            utm_content: None,
+            forward_query: false,
+        }
+    }
+
+    #[test]
+    fn destination_is_unchanged_without_utm_or_forwarding() {
+        let link = url("https://example.com/page?a=1");
+        assert_eq!(
+            link.destination(Some("b=2")),
+            "https://example.com/page?a=1"
+        );
+    }
+
+    #[test]
+    fn utm_fields_override_the_destination_query() {
+        let link = Url {
+            utm_source: Some("newsletter".to_string()),
+            utm_campaign: Some("spring sale".to_string()),
+            ..url("https://example.com/?utm_source=old&a=1")
+        };
+        assert_eq!(
+            link.destination(None),
+            "https://example.com/?a=1&utm_source=newsletter&utm_campaign=spring+sale"
+        );
+    }
+
+    #[test]
+    fn forwarded_query_skips_the_password_and_utm_keys() {
+        let link = Url {
+            forward_query: true,
+            utm_source: Some("qr".to_string()),
+            ..url("https://example.com/?a=1&b=1")
+        };
+        let query = format!("b=2&{}=secret&utm_source=spoofed&c=3", LINK_PASSWORD_PARAM);
+        assert_eq!(
+            link.destination(Some(&query)),
+            "https://example.com/?a=1&b=2&c=3&utm_source=qr"
+        );
+    }
+
+    #[test]
+    fn destination_with_applies_the_link_settings_to_another_target() {
+        let link = Url {
+            utm_medium: Some("social".to_string()),
+            ..url("https://example.com/")
+        };
+        assert_eq!(
+            link.destination_with("https://m.example.com/app", None),
+            "https://m.example.com/app?utm_medium=social"
+        );
+        assert_eq!(link.destination_with("not a url", None), "not a url");
+    }
 }
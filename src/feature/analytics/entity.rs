@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct ClickEvent {
+    pub url_id: Uuid,
+    pub clicked_at: DateTime<Utc>,
+    pub referrer: Option<String>,
+    pub user_agent: Option<String>,
+    pub country: Option<String>,
+    pub ip_hash: String,
+    pub increment_counter: bool,
+    pub variant_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsInterval {
+    Hour,
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl StatsInterval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatsInterval::Hour => "hour",
+            StatsInterval::Day => "day",
+            StatsInterval::Week => "week",
+            StatsInterval::Month => "month",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct StatsQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[param(inline)]
+    pub interval: StatsInterval,
+}
+
+#[derive(Debug, Serialize, ToSchema, sqlx::FromRow)]
+pub struct StatsBucket {
+    pub bucket: DateTime<Utc>,
+    pub clicks: i64,
+    pub unique_visitors: i64,
+}
+
+/// Clicks served by one A/B variant of the link.
+#[derive(Debug, Serialize, ToSchema, sqlx::FromRow)]
+pub struct VariantClicks {
+    pub variant_id: Uuid,
+    pub clicks: i64,
+    pub unique_visitors: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LinkStats {
+    pub url_id: Uuid,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub interval: StatsInterval,
+    pub total_clicks: i64,
+    pub unique_visitors: i64,
+    pub buckets: Vec<StatsBucket>,
+    pub variants: Vec<VariantClicks>,
+}
+
+pub const CLICKS_TABLE: &str = "clicks";
+pub const CLICKS_URL_ID: &str = "url_id";
+pub const CLICKS_CLICKED_AT: &str = "clicked_at";
+pub const CLICKS_REFERRER: &str = "referrer";
+pub const CLICKS_USER_AGENT: &str = "user_agent";
+pub const CLICKS_COUNTRY: &str = "country";
+pub const CLICKS_IP_HASH: &str = "ip_hash";
+pub const CLICKS_VARIANT_ID: &str = "variant_id";
@@ -0,0 +1,57 @@
+use crate::app::config::{AnalyticsConfig, GeoIpBackend};
+use ipnet::IpNet;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+pub trait GeoIpProvider: Send + Sync {
+    fn lookup_country(&self, ip: IpAddr) -> Option<String>;
+}
+
+pub struct NoopGeoIp;
+
+impl GeoIpProvider for NoopGeoIp {
+    fn lookup_country(&self, _ip: IpAddr) -> Option<String> {
+        None
+    }
+}
+
+/// Resolves countries from a static list of CIDR ranges taken from the config.
+pub struct StaticGeoIp {
+    ranges: Vec<(IpNet, String)>,
+}
+
+impl StaticGeoIp {
+    pub fn new(ranges: Vec<(IpNet, String)>) -> Self {
+        Self { ranges }
+    }
+}
+
+impl GeoIpProvider for StaticGeoIp {
+    fn lookup_country(&self, ip: IpAddr) -> Option<String> {
+        self.ranges
+            .iter()
+            .filter(|(net, _)| net.contains(&ip))
+            .max_by_key(|(net, _)| net.prefix_len())
+            .map(|(_, country)| country.clone())
+    }
+}
+
+pub fn new_geoip_provider(config: &AnalyticsConfig) -> Arc<dyn GeoIpProvider> {
+    match config.geoip {
+        GeoIpBackend::None => Arc::new(NoopGeoIp),
+        GeoIpBackend::Static => {
+            let ranges = config
+                .geoip_ranges
+                .iter()
+                .filter_map(|range| match range.cidr.parse::<IpNet>() {
+                    Ok(net) => Some((net, range.country.to_uppercase())),
+                    Err(e) => {
+                        eprintln!("❌ Invalid GeoIP range {}: {}", range.cidr, e);
+                        None
+                    }
+                })
+                .collect();
+            Arc::new(StaticGeoIp::new(ranges))
+        }
+    }
+}
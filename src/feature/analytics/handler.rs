@@ -0,0 +1,97 @@
+use crate::feature::analytics::entity::{LinkStats, StatsQuery};
+use crate::feature::analytics::service::{AnalyticsService, AnalyticsServiceTrait};
+use crate::feature::url::entity::UrlError;
+use crate::feature::url::service::{UrlService, UrlServiceTrait};
+use crate::metrics::PrometheusMetrics;
+use crate::servers::http::middleware::UserJWT;
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct AnalyticsHandler {
+    analytics_service: Arc<AnalyticsService>,
+    url_service: Arc<UrlService>,
+    metrics: Arc<PrometheusMetrics>,
+}
+
+impl AnalyticsHandler {
+    pub fn new_handler(
+        analytics_service: Arc<AnalyticsService>,
+        url_service: Arc<UrlService>,
+        metrics: Arc<PrometheusMetrics>,
+    ) -> Self {
+        Self {
+            analytics_service,
+            url_service,
+            metrics,
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/private/url/{id}/stats",
+    params(
+        ("id" = Uuid, Path, description = "URL ID"),
+        StatsQuery
+    ),
+    responses(
+        (status = 200, description = "Click statistics for the link", body = LinkStats),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Analytics"
+)]
+pub async fn get_url_stats_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<AnalyticsHandler>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<StatsQuery>,
+) -> Response {
+    match handlers
+        .url_service
+        .get_owned_url(id, user_jwt.id, user_jwt.is_admin())
+        .await
+    {
+        Ok(_) => {}
+        Err(UrlError::NotFound) => {
+            return (StatusCode::NOT_FOUND, Json("URL not found".to_string())).into_response();
+        }
+        Err(UrlError::Forbidden) => {
+            return (StatusCode::FORBIDDEN, Json("Forbidden".to_string())).into_response();
+        }
+        Err(_) => {
+            handlers
+                .metrics
+                .inc_errors("database_error", "analytics_handler");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json("Error retrieving URL".to_string()),
+            )
+                .into_response();
+        }
+    }
+    match handlers.analytics_service.get_stats(id, query).await {
+        Ok(stats) => Json(stats).into_response(),
+        Err(_) => {
+            handlers
+                .metrics
+                .inc_errors("database_error", "analytics_handler");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json("Error retrieving statistics".to_string()),
+            )
+                .into_response()
+        }
+    }
+}
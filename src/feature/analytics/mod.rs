@@ -0,0 +1,6 @@
+pub mod entity;
+pub mod geoip;
+pub mod handler;
+pub mod repository;
+pub mod service;
+pub mod writer;
@@ -0,0 +1,221 @@
+use crate::feature::analytics::entity::{
+    CLICKS_CLICKED_AT, CLICKS_COUNTRY, CLICKS_IP_HASH, CLICKS_REFERRER, CLICKS_TABLE,
+    CLICKS_URL_ID, CLICKS_USER_AGENT, CLICKS_VARIANT_ID, ClickEvent, StatsBucket, StatsInterval,
+    VariantClicks,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sea_query::{Alias, Expr, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::{Error, Pool, Postgres, query_as_with, query_with};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ClickRepositoryTrait: Send + Sync {
+    async fn insert_clicks(&self, clicks: Vec<ClickEvent>) -> Result<u64, Error>;
+    async fn increment_url_clicks(&self, counts: Vec<(Uuid, i64)>) -> Result<(), Error>;
+    async fn increment_variant_clicks(&self, counts: Vec<(Uuid, i64)>) -> Result<(), Error>;
+    async fn get_totals(
+        &self,
+        url_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<(i64, i64), Error>;
+    async fn get_buckets(
+        &self,
+        url_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        interval: StatsInterval,
+    ) -> Result<Vec<StatsBucket>, Error>;
+    async fn get_variant_totals(
+        &self,
+        url_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<VariantClicks>, Error>;
+}
+
+#[derive(Clone)]
+pub struct ClickRepository {
+    primary_db: Pool<Postgres>,
+}
+
+impl ClickRepository {
+    pub fn new_click_repository(primary_db: Pool<Postgres>) -> Self {
+        Self { primary_db }
+    }
+}
+
+#[async_trait]
+impl ClickRepositoryTrait for ClickRepository {
+    async fn insert_clicks(&self, clicks: Vec<ClickEvent>) -> Result<u64, Error> {
+        if clicks.is_empty() {
+            return Ok(0);
+        }
+        let mut insert = Query::insert();
+        insert.into_table(Alias::new(CLICKS_TABLE)).columns([
+            Alias::new(CLICKS_URL_ID),
+            Alias::new(CLICKS_CLICKED_AT),
+            Alias::new(CLICKS_REFERRER),
+            Alias::new(CLICKS_USER_AGENT),
+            Alias::new(CLICKS_COUNTRY),
+            Alias::new(CLICKS_IP_HASH),
+            Alias::new(CLICKS_VARIANT_ID),
+        ]);
+        for click in clicks {
+            insert.values_panic([
+                click.url_id.into(),
+                click.clicked_at.into(),
+                click.referrer.into(),
+                click.user_agent.into(),
+                click.country.into(),
+                click.ip_hash.into(),
+                click.variant_id.into(),
+            ]);
+        }
+        let (sql, values) = insert.build_sqlx(PostgresQueryBuilder);
+        let result = query_with(&sql, values)
+            .execute(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error inserting clicks: {:?}", err);
+                err
+            })?;
+        Ok(result.rows_affected())
+    }
+
+    async fn increment_url_clicks(&self, counts: Vec<(Uuid, i64)>) -> Result<(), Error> {
+        if counts.is_empty() {
+            return Ok(());
+        }
+        let (ids, increments): (Vec<Uuid>, Vec<i64>) = counts.into_iter().unzip();
+        sqlx::query(
+            "UPDATE url SET clicks = url.clicks + v.n \
+             FROM UNNEST($1::uuid[], $2::bigint[]) AS v(id, n) \
+             WHERE url.id = v.id",
+        )
+        .bind(ids)
+        .bind(increments)
+        .execute(&self.primary_db)
+        .await
+        .map_err(|err| {
+            eprintln!("❌ Error incrementing url clicks: {:?}", err);
+            err
+        })?;
+        Ok(())
+    }
+
+    async fn increment_variant_clicks(&self, counts: Vec<(Uuid, i64)>) -> Result<(), Error> {
+        if counts.is_empty() {
+            return Ok(());
+        }
+        let (ids, increments): (Vec<Uuid>, Vec<i64>) = counts.into_iter().unzip();
+        sqlx::query(
+            "UPDATE url_variants SET clicks = url_variants.clicks + v.n \
+             FROM UNNEST($1::uuid[], $2::bigint[]) AS v(id, n) \
+             WHERE url_variants.id = v.id",
+        )
+        .bind(ids)
+        .bind(increments)
+        .execute(&self.primary_db)
+        .await
+        .map_err(|err| {
+            eprintln!("❌ Error incrementing variant clicks: {:?}", err);
+            err
+        })?;
+        Ok(())
+    }
+
+    async fn get_totals(
+        &self,
+        url_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<(i64, i64), Error> {
+        let (sql, values) = Query::select()
+            .expr(Expr::cust("COUNT(*)"))
+            .expr(Expr::cust("COUNT(DISTINCT ip_hash)"))
+            .from(CLICKS_TABLE)
+            .and_where(Expr::col(CLICKS_URL_ID).eq(url_id))
+            .and_where(Expr::col(CLICKS_CLICKED_AT).gte(from))
+            .and_where(Expr::col(CLICKS_CLICKED_AT).lt(to))
+            .build_sqlx(PostgresQueryBuilder);
+        let totals = query_as_with::<_, (i64, i64), _>(&sql, values)
+            .fetch_one(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching click totals: {:?}", err);
+                err
+            })?;
+        Ok(totals)
+    }
+
+    async fn get_buckets(
+        &self,
+        url_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        interval: StatsInterval,
+    ) -> Result<Vec<StatsBucket>, Error> {
+        let (sql, values) = Query::select()
+            .expr_as(
+                Expr::cust_with_values("date_trunc($1, clicked_at)", [interval.as_str()]),
+                Alias::new("bucket"),
+            )
+            .expr_as(Expr::cust("COUNT(*)"), Alias::new("clicks"))
+            .expr_as(
+                Expr::cust("COUNT(DISTINCT ip_hash)"),
+                Alias::new("unique_visitors"),
+            )
+            .from(CLICKS_TABLE)
+            .and_where(Expr::col(CLICKS_URL_ID).eq(url_id))
+            .and_where(Expr::col(CLICKS_CLICKED_AT).gte(from))
+            .and_where(Expr::col(CLICKS_CLICKED_AT).lt(to))
+            .add_group_by([Expr::cust("1")])
+            .order_by_expr(Expr::cust("1"), Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+        let buckets = query_as_with::<_, StatsBucket, _>(&sql, values)
+            .fetch_all(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching click buckets: {:?}", err);
+                err
+            })?;
+        Ok(buckets)
+    }
+
+    async fn get_variant_totals(
+        &self,
+        url_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<VariantClicks>, Error> {
+        let (sql, values) = Query::select()
+            .column(CLICKS_VARIANT_ID)
+            .expr_as(Expr::cust("COUNT(*)"), Alias::new("clicks"))
+            .expr_as(
+                Expr::cust("COUNT(DISTINCT ip_hash)"),
+                Alias::new("unique_visitors"),
+            )
+            .from(CLICKS_TABLE)
+            .and_where(Expr::col(CLICKS_URL_ID).eq(url_id))
+            .and_where(Expr::col(CLICKS_VARIANT_ID).is_not_null())
+            .and_where(Expr::col(CLICKS_CLICKED_AT).gte(from))
+            .and_where(Expr::col(CLICKS_CLICKED_AT).lt(to))
+            .group_by_col(CLICKS_VARIANT_ID)
+            .order_by_expr(Expr::cust("2"), Order::Desc)
+            .build_sqlx(PostgresQueryBuilder);
+        let variants = query_as_with::<_, VariantClicks, _>(&sql, values)
+            .fetch_all(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching variant clicks: {:?}", err);
+                err
+            })?;
+        Ok(variants)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::feature::analytics::entity::{ClickEvent, LinkStats, StatsQuery};
+use crate::feature::analytics::geoip::GeoIpProvider;
+use crate::feature::analytics::repository::{ClickRepository, ClickRepositoryTrait};
+use crate::feature::analytics::writer::ClickWriter;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct ClickContext {
+    pub ip: Option<IpAddr>,
+    pub referrer: Option<String>,
+    pub user_agent: Option<String>,
+    pub variant_id: Option<Uuid>,
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait AnalyticsServiceTrait: Send + Sync {
+    fn record_click(&self, url_id: Uuid, increment_counter: bool, context: ClickContext);
+    async fn get_stats(&self, url_id: Uuid, query: StatsQuery) -> Result<LinkStats, sqlx::Error>;
+}
+
+pub struct AnalyticsService {
+    click_repository: Arc<ClickRepository>,
+    click_writer: Arc<ClickWriter>,
+    geoip: Arc<dyn GeoIpProvider>,
+    ip_hash_salt: String,
+}
+
+impl AnalyticsService {
+    pub fn new(
+        click_repository: Arc<ClickRepository>,
+        click_writer: Arc<ClickWriter>,
+        geoip: Arc<dyn GeoIpProvider>,
+        ip_hash_salt: String,
+    ) -> Self {
+        Self {
+            click_repository,
+            click_writer,
+            geoip,
+            ip_hash_salt,
+        }
+    }
+
+    pub fn country_for(&self, ip: Option<IpAddr>) -> Option<String> {
+        ip.and_then(|ip| self.geoip.lookup_country(ip))
+    }
+
+    fn hash_ip(&self, ip: Option<IpAddr>) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.ip_hash_salt.as_bytes());
+        match ip {
+            Some(ip) => hasher.update(ip.to_string().as_bytes()),
+            None => hasher.update(b"unknown"),
+        }
+        hex::encode(hasher.finalize())
+    }
+}
+
+#[async_trait]
+impl AnalyticsServiceTrait for AnalyticsService {
+    fn record_click(&self, url_id: Uuid, increment_counter: bool, context: ClickContext) {
+        self.click_writer.record(ClickEvent {
+            url_id,
+            clicked_at: Utc::now(),
+            referrer: context.referrer,
+            user_agent: context.user_agent,
+            country: self.country_for(context.ip),
+            ip_hash: self.hash_ip(context.ip),
+            increment_counter,
+            variant_id: context.variant_id,
+        });
+    }
+
+    async fn get_stats(&self, url_id: Uuid, query: StatsQuery) -> Result<LinkStats, sqlx::Error> {
+        let to = query.to.unwrap_or_else(Utc::now);
+        let from = query.from.unwrap_or(to - Duration::days(30));
+        let (total_clicks, unique_visitors) =
+            self.click_repository.get_totals(url_id, from, to).await?;
+        let buckets = self
+            .click_repository
+            .get_buckets(url_id, from, to, query.interval)
+            .await?;
+        let variants = self
+            .click_repository
+            .get_variant_totals(url_id, from, to)
+            .await?;
+        Ok(LinkStats {
+            url_id,
+            from,
+            to,
+            interval: query.interval,
+            total_clicks,
+            unique_visitors,
+            buckets,
+            variants,
+        })
+    }
+}
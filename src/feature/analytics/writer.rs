@@ -0,0 +1,116 @@
+use crate::app::config::AnalyticsConfig;
+use crate::feature::analytics::entity::ClickEvent;
+use crate::feature::analytics::repository::{ClickRepository, ClickRepositoryTrait};
+use crate::metrics::PrometheusMetrics;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc::{self, Receiver, Sender, error::TrySendError};
+use tokio::time::{MissedTickBehavior, interval};
+
+/// Buffers click events in memory and writes them to the `clicks` table in
+/// batches, so the redirect path never waits for the database.
+pub struct ClickWriter {
+    sender: Sender<ClickEvent>,
+    metrics: Arc<PrometheusMetrics>,
+}
+
+impl ClickWriter {
+    pub fn spawn(
+        config: &AnalyticsConfig,
+        click_repository: Arc<ClickRepository>,
+        metrics: Arc<PrometheusMetrics>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(config.channel_capacity.max(1));
+        tokio::spawn(run_writer(
+            receiver,
+            click_repository,
+            metrics.clone(),
+            config.batch_size.max(1),
+            config.get_flush_interval(),
+        ));
+        Self { sender, metrics }
+    }
+
+    pub fn record(&self, event: ClickEvent) {
+        match self.sender.try_send(event) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.metrics.inc_errors("click_dropped", "click_writer");
+            }
+            Err(TrySendError::Closed(_)) => {
+                self.metrics
+                    .inc_errors("click_writer_closed", "click_writer");
+            }
+        }
+    }
+}
+
+async fn run_writer(
+    mut receiver: Receiver<ClickEvent>,
+    click_repository: Arc<ClickRepository>,
+    metrics: Arc<PrometheusMetrics>,
+    batch_size: usize,
+    flush_interval: std::time::Duration,
+) {
+    let mut buffer = Vec::with_capacity(batch_size);
+    let mut ticker = interval(flush_interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            event = receiver.recv() => match event {
+                Some(event) => {
+                    buffer.push(event);
+                    if buffer.len() >= batch_size {
+                        flush(&mut buffer, &click_repository, &metrics).await;
+                    }
+                }
+                None => {
+                    flush(&mut buffer, &click_repository, &metrics).await;
+                    log::warn!("Click writer stopped");
+                    return;
+                }
+            },
+            _ = ticker.tick() => {
+                flush(&mut buffer, &click_repository, &metrics).await;
+            }
+        }
+    }
+}
+
+async fn flush(
+    buffer: &mut Vec<ClickEvent>,
+    click_repository: &ClickRepository,
+    metrics: &PrometheusMetrics,
+) {
+    if buffer.is_empty() {
+        return;
+    }
+    let batch = std::mem::take(buffer);
+    let size = batch.len() as u64;
+    let mut counts: HashMap<_, i64> = HashMap::new();
+    for event in batch.iter().filter(|event| event.increment_counter) {
+        *counts.entry(event.url_id).or_default() += 1;
+    }
+    let mut variant_counts: HashMap<_, i64> = HashMap::new();
+    for variant_id in batch.iter().filter_map(|event| event.variant_id) {
+        *variant_counts.entry(variant_id).or_default() += 1;
+    }
+    match click_repository.insert_clicks(batch).await {
+        Ok(_) => metrics.inc_clicks_recorded(size),
+        Err(_) => metrics.inc_errors("database_error", "click_writer"),
+    }
+    if click_repository
+        .increment_url_clicks(counts.into_iter().collect())
+        .await
+        .is_err()
+    {
+        metrics.inc_errors("database_error", "click_writer");
+    }
+    if click_repository
+        .increment_variant_clicks(variant_counts.into_iter().collect())
+        .await
+        .is_err()
+    {
+        metrics.inc_errors("database_error", "click_writer");
+    }
+}
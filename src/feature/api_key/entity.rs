@@ -0,0 +1,179 @@
+use crate::feature::auth::entity::UserRole;
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::types::Json;
+use sqlx::{Error as SqlxError, FromRow};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiKeyScope {
+    /// `GET` and `HEAD` requests.
+    Read,
+    /// Requests that change data.
+    Write,
+    /// Keeps the admin role of an admin owner; other keys act as a user.
+    Admin,
+}
+
+/// Whether a key with these scopes may make a request with this method.
+pub fn scopes_allow(scopes: &[ApiKeyScope], method: &Method) -> bool {
+    let required = if method == Method::GET || method == Method::HEAD {
+        ApiKeyScope::Read
+    } else {
+        ApiKeyScope::Write
+    };
+    scopes.contains(&required)
+}
+
+/// Role a request made with the key acts as.
+pub fn effective_role(scopes: &[ApiKeyScope], role: UserRole) -> UserRole {
+    if role == UserRole::Admin && !scopes.contains(&ApiKeyScope::Admin) {
+        UserRole::User
+    } else {
+        role
+    }
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    /// Start of the key, to tell keys apart.
+    #[schema(example = "usk_3fK9aQ2x")]
+    pub prefix: String,
+    #[schema(value_type = Vec<ApiKeyScope>)]
+    pub scopes: Json<Vec<ApiKeyScope>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A new key together with its secret, which is only returned once.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateApiKeyDTO {
+    #[validate(length(min = 1, max = 100))]
+    #[schema(example = "ci-pipeline")]
+    pub name: String,
+    #[validate(length(min = 1))]
+    pub scopes: Vec<ApiKeyScope>,
+    /// The key never expires when omitted.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Active key resolved from a bearer token, with its owner's role.
+#[derive(Debug, Clone, FromRow)]
+pub struct ApiKeyOwner {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub role: UserRole,
+    pub scopes: Json<Vec<ApiKeyScope>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub enum ApiKeyError {
+    NotFound,
+    /// Only admins may create keys with the admin scope.
+    Forbidden,
+    InvalidExpiry,
+    Db(SqlxError),
+}
+
+impl std::fmt::Display for ApiKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiKeyError::NotFound => write!(f, "api key not found"),
+            ApiKeyError::Forbidden => write!(f, "admin scope requires an admin account"),
+            ApiKeyError::InvalidExpiry => write!(f, "expiry must be in the future"),
+            ApiKeyError::Db(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl From<SqlxError> for ApiKeyError {
+    fn from(err: SqlxError) -> Self {
+        ApiKeyError::Db(err)
+    }
+}
+
+pub const API_KEYS_TABLE: &str = "api_keys";
+pub const API_KEYS_ID: &str = "id";
+pub const API_KEYS_USER_ID: &str = "user_id";
+pub const API_KEYS_NAME: &str = "name";
+pub const API_KEYS_PREFIX: &str = "prefix";
+pub const API_KEYS_KEY_HASH: &str = "key_hash";
+pub const API_KEYS_SCOPES: &str = "scopes";
+pub const API_KEYS_EXPIRES_AT: &str = "expires_at";
+pub const API_KEYS_LAST_USED_AT: &str = "last_used_at";
+pub const API_KEYS_CREATED_AT: &str = "created_at";
+pub const API_KEYS_REVOKED_AT: &str = "revoked_at";
+pub const API_KEYS_COLUMNS: [&str; 7] = [
+    API_KEYS_ID,
+    API_KEYS_NAME,
+    API_KEYS_PREFIX,
+    API_KEYS_SCOPES,
+    API_KEYS_EXPIRES_AT,
+    API_KEYS_LAST_USED_AT,
+    API_KEYS_CREATED_AT,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_scope_only_allows_safe_methods() {
+        let read = [ApiKeyScope::Read];
+        assert!(scopes_allow(&read, &Method::GET));
+        assert!(scopes_allow(&read, &Method::HEAD));
+        for method in [Method::POST, Method::PATCH, Method::PUT, Method::DELETE] {
+            assert!(!scopes_allow(&read, &method), "{} is allowed", method);
+        }
+    }
+
+    #[test]
+    fn write_scope_does_not_imply_read() {
+        let write = [ApiKeyScope::Write];
+        assert!(scopes_allow(&write, &Method::POST));
+        assert!(scopes_allow(&write, &Method::DELETE));
+        assert!(!scopes_allow(&write, &Method::GET));
+        assert!(!scopes_allow(&[ApiKeyScope::Admin], &Method::GET));
+    }
+
+    #[test]
+    fn admin_role_requires_the_admin_scope() {
+        let read = [ApiKeyScope::Read];
+        let admin = [ApiKeyScope::Read, ApiKeyScope::Admin];
+        assert_eq!(effective_role(&read, UserRole::Admin), UserRole::User);
+        assert_eq!(effective_role(&admin, UserRole::Admin), UserRole::Admin);
+        assert_eq!(effective_role(&admin, UserRole::User), UserRole::User);
+        assert_eq!(
+            effective_role(&read, UserRole::Moderator),
+            UserRole::Moderator
+        );
+    }
+
+    #[test]
+    fn hash_api_key_is_hex_sha256() {
+        assert_eq!(
+            hash_api_key("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use crate::feature::api_key::entity::{ApiKey, ApiKeyError, CreateApiKeyDTO, CreatedApiKey};
+use crate::feature::api_key::service::{ApiKeyService, ApiKeyServiceTrait};
+use crate::metrics::PrometheusMetrics;
+use crate::servers::http::middleware::{ApiKeyAuth, UserJWT};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+pub struct ApiKeyHandler {
+    api_key_service: Arc<ApiKeyService>,
+    metrics: Arc<PrometheusMetrics>,
+}
+
+impl ApiKeyHandler {
+    pub fn new_handler(
+        api_key_service: Arc<ApiKeyService>,
+        metrics: Arc<PrometheusMetrics>,
+    ) -> Self {
+        Self {
+            api_key_service,
+            metrics,
+        }
+    }
+
+    /// Keys are managed from a browser session only, so a leaked key cannot
+    /// mint further keys.
+    fn reject_api_key(&self, api_key: &Option<Extension<ApiKeyAuth>>) -> Option<Response> {
+        let Extension(api_key) = api_key.as_ref()?;
+        log::warn!("API key {} tried to manage API keys", api_key.id);
+        self.metrics
+            .inc_errors("authorization_error", "api_key_handler");
+        Some(
+            (
+                StatusCode::FORBIDDEN,
+                Json("API keys cannot manage API keys".to_string()),
+            )
+                .into_response(),
+        )
+    }
+
+    fn error_response(&self, err: ApiKeyError) -> Response {
+        match err {
+            ApiKeyError::NotFound => {
+                self.metrics.inc_errors("not_found", "api_key_handler");
+                (StatusCode::NOT_FOUND, Json("API key not found".to_string())).into_response()
+            }
+            ApiKeyError::Forbidden => {
+                self.metrics
+                    .inc_errors("authorization_error", "api_key_handler");
+                (StatusCode::FORBIDDEN, Json(err.to_string())).into_response()
+            }
+            ApiKeyError::InvalidExpiry => {
+                self.metrics
+                    .inc_errors("validation_error", "api_key_handler");
+                (StatusCode::UNPROCESSABLE_ENTITY, Json(err.to_string())).into_response()
+            }
+            ApiKeyError::Db(err) => {
+                eprintln!("❌ API key handler error: {}", err);
+                self.metrics.inc_errors("database_error", "api_key_handler");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json("Internal server error".to_string()),
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/private/api-keys",
+    responses(
+        (status = 200, description = "Active API keys of the current user", body = Vec<ApiKey>),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "Called with an API key"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "API keys"
+)]
+pub async fn list_api_keys_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    api_key: Option<Extension<ApiKeyAuth>>,
+    State(handlers): State<Arc<ApiKeyHandler>>,
+) -> Response {
+    if let Some(response) = handlers.reject_api_key(&api_key) {
+        return response;
+    }
+    match handlers.api_key_service.list_keys(user_jwt.id).await {
+        Ok(keys) => Json(keys).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/private/api-keys",
+    request_body = CreateApiKeyDTO,
+    responses(
+        (status = 201, description = "API key created; the key itself is only returned here", body = CreatedApiKey),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "Admin scope requested by a non-admin, or called with an API key"),
+        (status = 422, description = "Validation error"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "API keys"
+)]
+pub async fn create_api_key_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    api_key: Option<Extension<ApiKeyAuth>>,
+    State(handlers): State<Arc<ApiKeyHandler>>,
+    Json(payload): Json<CreateApiKeyDTO>,
+) -> Response {
+    if let Some(response) = handlers.reject_api_key(&api_key) {
+        return response;
+    }
+    if let Err(validation_errors) = payload.validate() {
+        handlers
+            .metrics
+            .inc_errors("validation_error", "api_key_handler");
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(format!("Validation error: {:?}", validation_errors)),
+        )
+            .into_response();
+    }
+    match handlers
+        .api_key_service
+        .create_key(user_jwt.id, user_jwt.is_admin(), payload)
+        .await
+    {
+        Ok(created) => (StatusCode::CREATED, Json(created)).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/private/api-keys/{id}",
+    params(
+        ("id" = Uuid, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 204, description = "API key revoked"),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "Called with an API key"),
+        (status = 404, description = "API key not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "API keys"
+)]
+pub async fn revoke_api_key_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    api_key: Option<Extension<ApiKeyAuth>>,
+    State(handlers): State<Arc<ApiKeyHandler>>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Some(response) = handlers.reject_api_key(&api_key) {
+        return response;
+    }
+    match handlers.api_key_service.revoke_key(user_jwt.id, id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
@@ -0,0 +1,168 @@
+use crate::feature::api_key::entity::{
+    API_KEYS_COLUMNS, API_KEYS_CREATED_AT, API_KEYS_EXPIRES_AT, API_KEYS_ID, API_KEYS_KEY_HASH,
+    API_KEYS_LAST_USED_AT, API_KEYS_NAME, API_KEYS_PREFIX, API_KEYS_REVOKED_AT, API_KEYS_SCOPES,
+    API_KEYS_TABLE, API_KEYS_USER_ID, ApiKey, ApiKeyOwner,
+};
+use crate::feature::auth::entity::{USERS_ID, USERS_ROLE, USERS_TABLE};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sea_query::{Alias, Cond, Expr, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use serde_json::Value;
+use sqlx::{Error, Pool, Postgres, query_as_with, query_with};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ApiKeyRepositoryTrait: Send + Sync {
+    async fn list_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>, Error>;
+    async fn create_key(
+        &self,
+        user_id: Uuid,
+        name: String,
+        prefix: String,
+        key_hash: String,
+        scopes: Value,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKey, Error>;
+    async fn revoke_key(&self, user_id: Uuid, key_id: Uuid) -> Result<Option<ApiKey>, Error>;
+    async fn find_active_key(&self, key_hash: &str) -> Result<Option<ApiKeyOwner>, Error>;
+    async fn touch_key(&self, key_id: Uuid) -> Result<(), Error>;
+}
+
+#[derive(Clone)]
+pub struct ApiKeyRepository {
+    primary_db: Pool<Postgres>,
+}
+
+impl ApiKeyRepository {
+    pub fn new_api_key_repository(primary_db: Pool<Postgres>) -> Self {
+        Self { primary_db }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepositoryTrait for ApiKeyRepository {
+    async fn list_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>, Error> {
+        let (sql, values) = Query::select()
+            .columns(API_KEYS_COLUMNS)
+            .from(API_KEYS_TABLE)
+            .and_where(Expr::col(API_KEYS_USER_ID).eq(user_id))
+            .and_where(Expr::col(API_KEYS_REVOKED_AT).is_null())
+            .order_by(API_KEYS_CREATED_AT, Order::Desc)
+            .build_sqlx(PostgresQueryBuilder);
+        let keys = query_as_with::<_, ApiKey, _>(&sql, values)
+            .fetch_all(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching api keys: {:?}", err);
+                err
+            })?;
+        Ok(keys)
+    }
+    async fn create_key(
+        &self,
+        user_id: Uuid,
+        name: String,
+        prefix: String,
+        key_hash: String,
+        scopes: Value,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKey, Error> {
+        let (sql, values) = Query::insert()
+            .into_table(Alias::new(API_KEYS_TABLE))
+            .columns([
+                Alias::new(API_KEYS_USER_ID),
+                Alias::new(API_KEYS_NAME),
+                Alias::new(API_KEYS_PREFIX),
+                Alias::new(API_KEYS_KEY_HASH),
+                Alias::new(API_KEYS_SCOPES),
+                Alias::new(API_KEYS_EXPIRES_AT),
+            ])
+            .values_panic([
+                user_id.into(),
+                name.into(),
+                prefix.into(),
+                key_hash.into(),
+                scopes.into(),
+                expires_at.into(),
+            ])
+            .returning(Query::returning().columns(API_KEYS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let key = query_as_with::<_, ApiKey, _>(&sql, values)
+            .fetch_one(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error creating api key: {:?}", err);
+                err
+            })?;
+        Ok(key)
+    }
+    async fn revoke_key(&self, user_id: Uuid, key_id: Uuid) -> Result<Option<ApiKey>, Error> {
+        let (sql, values) = Query::update()
+            .table(API_KEYS_TABLE)
+            .value(API_KEYS_REVOKED_AT, Expr::current_timestamp())
+            .and_where(Expr::col(API_KEYS_ID).eq(key_id))
+            .and_where(Expr::col(API_KEYS_USER_ID).eq(user_id))
+            .and_where(Expr::col(API_KEYS_REVOKED_AT).is_null())
+            .returning(Query::returning().columns(API_KEYS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let key = query_as_with::<_, ApiKey, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error revoking api key: {:?}", err);
+                err
+            })?;
+        Ok(key)
+    }
+    async fn find_active_key(&self, key_hash: &str) -> Result<Option<ApiKeyOwner>, Error> {
+        let (sql, values) = Query::select()
+            .column((API_KEYS_TABLE, API_KEYS_ID))
+            .column((API_KEYS_TABLE, API_KEYS_USER_ID))
+            .column((USERS_TABLE, USERS_ROLE))
+            .column((API_KEYS_TABLE, API_KEYS_SCOPES))
+            .column((API_KEYS_TABLE, API_KEYS_LAST_USED_AT))
+            .from(API_KEYS_TABLE)
+            .inner_join(
+                USERS_TABLE,
+                Expr::col((API_KEYS_TABLE, API_KEYS_USER_ID)).equals((USERS_TABLE, USERS_ID)),
+            )
+            .and_where(Expr::col((API_KEYS_TABLE, API_KEYS_KEY_HASH)).eq(key_hash))
+            .and_where(Expr::col((API_KEYS_TABLE, API_KEYS_REVOKED_AT)).is_null())
+            .cond_where(
+                Cond::any()
+                    .add(Expr::col((API_KEYS_TABLE, API_KEYS_EXPIRES_AT)).is_null())
+                    .add(
+                        Expr::col((API_KEYS_TABLE, API_KEYS_EXPIRES_AT))
+                            .gt(Expr::current_timestamp()),
+                    ),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+        let owner = query_as_with::<_, ApiKeyOwner, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error resolving api key: {:?}", err);
+                err
+            })?;
+        Ok(owner)
+    }
+    async fn touch_key(&self, key_id: Uuid) -> Result<(), Error> {
+        let (sql, values) = Query::update()
+            .table(API_KEYS_TABLE)
+            .value(API_KEYS_LAST_USED_AT, Expr::current_timestamp())
+            .and_where(Expr::col(API_KEYS_ID).eq(key_id))
+            .build_sqlx(PostgresQueryBuilder);
+        query_with(&sql, values)
+            .execute(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error updating api key usage: {:?}", err);
+                err
+            })?;
+        Ok(())
+    }
+}
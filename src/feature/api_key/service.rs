@@ -0,0 +1,121 @@
+use crate::feature::api_key::entity::{
+    ApiKey, ApiKeyError, ApiKeyOwner, ApiKeyScope, CreateApiKeyDTO, CreatedApiKey, hash_api_key,
+};
+use crate::feature::api_key::repository::{ApiKeyRepository, ApiKeyRepositoryTrait};
+use crate::utils::constants::{
+    API_KEY_DISPLAY_LENGTH, API_KEY_LENGTH, API_KEY_PREFIX, API_KEY_TOUCH_INTERVAL_SECS,
+};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+#[cfg(test)]
+use mockall::automock;
+use rand::Rng;
+use rand::distributions::Alphanumeric;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait ApiKeyServiceTrait: Send + Sync {
+    async fn list_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>, ApiKeyError>;
+    async fn create_key(
+        &self,
+        user_id: Uuid,
+        is_admin: bool,
+        dto: CreateApiKeyDTO,
+    ) -> Result<CreatedApiKey, ApiKeyError>;
+    async fn revoke_key(&self, user_id: Uuid, key_id: Uuid) -> Result<(), ApiKeyError>;
+    async fn authenticate(&self, key: &str) -> Result<Option<ApiKeyOwner>, ApiKeyError>;
+}
+
+pub struct ApiKeyService {
+    api_key_repository: Arc<ApiKeyRepository>,
+}
+
+impl ApiKeyService {
+    pub fn new(api_key_repository: Arc<ApiKeyRepository>) -> Self {
+        Self { api_key_repository }
+    }
+}
+
+fn generate_key() -> String {
+    let secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(API_KEY_LENGTH)
+        .map(char::from)
+        .collect();
+    format!("{}{}", API_KEY_PREFIX, secret)
+}
+
+#[async_trait]
+impl ApiKeyServiceTrait for ApiKeyService {
+    async fn list_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>, ApiKeyError> {
+        Ok(self.api_key_repository.list_keys(user_id).await?)
+    }
+    async fn create_key(
+        &self,
+        user_id: Uuid,
+        is_admin: bool,
+        dto: CreateApiKeyDTO,
+    ) -> Result<CreatedApiKey, ApiKeyError> {
+        if dto.scopes.contains(&ApiKeyScope::Admin) && !is_admin {
+            return Err(ApiKeyError::Forbidden);
+        }
+        if dto
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+        {
+            return Err(ApiKeyError::InvalidExpiry);
+        }
+        let mut scopes: Vec<ApiKeyScope> = Vec::new();
+        for scope in dto.scopes {
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+
+        let key = generate_key();
+        let api_key = self
+            .api_key_repository
+            .create_key(
+                user_id,
+                dto.name,
+                key[..API_KEY_DISPLAY_LENGTH].to_string(),
+                hash_api_key(&key),
+                serde_json::to_value(&scopes).unwrap_or_default(),
+                dto.expires_at,
+            )
+            .await?;
+        Ok(CreatedApiKey { api_key, key })
+    }
+    async fn revoke_key(&self, user_id: Uuid, key_id: Uuid) -> Result<(), ApiKeyError> {
+        self.api_key_repository
+            .revoke_key(user_id, key_id)
+            .await?
+            .map(|_| ())
+            .ok_or(ApiKeyError::NotFound)
+    }
+    /// Resolves an active key. `last_used_at` is refreshed at most once per
+    /// interval so that busy keys do not write on every request.
+    async fn authenticate(&self, key: &str) -> Result<Option<ApiKeyOwner>, ApiKeyError> {
+        if !key.starts_with(API_KEY_PREFIX) {
+            return Ok(None);
+        }
+        let Some(owner) = self
+            .api_key_repository
+            .find_active_key(&hash_api_key(key))
+            .await?
+        else {
+            return Ok(None);
+        };
+        let stale_before = Utc::now() - Duration::seconds(API_KEY_TOUCH_INTERVAL_SECS);
+        if owner
+            .last_used_at
+            .is_none_or(|last_used_at| last_used_at < stale_before)
+            && let Err(err) = self.api_key_repository.touch_key(owner.id).await
+        {
+            log::warn!("Could not record api key usage: {}", err);
+        }
+        Ok(Some(owner))
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::Error as SqlxError;
 use utoipa::ToSchema;
@@ -10,11 +10,6 @@ pub enum AppError {
     Db(SqlxError),
 }
 #[derive(Deserialize, Validate, Serialize, Debug, ToSchema)]
-pub struct AuthGoogleDTO {
-    #[validate(length(min = 1))]
-    pub code: String,
-}
-#[derive(Deserialize, Validate, Serialize, Debug, ToSchema)]
 pub struct RegisterDTO {
     #[validate(email)]
     pub email: String,
@@ -59,3 +54,50 @@ pub const USERS_ROLE: &str = "role";
 pub const USERS_CREATED_AT: &str = "created_at";
 pub const USERS_UPDATED_AT: &str = "updated_at";
 pub const USERS_VERSION: &str = "version";
+
+/// Stored refresh token. Rotation replaces a token with a new one of the same
+/// family; presenting a replaced token revokes the whole family.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RefreshToken {
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    InvalidToken,
+    TokenReused,
+    Token(String),
+    Db(SqlxError),
+}
+
+impl std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::InvalidToken => write!(f, "invalid refresh token"),
+            SessionError::TokenReused => write!(f, "refresh token reuse detected"),
+            SessionError::Token(err) => write!(f, "token error: {}", err),
+            SessionError::Db(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl From<SqlxError> for SessionError {
+    fn from(err: SqlxError) -> Self {
+        SessionError::Db(err)
+    }
+}
+
+pub const REFRESH_TOKENS_TABLE: &str = "refresh_tokens";
+pub const REFRESH_TOKENS_JTI: &str = "jti";
+pub const REFRESH_TOKENS_USER_ID: &str = "user_id";
+pub const REFRESH_TOKENS_FAMILY_ID: &str = "family_id";
+pub const REFRESH_TOKENS_EXPIRES_AT: &str = "expires_at";
+pub const REFRESH_TOKENS_REVOKED_AT: &str = "revoked_at";
+pub const REFRESH_TOKENS_REPLACED_BY: &str = "replaced_by";
+pub const REFRESH_TOKENS_COLUMNS: [&str; 3] = [
+    REFRESH_TOKENS_USER_ID,
+    REFRESH_TOKENS_FAMILY_ID,
+    REFRESH_TOKENS_REVOKED_AT,
+];
@@ -1,43 +1,49 @@
-use crate::feature::auth::entity::{AuthGoogleDTO, LoginDTO, RegisterDTO};
-use crate::feature::auth::jwt::set_jwt;
+use crate::feature::auth::entity::{LoginDTO, RegisterDTO, SessionError};
+use crate::feature::auth::jwt::clear_jwt;
+use crate::feature::auth::keyring::get_keyring;
 use crate::feature::auth::service::{UserService, UserServiceTrait};
-use crate::utils::url::generate_google_oauth_url;
+use crate::feature::auth::token_service::{TokenService, TokenServiceTrait};
+use crate::utils::constants::REFRESH_TOKEN_COOKIE;
 use axum::{
     Json as AxumJson,
     extract::{Json, State},
     http::StatusCode,
-    response::{IntoResponse, Redirect},
+    response::{IntoResponse, Response},
 };
-use reqwest::ClientBuilder;
+use axum_extra::extract::cookie::CookieJar;
+use jsonwebtoken::jwk::JwkSet;
 use serde_json::json;
-use std::env;
 use std::sync::Arc;
 use validator::Validate;
 
 pub struct UserHandler {
     user_service: Arc<UserService>,
+    token_service: Arc<TokenService>,
 }
 
 impl UserHandler {
-    pub fn new_handler(user_service: Arc<UserService>) -> Self {
-        Self { user_service }
+    pub fn new_handler(user_service: Arc<UserService>, token_service: Arc<TokenService>) -> Self {
+        Self {
+            user_service,
+            token_service,
+        }
     }
 }
 
 #[utoipa::path(
     get,
-    path = "/auth/google/url",
+    path = "/.well-known/jwks.json",
     responses(
-        (status = 302, description = "Redirect to Google OAuth"),
+        (status = 200, description = "JWK Set with the public keys that verify issued tokens"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Auth"
 )]
-pub async fn google_oauth_handler() -> Result<Redirect, (StatusCode, Json<serde_json::Value>)> {
-    match generate_google_oauth_url() {
-        Ok(url) => Ok(Redirect::temporary(&url)),
+pub async fn jwks_handler() -> Result<Json<JwkSet>, (StatusCode, Json<serde_json::Value>)> {
+    match get_keyring() {
+        Ok(keyring) => Ok(Json(keyring.jwks().clone())),
         Err(e) => {
-            eprintln!("Ошибка генерации Google OAuth URL: {}", e);
+            eprintln!("❌ JWKS error: {}", e);
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({ "error": "ошибка на сервере" })),
@@ -46,69 +52,6 @@ pub async fn google_oauth_handler() -> Result<Redirect, (StatusCode, Json<serde_
     }
 }
 
-#[utoipa::path(
-    post,
-    path = "/auth/google/callback",
-    request_body = AuthGoogleDTO,
-    responses(
-        (status = 200, description = "Google OAuth callback successful"),
-        (status = 422, description = "Validation error"),
-        (status = 500, description = "Internal server error"),
-        (status = 502, description = "Bad gateway")
-    ),
-    tag = "Auth"
-)]
-pub async fn handle_google_code(
-    Json(payload): Json<AuthGoogleDTO>,
-) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
-    if let Err(validation_errors) = payload.validate() {
-        return Err((
-            StatusCode::UNPROCESSABLE_ENTITY,
-            Json(json!({
-                "error": validation_errors
-            })),
-        ));
-    }
-
-    let client = ClientBuilder::new()
-        .danger_accept_invalid_certs(true)
-        .build()
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": format!("Failed to build client: {}", e)})),
-            )
-        })?;
-    let params = [
-        ("code", payload.code.clone()),
-        ("client_id", env::var("GOOGLE_CLIENT_ID").unwrap()),
-        ("client_secret", env::var("GOOGLE_SECRET").unwrap()),
-        ("redirect_uri", env::var("GOOGLE_URI_REDIRECT").unwrap()),
-        ("grant_type", "authorization_code".to_string()),
-    ];
-    let res = client
-        .post("https://oauth2.googleapis.com/token")
-        .form(&params)
-        .send()
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::BAD_GATEWAY,
-                Json(json!({ "error": format!("Request failed: {}", e) })),
-            )
-        })?;
-
-    let res_json = res.json().await.map_err(|e| {
-        (
-            StatusCode::BAD_GATEWAY,
-            Json(json!({"error": format!("Invalid JSON: {}", e)})),
-        )
-    })?;
-
-    println!("Ответ от Google: {:?}", res_json);
-    Ok(Json(res_json))
-}
-
 #[utoipa::path(
     post,
     path = "/auth/register",
@@ -139,7 +82,11 @@ pub async fn register_handler(
         .await
     {
         Ok(user) => {
-            let cookies = match set_jwt(user.id, user.role.clone()).await {
+            let cookies = match handler
+                .token_service
+                .issue_session(user.id, user.role.clone())
+                .await
+            {
                 Ok(jar) => jar,
                 Err(e) => {
                     eprintln!("❌ JWT generation error: {}", e);
@@ -201,7 +148,11 @@ pub async fn get_user_by_email_handler(
         .get_user_by_email_service(payload.email, payload.password)
         .await
     {
-        Ok(Some(user)) => match set_jwt(user.id, user.role.clone()).await {
+        Ok(Some(user)) => match handler
+            .token_service
+            .issue_session(user.id, user.role.clone())
+            .await
+        {
             Ok(cookies) => Ok((StatusCode::OK, (cookies, AxumJson(json!({ "user": user }))))),
             Err(err) => {
                 eprintln!("❌ JWT generation error: {err}");
@@ -224,3 +175,76 @@ pub async fn get_user_by_email_handler(
         }
     }
 }
+
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    responses(
+        (status = 200, description = "Refresh token rotated, new token cookies set"),
+        (status = 401, description = "Missing, expired, revoked or reused refresh token"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn refresh_handler(State(handler): State<Arc<UserHandler>>, jar: CookieJar) -> Response {
+    let Some(refresh_token) = jar.get(REFRESH_TOKEN_COOKIE).map(|c| c.value().to_string()) else {
+        return (
+            StatusCode::UNAUTHORIZED,
+            AxumJson(json!({ "error": "Refresh token is missing" })),
+        )
+            .into_response();
+    };
+    match handler.token_service.refresh_session(&refresh_token).await {
+        Ok(cookies) => (
+            StatusCode::OK,
+            cookies,
+            AxumJson(json!({ "message": "Session refreshed" })),
+        )
+            .into_response(),
+        Err(SessionError::InvalidToken) | Err(SessionError::TokenReused) => (
+            StatusCode::UNAUTHORIZED,
+            clear_jwt(),
+            AxumJson(json!({ "error": "Invalid refresh token" })),
+        )
+            .into_response(),
+        Err(e) => {
+            eprintln!("❌ Session refresh error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                AxumJson(json!({ "error": "Failed to refresh session" })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    responses(
+        (status = 200, description = "Refresh token revoked, token cookies cleared"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Auth"
+)]
+pub async fn logout_handler(State(handler): State<Arc<UserHandler>>, jar: CookieJar) -> Response {
+    if let Some(refresh_token) = jar.get(REFRESH_TOKEN_COOKIE)
+        && let Err(e) = handler
+            .token_service
+            .revoke_session(refresh_token.value())
+            .await
+    {
+        eprintln!("❌ Logout error: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            AxumJson(json!({ "error": "Failed to log out" })),
+        )
+            .into_response();
+    }
+    (
+        StatusCode::OK,
+        clear_jwt(),
+        AxumJson(json!({ "message": "Logged out" })),
+    )
+        .into_response()
+}
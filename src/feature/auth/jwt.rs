@@ -1,18 +1,25 @@
 use axum_extra::extract::cookie::CookieJar;
 use chrono::{Duration, Utc};
 use cookie::{Cookie, SameSite};
-use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use serde::{Deserialize, Serialize};
 use tower_http::follow_redirect::ResponseFuture;
 use uuid::Uuid;
 
 use crate::{
-    feature::auth::entity::UserRole,
+    feature::auth::{entity::UserRole, keyring::get_keyring},
     utils::constants::{ACCESS_TOKEN_COOKIE, REFRESH_TOKEN_COOKIE},
 };
 
-const TOKEN_EXPIRATION_HOURS: i64 = 24;
-const TOKEN_EXPIRATION_MINUTES: i64 = 15;
+pub const TOKEN_EXPIRATION_HOURS: i64 = 24;
+pub const TOKEN_EXPIRATION_MINUTES: i64 = 15;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenType {
+    Access,
+    Refresh,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub id: Uuid,
@@ -21,39 +28,69 @@ pub struct Claims {
     pub exp: usize,
     pub iat: usize,
     pub jti: String,
+    pub typ: TokenType,
 }
-pub async fn get_jwt(id: Uuid, role: UserRole, exp: i64) -> Result<String, String> {
-    let token = encode(
-        &Header::default(),
-        &Claims {
-            id,
-            sub: id.to_string(),
-            role,
-            exp: (Utc::now() + Duration::hours(exp)).timestamp() as usize,
-            iat: Utc::now().timestamp() as usize,
-            jti: Uuid::new_v4().to_string(),
-        },
-        &EncodingKey::from_secret("JWT_KEY".as_bytes()),
-    )
-    .unwrap();
-    Ok(token)
+
+pub fn refresh_token_ttl() -> Duration {
+    Duration::hours(TOKEN_EXPIRATION_HOURS)
+}
+
+pub fn access_token_ttl() -> Duration {
+    Duration::minutes(TOKEN_EXPIRATION_MINUTES)
 }
 
-pub async fn get_two_jwt(id: Uuid, role: UserRole) -> Result<(String, String), String> {
-    let refresh_token = get_jwt(id, role.clone(), TOKEN_EXPIRATION_HOURS).await?;
-    let access_token = get_jwt(id, role, TOKEN_EXPIRATION_MINUTES).await?;
+pub async fn get_jwt(
+    id: Uuid,
+    role: UserRole,
+    typ: TokenType,
+    jti: Uuid,
+    ttl: Duration,
+) -> Result<String, String> {
+    get_keyring()?.sign(&Claims {
+        id,
+        sub: id.to_string(),
+        role,
+        exp: (Utc::now() + ttl).timestamp() as usize,
+        iat: Utc::now().timestamp() as usize,
+        jti: jti.to_string(),
+        typ,
+    })
+}
+
+/// Signs a refresh token with the given `jti`, which the caller stores, and a
+/// fresh access token.
+pub async fn get_two_jwt(
+    id: Uuid,
+    role: UserRole,
+    refresh_jti: Uuid,
+) -> Result<(String, String), String> {
+    let refresh_token = get_jwt(
+        id,
+        role.clone(),
+        TokenType::Refresh,
+        refresh_jti,
+        refresh_token_ttl(),
+    )
+    .await?;
+    let access_token = get_jwt(
+        id,
+        role,
+        TokenType::Access,
+        Uuid::new_v4(),
+        access_token_ttl(),
+    )
+    .await?;
     Ok((refresh_token, access_token))
 }
-pub async fn set_jwt(id: Uuid, role: UserRole) -> Result<CookieJar, String> {
-    let (refresh_token, access_token) = get_two_jwt(id, role).await?;
 
+pub fn set_jwt(refresh_token: String, access_token: String) -> CookieJar {
     let mut jar = CookieJar::new();
 
     let refresh_cookie = Cookie::build((REFRESH_TOKEN_COOKIE, refresh_token))
         .http_only(true)
         .secure(true)
         .same_site(SameSite::Strict)
-        .max_age(cookie::time::Duration::hours(TOKEN_EXPIRATION_HOURS as i64))
+        .max_age(cookie::time::Duration::hours(TOKEN_EXPIRATION_HOURS))
         .path("/")
         .build();
 
@@ -61,25 +98,35 @@ pub async fn set_jwt(id: Uuid, role: UserRole) -> Result<CookieJar, String> {
         .http_only(true)
         .secure(true)
         .same_site(SameSite::Strict)
-        .max_age(cookie::time::Duration::minutes(
-            TOKEN_EXPIRATION_MINUTES as i64,
-        ))
+        .max_age(cookie::time::Duration::minutes(TOKEN_EXPIRATION_MINUTES))
         .path("/")
         .build();
 
     jar = jar.add(refresh_cookie);
     jar = jar.add(access_cookie);
 
-    Ok(jar)
+    jar
 }
 
-pub async fn decode_jwt(token: &str) -> Result<Claims, String> {
-    let token_data = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret("JWT_KEY".as_bytes()),
-        &Validation::default(),
-    )
-    .map_err(|e| e.to_string())?;
+/// Expires the cookies set by `set_jwt`. The removal cookies are written
+/// explicitly, since `CookieJar::remove` is a no-op for cookies that are not
+/// already in the jar.
+pub fn clear_jwt() -> CookieJar {
+    [REFRESH_TOKEN_COOKIE, ACCESS_TOKEN_COOKIE]
+        .into_iter()
+        .fold(CookieJar::new(), |jar, name| {
+            jar.add(
+                Cookie::build((name, ""))
+                    .http_only(true)
+                    .secure(true)
+                    .same_site(SameSite::Strict)
+                    .max_age(cookie::time::Duration::ZERO)
+                    .path("/")
+                    .build(),
+            )
+        })
+}
 
-    Ok(token_data.claims)
+pub async fn decode_jwt(token: &str) -> Result<Claims, String> {
+    get_keyring()?.verify::<Claims>(token)
 }
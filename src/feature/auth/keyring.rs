@@ -0,0 +1,364 @@
+use crate::app::config::{JwtAlgorithm, JwtConfig, JwtKeyConfig};
+use crate::utils::secret::load_material;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+    OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, decode_header, encode,
+};
+use rsa::RsaPublicKey;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const MIN_SECRET_LENGTH: usize = 32;
+const ED25519_KEY_LENGTH: usize = 32;
+
+static KEYRING: OnceLock<JwtKeyring> = OnceLock::new();
+
+struct SigningKey {
+    kid: String,
+    algorithm: Algorithm,
+    key: EncodingKey,
+}
+
+struct VerifyingKey {
+    algorithm: Algorithm,
+    key: DecodingKey,
+}
+
+/// Signs tokens with the active key and verifies them with whichever
+/// configured key their `kid` header names.
+pub struct JwtKeyring {
+    signing: SigningKey,
+    verifying: HashMap<String, VerifyingKey>,
+    jwks: JwkSet,
+}
+
+fn to_algorithm(algorithm: JwtAlgorithm) -> Algorithm {
+    match algorithm {
+        JwtAlgorithm::HS256 => Algorithm::HS256,
+        JwtAlgorithm::RS256 => Algorithm::RS256,
+        JwtAlgorithm::EdDSA => Algorithm::EdDSA,
+    }
+}
+
+fn required(field: &str, value: &Option<String>) -> Result<String, String> {
+    let value = value
+        .as_deref()
+        .ok_or_else(|| format!("{} is missing", field))?;
+    load_material(value)
+}
+
+fn public_jwk(kid: &str, key_algorithm: KeyAlgorithm, algorithm: AlgorithmParameters) -> Jwk {
+    Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(key_algorithm),
+            key_id: Some(kid.to_string()),
+            ..Default::default()
+        },
+        algorithm,
+    }
+}
+
+fn rsa_jwk(kid: &str, pem: &str) -> Result<Jwk, String> {
+    let public_key = RsaPublicKey::from_public_key_pem(pem).map_err(|err| err.to_string())?;
+    Ok(public_jwk(
+        kid,
+        KeyAlgorithm::RS256,
+        AlgorithmParameters::RSA(RSAKeyParameters {
+            key_type: RSAKeyType::RSA,
+            n: URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be()),
+            e: URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be()),
+        }),
+    ))
+}
+
+fn ed25519_jwk(kid: &str, pem: &str) -> Result<Jwk, String> {
+    let der = pem::parse(pem).map_err(|err| err.to_string())?;
+    // The raw key is the tail of the SubjectPublicKeyInfo structure.
+    let contents = der.contents();
+    if contents.len() < ED25519_KEY_LENGTH {
+        return Err("not an Ed25519 public key".to_string());
+    }
+    let raw = &contents[contents.len() - ED25519_KEY_LENGTH..];
+    Ok(public_jwk(
+        kid,
+        KeyAlgorithm::EdDSA,
+        AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+            key_type: OctetKeyPairType::OctetKeyPair,
+            curve: EllipticCurve::Ed25519,
+            x: URL_SAFE_NO_PAD.encode(raw),
+        }),
+    ))
+}
+
+struct LoadedKey {
+    decoding: DecodingKey,
+    encoding: Option<EncodingKey>,
+    jwk: Option<Jwk>,
+}
+
+/// Loads one configured key; the private half is only read for the active
+/// key.
+fn load_key(key: &JwtKeyConfig, is_active: bool) -> Result<LoadedKey, String> {
+    if key.algorithm == JwtAlgorithm::HS256 {
+        let secret = required("secret", &key.secret)?;
+        if secret.len() < MIN_SECRET_LENGTH {
+            return Err(format!(
+                "secret must be at least {} bytes",
+                MIN_SECRET_LENGTH
+            ));
+        }
+        return Ok(LoadedKey {
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            encoding: is_active.then(|| EncodingKey::from_secret(secret.as_bytes())),
+            jwk: None,
+        });
+    }
+    let public_pem = required("public_key", &key.public_key)?;
+    let private_pem = if is_active {
+        Some(required("private_key", &key.private_key)?)
+    } else {
+        None
+    };
+    let (decoding, encoding, jwk) = if key.algorithm == JwtAlgorithm::RS256 {
+        (
+            DecodingKey::from_rsa_pem(public_pem.as_bytes()),
+            private_pem.map(|pem| EncodingKey::from_rsa_pem(pem.as_bytes())),
+            rsa_jwk(&key.kid, &public_pem)?,
+        )
+    } else {
+        (
+            DecodingKey::from_ed_pem(public_pem.as_bytes()),
+            private_pem.map(|pem| EncodingKey::from_ed_pem(pem.as_bytes())),
+            ed25519_jwk(&key.kid, &public_pem)?,
+        )
+    };
+    Ok(LoadedKey {
+        decoding: decoding.map_err(|err| err.to_string())?,
+        encoding: encoding.transpose().map_err(|err| err.to_string())?,
+        jwk: Some(jwk),
+    })
+}
+
+impl JwtKeyring {
+    pub fn from_config(config: &JwtConfig) -> Result<Self, String> {
+        let mut signing = None;
+        let mut verifying = HashMap::new();
+        let mut jwks = Vec::new();
+        for key in &config.keys {
+            if verifying.contains_key(&key.kid) {
+                return Err(format!("JWT key {} is configured twice", key.kid));
+            }
+            let loaded = load_key(key, key.kid == config.active_kid)
+                .map_err(|err| format!("JWT key {}: {}", key.kid, err))?;
+            let algorithm = to_algorithm(key.algorithm);
+            if let Some(encoding) = loaded.encoding {
+                signing = Some(SigningKey {
+                    kid: key.kid.clone(),
+                    algorithm,
+                    key: encoding,
+                });
+            }
+            jwks.extend(loaded.jwk);
+            verifying.insert(
+                key.kid.clone(),
+                VerifyingKey {
+                    algorithm,
+                    key: loaded.decoding,
+                },
+            );
+        }
+        let signing = signing
+            .ok_or_else(|| format!("active JWT key {} is not configured", config.active_kid))?;
+        Ok(Self {
+            signing,
+            verifying,
+            jwks: JwkSet { keys: jwks },
+        })
+    }
+
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String, String> {
+        let mut header = Header::new(self.signing.algorithm);
+        header.kid = Some(self.signing.kid.clone());
+        encode(&header, claims, &self.signing.key).map_err(|err| err.to_string())
+    }
+
+    /// Only the algorithm of the key named by `kid` is accepted, so a token
+    /// cannot pick a weaker algorithm for itself.
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> Result<T, String> {
+        let header = decode_header(token).map_err(|err| err.to_string())?;
+        let kid = header
+            .kid
+            .ok_or_else(|| "token has no key id".to_string())?;
+        let key = self
+            .verifying
+            .get(&kid)
+            .ok_or_else(|| format!("unknown key id {}", kid))?;
+        let data = decode::<T>(token, &key.key, &Validation::new(key.algorithm))
+            .map_err(|err| err.to_string())?;
+        Ok(data.claims)
+    }
+
+    /// Public keys of the asymmetric keys; HS256 secrets are never published.
+    pub fn jwks(&self) -> &JwkSet {
+        &self.jwks
+    }
+}
+
+pub fn init_keyring(config: &JwtConfig) -> Result<(), String> {
+    let keyring = JwtKeyring::from_config(config)?;
+    KEYRING
+        .set(keyring)
+        .map_err(|_| "JWT keyring is already initialised".to_string())
+}
+
+pub fn get_keyring() -> Result<&'static JwtKeyring, String> {
+    KEYRING
+        .get()
+        .ok_or_else(|| "JWT keyring is not initialised".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsa::RsaPrivateKey;
+    use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize)]
+    struct Claims {
+        sub: String,
+        exp: i64,
+    }
+
+    fn claims() -> Claims {
+        Claims {
+            sub: "user".to_string(),
+            exp: chrono::Utc::now().timestamp() + 300,
+        }
+    }
+
+    fn hs256_key(kid: &str) -> JwtKeyConfig {
+        JwtKeyConfig {
+            kid: kid.to_string(),
+            algorithm: JwtAlgorithm::HS256,
+            secret: Some(format!("{}-secret-that-is-long-enough-for-hs256", kid)),
+            private_key: None,
+            public_key: None,
+        }
+    }
+
+    fn keyring(active_kid: &str, keys: Vec<JwtKeyConfig>) -> Result<JwtKeyring, String> {
+        JwtKeyring::from_config(&JwtConfig {
+            active_kid: active_kid.to_string(),
+            keys,
+        })
+    }
+
+    fn kid_of(token: &str) -> Option<String> {
+        decode_header(token).unwrap().kid
+    }
+
+    #[test]
+    fn rotation_keeps_tokens_of_the_previous_key_valid() {
+        let before = keyring("old", vec![hs256_key("old")]).unwrap();
+        let old_token = before.sign(&claims()).unwrap();
+        assert_eq!(kid_of(&old_token).as_deref(), Some("old"));
+
+        let rotated = keyring("new", vec![hs256_key("old"), hs256_key("new")]).unwrap();
+        assert_eq!(rotated.verify::<Claims>(&old_token).unwrap().sub, "user");
+        let new_token = rotated.sign(&claims()).unwrap();
+        assert_eq!(kid_of(&new_token).as_deref(), Some("new"));
+        assert!(before.verify::<Claims>(&new_token).is_err());
+
+        let retired = keyring("new", vec![hs256_key("new")]).unwrap();
+        assert!(retired.verify::<Claims>(&old_token).is_err());
+        assert!(retired.verify::<Claims>(&new_token).is_ok());
+    }
+
+    #[test]
+    fn tokens_without_a_known_kid_are_rejected() {
+        let keyring = keyring("a", vec![hs256_key("a")]).unwrap();
+        let secret = hs256_key("a").secret.unwrap();
+        let without_kid = encode(
+            &Header::new(Algorithm::HS256),
+            &claims(),
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap();
+        assert!(keyring.verify::<Claims>(&without_kid).is_err());
+
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("b".to_string());
+        let unknown_kid = encode(
+            &header,
+            &claims(),
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap();
+        assert!(keyring.verify::<Claims>(&unknown_kid).is_err());
+    }
+
+    #[test]
+    fn invalid_configurations_are_rejected() {
+        assert!(keyring("missing", vec![hs256_key("a")]).is_err());
+        assert!(keyring("a", vec![hs256_key("a"), hs256_key("a")]).is_err());
+        let short = JwtKeyConfig {
+            secret: Some("short".to_string()),
+            ..hs256_key("a")
+        };
+        assert!(keyring("a", vec![short]).is_err());
+    }
+
+    #[test]
+    fn asymmetric_keys_are_published_and_pin_their_algorithm() {
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+        let public_pem = private_key
+            .to_public_key()
+            .to_public_key_pem(LineEnding::LF)
+            .unwrap();
+        let rsa_key = JwtKeyConfig {
+            kid: "rsa".to_string(),
+            algorithm: JwtAlgorithm::RS256,
+            secret: None,
+            private_key: Some(
+                private_key
+                    .to_pkcs8_pem(LineEnding::LF)
+                    .unwrap()
+                    .to_string(),
+            ),
+            public_key: Some(public_pem.clone()),
+        };
+        let keyring = keyring("rsa", vec![rsa_key, hs256_key("hs")]).unwrap();
+
+        let kids: Vec<_> = keyring
+            .jwks()
+            .keys
+            .iter()
+            .map(|jwk| jwk.common.key_id.clone())
+            .collect();
+        assert_eq!(kids, vec![Some("rsa".to_string())]);
+        let token = keyring.sign(&claims()).unwrap();
+        assert_eq!(keyring.verify::<Claims>(&token).unwrap().sub, "user");
+
+        // An HS256 token keyed with the published RSA key must not pass.
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("rsa".to_string());
+        let forged = encode(
+            &header,
+            &claims(),
+            &EncodingKey::from_secret(public_pem.as_bytes()),
+        )
+        .unwrap();
+        assert!(keyring.verify::<Claims>(&forged).is_err());
+    }
+}
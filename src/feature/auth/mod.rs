@@ -1,6 +1,9 @@
 pub mod entity;
 pub mod handler;
 pub mod jwt;
-mod password;
+pub mod keyring;
+pub(crate) mod password;
 pub mod repository;
 pub mod service;
+pub mod token_repository;
+pub mod token_service;
@@ -4,9 +4,10 @@ use mockall::automock;
 use sea_query::{Alias, Expr, PostgresQueryBuilder, Query};
 use sea_query_binder::SqlxBinder;
 use sqlx::{Error, Pool, Postgres, query_as};
+use uuid::Uuid;
 #[cfg_attr(test, automock)]
 #[async_trait]
-pub trait UserRepositoryTrait {
+pub trait UserRepositoryTrait: Send + Sync {
     async fn get_all_users(&self) -> Result<Vec<UserDB>, Error>;
     async fn create_user(
         &self,
@@ -15,6 +16,7 @@ pub trait UserRepositoryTrait {
         password: Vec<u8>,
     ) -> Result<UserDB, Error>;
     async fn get_user_by_email(&self, email: String) -> Result<Option<UserDB>, Error>;
+    async fn get_user_by_id(&self, id: Uuid) -> Result<Option<UserDB>, Error>;
 }
 
 #[derive(Clone)]
@@ -117,4 +119,31 @@ impl UserRepositoryTrait for UserRepository {
 
         Ok(user)
     }
+
+    async fn get_user_by_id(&self, id: Uuid) -> Result<Option<UserDB>, Error> {
+        let (query, args) = Query::select()
+            .columns([
+                "id",
+                "title",
+                "email",
+                "password",
+                "role",
+                "created_at",
+                "updated_at",
+                "version",
+            ])
+            .from("users")
+            .and_where(Expr::col((Alias::new("users"), Alias::new("id"))).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let user = sqlx::query_as_with::<_, UserDB, _>(&query, args)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching user by id: {:?}", err);
+                err
+            })?;
+
+        Ok(user)
+    }
 }
@@ -0,0 +1,148 @@
+use crate::feature::auth::entity::{
+    REFRESH_TOKENS_COLUMNS, REFRESH_TOKENS_EXPIRES_AT, REFRESH_TOKENS_FAMILY_ID,
+    REFRESH_TOKENS_JTI, REFRESH_TOKENS_REPLACED_BY, REFRESH_TOKENS_REVOKED_AT,
+    REFRESH_TOKENS_TABLE, REFRESH_TOKENS_USER_ID, RefreshToken,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sea_query::{Alias, Expr, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::{Error, Pool, Postgres, query_as_with, query_with};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RefreshTokenRepositoryTrait: Send + Sync {
+    async fn create_token(
+        &self,
+        jti: Uuid,
+        user_id: Uuid,
+        family_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), Error>;
+    async fn rotate_token(
+        &self,
+        jti: Uuid,
+        new_jti: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<RefreshToken>, Error>;
+    async fn get_token(&self, jti: Uuid) -> Result<Option<RefreshToken>, Error>;
+    async fn revoke_family(&self, family_id: Uuid) -> Result<u64, Error>;
+}
+
+#[derive(Clone)]
+pub struct RefreshTokenRepository {
+    primary_db: Pool<Postgres>,
+}
+
+impl RefreshTokenRepository {
+    pub fn new_refresh_token_repository(primary_db: Pool<Postgres>) -> Self {
+        Self { primary_db }
+    }
+}
+
+fn insert_token_query(
+    jti: Uuid,
+    user_id: Uuid,
+    family_id: Uuid,
+    expires_at: DateTime<Utc>,
+) -> (String, sea_query_binder::SqlxValues) {
+    Query::insert()
+        .into_table(Alias::new(REFRESH_TOKENS_TABLE))
+        .columns([
+            Alias::new(REFRESH_TOKENS_JTI),
+            Alias::new(REFRESH_TOKENS_USER_ID),
+            Alias::new(REFRESH_TOKENS_FAMILY_ID),
+            Alias::new(REFRESH_TOKENS_EXPIRES_AT),
+        ])
+        .values_panic([
+            jti.into(),
+            user_id.into(),
+            family_id.into(),
+            expires_at.into(),
+        ])
+        .build_sqlx(PostgresQueryBuilder)
+}
+
+#[async_trait]
+impl RefreshTokenRepositoryTrait for RefreshTokenRepository {
+    async fn create_token(
+        &self,
+        jti: Uuid,
+        user_id: Uuid,
+        family_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        let (sql, values) = insert_token_query(jti, user_id, family_id, expires_at);
+        query_with(&sql, values)
+            .execute(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error storing refresh token: {:?}", err);
+                err
+            })?;
+        Ok(())
+    }
+    /// Marks a live token as replaced and stores its successor in the same
+    /// family. Returns `None` when the token is unknown, expired or already
+    /// used.
+    async fn rotate_token(
+        &self,
+        jti: Uuid,
+        new_jti: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<RefreshToken>, Error> {
+        let mut tx = self.primary_db.begin().await?;
+        let (sql, values) = Query::update()
+            .table(REFRESH_TOKENS_TABLE)
+            .value(REFRESH_TOKENS_REVOKED_AT, Expr::current_timestamp())
+            .value(REFRESH_TOKENS_REPLACED_BY, new_jti)
+            .and_where(Expr::col(REFRESH_TOKENS_JTI).eq(jti))
+            .and_where(Expr::col(REFRESH_TOKENS_REVOKED_AT).is_null())
+            .and_where(Expr::col(REFRESH_TOKENS_EXPIRES_AT).gt(Expr::current_timestamp()))
+            .returning(Query::returning().columns(REFRESH_TOKENS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let Some(token) = query_as_with::<_, RefreshToken, _>(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let (sql, values) = insert_token_query(new_jti, token.user_id, token.family_id, expires_at);
+        query_with(&sql, values).execute(&mut *tx).await?;
+        tx.commit().await.map_err(|err| {
+            eprintln!("❌ Error rotating refresh token: {:?}", err);
+            err
+        })?;
+        Ok(Some(token))
+    }
+    async fn get_token(&self, jti: Uuid) -> Result<Option<RefreshToken>, Error> {
+        let (sql, values) = Query::select()
+            .columns(REFRESH_TOKENS_COLUMNS)
+            .from(REFRESH_TOKENS_TABLE)
+            .and_where(Expr::col(REFRESH_TOKENS_JTI).eq(jti))
+            .build_sqlx(PostgresQueryBuilder);
+        let token = query_as_with::<_, RefreshToken, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await?;
+        Ok(token)
+    }
+    async fn revoke_family(&self, family_id: Uuid) -> Result<u64, Error> {
+        let (sql, values) = Query::update()
+            .table(REFRESH_TOKENS_TABLE)
+            .value(REFRESH_TOKENS_REVOKED_AT, Expr::current_timestamp())
+            .and_where(Expr::col(REFRESH_TOKENS_FAMILY_ID).eq(family_id))
+            .and_where(Expr::col(REFRESH_TOKENS_REVOKED_AT).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+        let result = query_with(&sql, values)
+            .execute(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error revoking refresh tokens: {:?}", err);
+                err
+            })?;
+        Ok(result.rows_affected())
+    }
+}
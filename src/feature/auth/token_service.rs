@@ -0,0 +1,283 @@
+use crate::feature::auth::entity::{SessionError, UserRole};
+use crate::feature::auth::jwt::{TokenType, decode_jwt, get_two_jwt, refresh_token_ttl, set_jwt};
+use crate::feature::auth::repository::UserRepositoryTrait;
+use crate::feature::auth::token_repository::RefreshTokenRepositoryTrait;
+use async_trait::async_trait;
+use axum_extra::extract::cookie::CookieJar;
+use chrono::Utc;
+#[cfg(test)]
+use mockall::automock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TokenServiceTrait: Send + Sync {
+    async fn issue_session(&self, user_id: Uuid, role: UserRole)
+    -> Result<CookieJar, SessionError>;
+    async fn refresh_session(&self, refresh_token: &str) -> Result<CookieJar, SessionError>;
+    async fn revoke_session(&self, refresh_token: &str) -> Result<(), SessionError>;
+}
+
+pub struct TokenService {
+    token_repository: Arc<dyn RefreshTokenRepositoryTrait>,
+    user_repository: Arc<dyn UserRepositoryTrait>,
+}
+
+impl TokenService {
+    pub fn new(
+        token_repository: Arc<dyn RefreshTokenRepositoryTrait>,
+        user_repository: Arc<dyn UserRepositoryTrait>,
+    ) -> Self {
+        Self {
+            token_repository,
+            user_repository,
+        }
+    }
+
+    async fn session_cookies(
+        &self,
+        user_id: Uuid,
+        role: UserRole,
+        refresh_jti: Uuid,
+    ) -> Result<CookieJar, SessionError> {
+        let (refresh_token, access_token) = get_two_jwt(user_id, role, refresh_jti)
+            .await
+            .map_err(SessionError::Token)?;
+        Ok(set_jwt(refresh_token, access_token))
+    }
+}
+
+/// `jti` of a valid refresh token; access tokens are rejected.
+async fn refresh_jti(refresh_token: &str) -> Result<Uuid, SessionError> {
+    let claims = decode_jwt(refresh_token)
+        .await
+        .map_err(|_| SessionError::InvalidToken)?;
+    if claims.typ != TokenType::Refresh {
+        return Err(SessionError::InvalidToken);
+    }
+    Uuid::parse_str(&claims.jti).map_err(|_| SessionError::InvalidToken)
+}
+
+#[async_trait]
+impl TokenServiceTrait for TokenService {
+    async fn issue_session(
+        &self,
+        user_id: Uuid,
+        role: UserRole,
+    ) -> Result<CookieJar, SessionError> {
+        let jti = Uuid::new_v4();
+        self.token_repository
+            .create_token(
+                jti,
+                user_id,
+                Uuid::new_v4(),
+                Utc::now() + refresh_token_ttl(),
+            )
+            .await?;
+        self.session_cookies(user_id, role, jti).await
+    }
+    async fn refresh_session(&self, refresh_token: &str) -> Result<CookieJar, SessionError> {
+        let jti = refresh_jti(refresh_token).await?;
+        let new_jti = Uuid::new_v4();
+        let rotated = self
+            .token_repository
+            .rotate_token(jti, new_jti, Utc::now() + refresh_token_ttl())
+            .await?;
+        let Some(previous) = rotated else {
+            return match self.token_repository.get_token(jti).await? {
+                Some(token) if token.revoked_at.is_some() => {
+                    // A rotated or revoked token came back: assume it was
+                    // stolen and end every session descending from it.
+                    log::warn!("Refresh token reuse detected for user {}", token.user_id);
+                    self.token_repository.revoke_family(token.family_id).await?;
+                    Err(SessionError::TokenReused)
+                }
+                _ => Err(SessionError::InvalidToken),
+            };
+        };
+        // The role is read again so that role changes apply on refresh.
+        let user = self
+            .user_repository
+            .get_user_by_id(previous.user_id)
+            .await?
+            .ok_or(SessionError::InvalidToken)?;
+        self.session_cookies(user.id, user.role, new_jti).await
+    }
+    async fn revoke_session(&self, refresh_token: &str) -> Result<(), SessionError> {
+        let Ok(jti) = refresh_jti(refresh_token).await else {
+            return Ok(());
+        };
+        if let Some(token) = self.token_repository.get_token(jti).await? {
+            self.token_repository.revoke_family(token.family_id).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::{JwtAlgorithm, JwtConfig, JwtKeyConfig};
+    use crate::feature::auth::entity::{RefreshToken, UserDB};
+    use crate::feature::auth::jwt::{access_token_ttl, get_jwt};
+    use crate::feature::auth::keyring::init_keyring;
+    use crate::feature::auth::repository::MockUserRepositoryTrait;
+    use crate::feature::auth::token_repository::MockRefreshTokenRepositoryTrait;
+    use mockall::predicate::eq;
+
+    fn init_test_keyring() {
+        // Tests share the global keyring; only the first call installs it.
+        let _ = init_keyring(&JwtConfig {
+            active_kid: "test".to_string(),
+            keys: vec![JwtKeyConfig {
+                kid: "test".to_string(),
+                algorithm: JwtAlgorithm::HS256,
+                secret: Some("token-service-test-secret-long-enough".to_string()),
+                private_key: None,
+                public_key: None,
+            }],
+        });
+    }
+
+    async fn token(user_id: Uuid, typ: TokenType, jti: Uuid) -> String {
+        init_test_keyring();
+        get_jwt(user_id, UserRole::User, typ, jti, access_token_ttl())
+            .await
+            .unwrap()
+    }
+
+    fn user(id: Uuid) -> UserDB {
+        UserDB {
+            id,
+            title: "user".to_string(),
+            email: "user@example.com".to_string(),
+            password: Vec::new(),
+            role: UserRole::User,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            version: 1,
+        }
+    }
+
+    fn service(
+        token_repository: MockRefreshTokenRepositoryTrait,
+        user_repository: MockUserRepositoryTrait,
+    ) -> TokenService {
+        TokenService::new(Arc::new(token_repository), Arc::new(user_repository))
+    }
+
+    #[tokio::test]
+    async fn refresh_rotates_the_token() {
+        let (user_id, jti, family_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut tokens = MockRefreshTokenRepositoryTrait::new();
+        tokens
+            .expect_rotate_token()
+            .withf(move |old, new, _| *old == jti && *new != jti)
+            .times(1)
+            .returning(move |_, _, _| {
+                Ok(Some(RefreshToken {
+                    user_id,
+                    family_id,
+                    revoked_at: None,
+                }))
+            });
+        tokens.expect_revoke_family().never();
+        let mut users = MockUserRepositoryTrait::new();
+        users
+            .expect_get_user_by_id()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|id| Ok(Some(user(id))));
+
+        let refresh_token = token(user_id, TokenType::Refresh, jti).await;
+        let jar = service(tokens, users)
+            .refresh_session(&refresh_token)
+            .await
+            .unwrap();
+        assert_eq!(jar.iter().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn reusing_a_rotated_token_revokes_the_whole_family() {
+        let (user_id, jti, family_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut tokens = MockRefreshTokenRepositoryTrait::new();
+        tokens
+            .expect_rotate_token()
+            .times(1)
+            .returning(|_, _, _| Ok(None));
+        tokens
+            .expect_get_token()
+            .with(eq(jti))
+            .times(1)
+            .returning(move |_| {
+                Ok(Some(RefreshToken {
+                    user_id,
+                    family_id,
+                    revoked_at: Some(Utc::now()),
+                }))
+            });
+        tokens
+            .expect_revoke_family()
+            .with(eq(family_id))
+            .times(1)
+            .returning(|_| Ok(3));
+        let mut users = MockUserRepositoryTrait::new();
+        users.expect_get_user_by_id().never();
+
+        let refresh_token = token(user_id, TokenType::Refresh, jti).await;
+        let result = service(tokens, users).refresh_session(&refresh_token).await;
+        assert!(matches!(result, Err(SessionError::TokenReused)));
+    }
+
+    #[tokio::test]
+    async fn access_token_cannot_refresh() {
+        let mut tokens = MockRefreshTokenRepositoryTrait::new();
+        tokens.expect_rotate_token().never();
+        let access_token = token(Uuid::new_v4(), TokenType::Access, Uuid::new_v4()).await;
+        let result = service(tokens, MockUserRepositoryTrait::new())
+            .refresh_session(&access_token)
+            .await;
+        assert!(matches!(result, Err(SessionError::InvalidToken)));
+    }
+
+    #[tokio::test]
+    async fn logout_revokes_the_current_token_family() {
+        let (user_id, jti, family_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut tokens = MockRefreshTokenRepositoryTrait::new();
+        tokens
+            .expect_get_token()
+            .with(eq(jti))
+            .times(1)
+            .returning(move |_| {
+                Ok(Some(RefreshToken {
+                    user_id,
+                    family_id,
+                    revoked_at: None,
+                }))
+            });
+        tokens
+            .expect_revoke_family()
+            .with(eq(family_id))
+            .times(1)
+            .returning(|_| Ok(1));
+
+        let refresh_token = token(user_id, TokenType::Refresh, jti).await;
+        service(tokens, MockUserRepositoryTrait::new())
+            .revoke_session(&refresh_token)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn logout_with_a_garbage_token_is_a_no_op() {
+        let mut tokens = MockRefreshTokenRepositoryTrait::new();
+        tokens.expect_get_token().never();
+        tokens.expect_revoke_family().never();
+        init_test_keyring();
+        service(tokens, MockUserRepositoryTrait::new())
+            .revoke_session("not-a-jwt")
+            .await
+            .unwrap();
+    }
+}
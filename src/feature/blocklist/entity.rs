@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Error as SqlxError, FromRow};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+pub fn validate_domain(domain: &str) -> Result<(), ValidationError> {
+    let valid = !domain.is_empty()
+        && domain.len() <= 253
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("invalid_domain"))
+    }
+}
+
+/// Lowercases the domain and drops a trailing dot, so `Example.COM.` and
+/// `example.com` are stored and matched the same way.
+pub fn normalize_domain(domain: &str) -> String {
+    domain.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct BlockedDomain {
+    pub id: Uuid,
+    pub domain: String,
+    pub reason: Option<String>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateBlockedDomainDTO {
+    /// Blocking a domain also blocks all of its subdomains.
+    #[validate(custom(function = "validate_domain"))]
+    #[schema(example = "malware.example")]
+    pub domain: String,
+    #[validate(length(max = 512))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum BlocklistError {
+    NotFound,
+    AlreadyExists,
+    Db(SqlxError),
+}
+
+impl std::fmt::Display for BlocklistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlocklistError::NotFound => write!(f, "blocked domain not found"),
+            BlocklistError::AlreadyExists => write!(f, "domain is already blocked"),
+            BlocklistError::Db(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl From<SqlxError> for BlocklistError {
+    fn from(err: SqlxError) -> Self {
+        BlocklistError::Db(err)
+    }
+}
+
+pub const BLOCKED_DOMAINS_TABLE: &str = "blocked_domains";
+pub const BLOCKED_DOMAINS_ID: &str = "id";
+pub const BLOCKED_DOMAINS_DOMAIN: &str = "domain";
+pub const BLOCKED_DOMAINS_REASON: &str = "reason";
+pub const BLOCKED_DOMAINS_CREATED_BY: &str = "created_by";
+pub const BLOCKED_DOMAINS_CREATED_AT: &str = "created_at";
+pub const BLOCKED_DOMAINS_COLUMNS: [&str; 5] = [
+    BLOCKED_DOMAINS_ID,
+    BLOCKED_DOMAINS_DOMAIN,
+    BLOCKED_DOMAINS_REASON,
+    BLOCKED_DOMAINS_CREATED_BY,
+    BLOCKED_DOMAINS_CREATED_AT,
+];
@@ -0,0 +1,171 @@
+use crate::feature::blocklist::entity::{BlockedDomain, BlocklistError, CreateBlockedDomainDTO};
+use crate::feature::blocklist::service::{BlocklistService, BlocklistServiceTrait};
+use crate::metrics::PrometheusMetrics;
+use crate::servers::http::middleware::UserJWT;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+pub struct BlocklistHandler {
+    blocklist_service: Arc<BlocklistService>,
+    metrics: Arc<PrometheusMetrics>,
+}
+
+impl BlocklistHandler {
+    pub fn new_handler(
+        blocklist_service: Arc<BlocklistService>,
+        metrics: Arc<PrometheusMetrics>,
+    ) -> Self {
+        Self {
+            blocklist_service,
+            metrics,
+        }
+    }
+
+    fn forbidden(&self) -> Response {
+        self.metrics
+            .inc_errors("authorization_error", "blocklist_handler");
+        (StatusCode::FORBIDDEN, Json("Forbidden".to_string())).into_response()
+    }
+
+    fn error_response(&self, err: BlocklistError) -> Response {
+        match err {
+            BlocklistError::NotFound => {
+                self.metrics.inc_errors("not_found", "blocklist_handler");
+                (
+                    StatusCode::NOT_FOUND,
+                    Json("Blocked domain not found".to_string()),
+                )
+                    .into_response()
+            }
+            BlocklistError::AlreadyExists => {
+                self.metrics.inc_errors("conflict", "blocklist_handler");
+                (
+                    StatusCode::CONFLICT,
+                    Json("Domain is already blocked".to_string()),
+                )
+                    .into_response()
+            }
+            BlocklistError::Db(err) => {
+                eprintln!("❌ Blocklist handler error: {}", err);
+                self.metrics
+                    .inc_errors("database_error", "blocklist_handler");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json("Internal server error".to_string()),
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/private/blocklist",
+    responses(
+        (status = 200, description = "Blocked destination domains", body = Vec<BlockedDomain>),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "Forbidden - requires admin role"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Blocklist"
+)]
+pub async fn list_blocked_domains_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<BlocklistHandler>>,
+) -> Response {
+    if !user_jwt.is_admin() {
+        return handlers.forbidden();
+    }
+    match handlers.blocklist_service.list_blocked_domains().await {
+        Ok(domains) => Json(domains).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/private/blocklist",
+    request_body = CreateBlockedDomainDTO,
+    responses(
+        (status = 201, description = "Domain blocked", body = BlockedDomain),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "Forbidden - requires admin role"),
+        (status = 409, description = "Domain is already blocked"),
+        (status = 422, description = "Validation error"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Blocklist"
+)]
+pub async fn add_blocked_domain_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<BlocklistHandler>>,
+    Json(payload): Json<CreateBlockedDomainDTO>,
+) -> Response {
+    if !user_jwt.is_admin() {
+        return handlers.forbidden();
+    }
+    if let Err(validation_errors) = payload.validate() {
+        handlers
+            .metrics
+            .inc_errors("validation_error", "blocklist_handler");
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(format!("Validation error: {:?}", validation_errors)),
+        )
+            .into_response();
+    }
+    match handlers
+        .blocklist_service
+        .block_domain(payload, user_jwt.id)
+        .await
+    {
+        Ok(blocked) => (StatusCode::CREATED, Json(blocked)).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/private/blocklist/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Blocked domain ID")
+    ),
+    responses(
+        (status = 200, description = "Domain unblocked", body = BlockedDomain),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "Forbidden - requires admin role"),
+        (status = 404, description = "Blocked domain not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Blocklist"
+)]
+pub async fn delete_blocked_domain_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<BlocklistHandler>>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if !user_jwt.is_admin() {
+        return handlers.forbidden();
+    }
+    match handlers.blocklist_service.unblock_domain(id).await {
+        Ok(blocked) => Json(blocked).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
@@ -0,0 +1,113 @@
+use crate::feature::blocklist::entity::{
+    BLOCKED_DOMAINS_COLUMNS, BLOCKED_DOMAINS_CREATED_AT, BLOCKED_DOMAINS_CREATED_BY,
+    BLOCKED_DOMAINS_DOMAIN, BLOCKED_DOMAINS_ID, BLOCKED_DOMAINS_REASON, BLOCKED_DOMAINS_TABLE,
+    BlockedDomain,
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_query::{Alias, Expr, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::{Error, Pool, Postgres, query_as_with};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait BlocklistRepositoryTrait: Send + Sync {
+    async fn list_blocked_domains(&self) -> Result<Vec<BlockedDomain>, Error>;
+    async fn add_blocked_domain(
+        &self,
+        domain: String,
+        reason: Option<String>,
+        created_by: Uuid,
+    ) -> Result<BlockedDomain, Error>;
+    async fn delete_blocked_domain(&self, id: Uuid) -> Result<Option<BlockedDomain>, Error>;
+    async fn find_blocked_domain(
+        &self,
+        domains: Vec<String>,
+    ) -> Result<Option<BlockedDomain>, Error>;
+}
+
+#[derive(Clone)]
+pub struct BlocklistRepository {
+    primary_db: Pool<Postgres>,
+}
+
+impl BlocklistRepository {
+    pub fn new_blocklist_repository(primary_db: Pool<Postgres>) -> Self {
+        Self { primary_db }
+    }
+}
+
+#[async_trait]
+impl BlocklistRepositoryTrait for BlocklistRepository {
+    async fn list_blocked_domains(&self) -> Result<Vec<BlockedDomain>, Error> {
+        let (sql, values) = Query::select()
+            .columns(BLOCKED_DOMAINS_COLUMNS)
+            .from(BLOCKED_DOMAINS_TABLE)
+            .order_by(BLOCKED_DOMAINS_CREATED_AT, Order::Desc)
+            .build_sqlx(PostgresQueryBuilder);
+        let domains = query_as_with::<_, BlockedDomain, _>(&sql, values)
+            .fetch_all(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching blocked domains: {:?}", err);
+                err
+            })?;
+        Ok(domains)
+    }
+    async fn add_blocked_domain(
+        &self,
+        domain: String,
+        reason: Option<String>,
+        created_by: Uuid,
+    ) -> Result<BlockedDomain, Error> {
+        let (sql, values) = Query::insert()
+            .into_table(Alias::new(BLOCKED_DOMAINS_TABLE))
+            .columns([
+                Alias::new(BLOCKED_DOMAINS_DOMAIN),
+                Alias::new(BLOCKED_DOMAINS_REASON),
+                Alias::new(BLOCKED_DOMAINS_CREATED_BY),
+            ])
+            .values_panic([domain.into(), reason.into(), created_by.into()])
+            .returning(Query::returning().columns(BLOCKED_DOMAINS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let blocked = query_as_with::<_, BlockedDomain, _>(&sql, values)
+            .fetch_one(&self.primary_db)
+            .await?;
+        Ok(blocked)
+    }
+    async fn delete_blocked_domain(&self, id: Uuid) -> Result<Option<BlockedDomain>, Error> {
+        let (sql, values) = Query::delete()
+            .from_table(BLOCKED_DOMAINS_TABLE)
+            .and_where(Expr::col(BLOCKED_DOMAINS_ID).eq(id))
+            .returning(Query::returning().columns(BLOCKED_DOMAINS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let blocked = query_as_with::<_, BlockedDomain, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await?;
+        Ok(blocked)
+    }
+    async fn find_blocked_domain(
+        &self,
+        domains: Vec<String>,
+    ) -> Result<Option<BlockedDomain>, Error> {
+        if domains.is_empty() {
+            return Ok(None);
+        }
+        let (sql, values) = Query::select()
+            .columns(BLOCKED_DOMAINS_COLUMNS)
+            .from(BLOCKED_DOMAINS_TABLE)
+            .and_where(Expr::col(BLOCKED_DOMAINS_DOMAIN).is_in(domains))
+            .limit(1)
+            .build_sqlx(PostgresQueryBuilder);
+        let blocked = query_as_with::<_, BlockedDomain, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error checking blocked domains: {:?}", err);
+                err
+            })?;
+        Ok(blocked)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::feature::blocklist::entity::{
+    BlockedDomain, BlocklistError, CreateBlockedDomainDTO, normalize_domain,
+};
+use crate::feature::blocklist::repository::{BlocklistRepository, BlocklistRepositoryTrait};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait BlocklistServiceTrait: Send + Sync {
+    async fn list_blocked_domains(&self) -> Result<Vec<BlockedDomain>, BlocklistError>;
+    async fn block_domain(
+        &self,
+        dto: CreateBlockedDomainDTO,
+        created_by: Uuid,
+    ) -> Result<BlockedDomain, BlocklistError>;
+    async fn unblock_domain(&self, id: Uuid) -> Result<BlockedDomain, BlocklistError>;
+}
+
+pub struct BlocklistService {
+    blocklist_repository: Arc<BlocklistRepository>,
+}
+
+impl BlocklistService {
+    pub fn new(blocklist_repository: Arc<BlocklistRepository>) -> Self {
+        Self {
+            blocklist_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl BlocklistServiceTrait for BlocklistService {
+    async fn list_blocked_domains(&self) -> Result<Vec<BlockedDomain>, BlocklistError> {
+        Ok(self.blocklist_repository.list_blocked_domains().await?)
+    }
+    async fn block_domain(
+        &self,
+        dto: CreateBlockedDomainDTO,
+        created_by: Uuid,
+    ) -> Result<BlockedDomain, BlocklistError> {
+        self.blocklist_repository
+            .add_blocked_domain(normalize_domain(&dto.domain), dto.reason, created_by)
+            .await
+            .map_err(|err| match &err {
+                sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                    BlocklistError::AlreadyExists
+                }
+                _ => BlocklistError::Db(err),
+            })
+    }
+    async fn unblock_domain(&self, id: Uuid) -> Result<BlockedDomain, BlocklistError> {
+        self.blocklist_repository
+            .delete_blocked_domain(id)
+            .await?
+            .ok_or(BlocklistError::NotFound)
+    }
+}
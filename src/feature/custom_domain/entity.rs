@@ -0,0 +1,88 @@
+use crate::feature::blocklist::entity::validate_domain;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Error as SqlxError, FromRow};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct CustomDomain {
+    pub id: Uuid,
+    pub host: String,
+    pub owner_id: Uuid,
+    pub verification_token: String,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CustomDomain {
+    pub fn is_verified(&self) -> bool {
+        self.verified_at.is_some()
+    }
+}
+
+/// A domain together with the TXT record its owner has to publish.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DomainResponse {
+    #[serde(flatten)]
+    pub domain: CustomDomain,
+    #[schema(example = "_shortener-verify.go.example.com")]
+    pub txt_record_name: String,
+    pub txt_record_value: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateDomainDTO {
+    #[validate(custom(function = "validate_domain"))]
+    #[schema(example = "go.example.com")]
+    pub host: String,
+}
+
+#[derive(Debug)]
+pub enum DomainError {
+    NotFound,
+    Forbidden,
+    AlreadyExists,
+    InUse,
+    /// The TXT record is missing or does not hold the token.
+    VerificationFailed,
+    Resolver(String),
+    Db(SqlxError),
+}
+
+impl std::fmt::Display for DomainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DomainError::NotFound => write!(f, "domain not found"),
+            DomainError::Forbidden => write!(f, "domain belongs to another user"),
+            DomainError::AlreadyExists => write!(f, "domain is already registered"),
+            DomainError::InUse => write!(f, "domain still has short links"),
+            DomainError::VerificationFailed => write!(f, "verification TXT record not found"),
+            DomainError::Resolver(err) => write!(f, "dns lookup failed: {}", err),
+            DomainError::Db(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl From<SqlxError> for DomainError {
+    fn from(err: SqlxError) -> Self {
+        DomainError::Db(err)
+    }
+}
+
+pub const DOMAINS_TABLE: &str = "domains";
+pub const DOMAINS_ID: &str = "id";
+pub const DOMAINS_HOST: &str = "host";
+pub const DOMAINS_OWNER_ID: &str = "owner_id";
+pub const DOMAINS_VERIFICATION_TOKEN: &str = "verification_token";
+pub const DOMAINS_VERIFIED_AT: &str = "verified_at";
+pub const DOMAINS_CREATED_AT: &str = "created_at";
+pub const DOMAINS_COLUMNS: [&str; 6] = [
+    DOMAINS_ID,
+    DOMAINS_HOST,
+    DOMAINS_OWNER_ID,
+    DOMAINS_VERIFICATION_TOKEN,
+    DOMAINS_VERIFIED_AT,
+    DOMAINS_CREATED_AT,
+];
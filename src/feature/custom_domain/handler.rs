@@ -0,0 +1,208 @@
+use crate::feature::custom_domain::entity::{CreateDomainDTO, DomainError, DomainResponse};
+use crate::feature::custom_domain::service::{DomainService, DomainServiceTrait};
+use crate::metrics::PrometheusMetrics;
+use crate::servers::http::middleware::UserJWT;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+pub struct DomainHandler {
+    domain_service: Arc<DomainService>,
+    metrics: Arc<PrometheusMetrics>,
+}
+
+impl DomainHandler {
+    pub fn new_handler(
+        domain_service: Arc<DomainService>,
+        metrics: Arc<PrometheusMetrics>,
+    ) -> Self {
+        Self {
+            domain_service,
+            metrics,
+        }
+    }
+
+    fn error_response(&self, err: DomainError) -> Response {
+        match err {
+            DomainError::NotFound => {
+                self.metrics.inc_errors("not_found", "domain_handler");
+                (StatusCode::NOT_FOUND, Json("Domain not found".to_string())).into_response()
+            }
+            DomainError::Forbidden => {
+                self.metrics
+                    .inc_errors("authorization_error", "domain_handler");
+                (StatusCode::FORBIDDEN, Json("Forbidden".to_string())).into_response()
+            }
+            DomainError::AlreadyExists | DomainError::InUse => {
+                self.metrics.inc_errors("conflict", "domain_handler");
+                (StatusCode::CONFLICT, Json(err.to_string())).into_response()
+            }
+            DomainError::VerificationFailed => {
+                self.metrics
+                    .inc_errors("verification_error", "domain_handler");
+                (StatusCode::UNPROCESSABLE_ENTITY, Json(err.to_string())).into_response()
+            }
+            DomainError::Resolver(_) => {
+                eprintln!("❌ Domain verification error: {}", err);
+                self.metrics.inc_errors("dns_error", "domain_handler");
+                (
+                    StatusCode::BAD_GATEWAY,
+                    Json("Could not look up the verification record".to_string()),
+                )
+                    .into_response()
+            }
+            DomainError::Db(err) => {
+                eprintln!("❌ Domain handler error: {}", err);
+                self.metrics.inc_errors("database_error", "domain_handler");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json("Internal server error".to_string()),
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/private/domains",
+    responses(
+        (status = 200, description = "Custom domains of the current user, all of them for admins", body = Vec<DomainResponse>),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Domains"
+)]
+pub async fn list_domains_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<DomainHandler>>,
+) -> Response {
+    match handlers
+        .domain_service
+        .list_domains(user_jwt.id, user_jwt.is_admin())
+        .await
+    {
+        Ok(domains) => Json(domains).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/private/domains",
+    request_body = CreateDomainDTO,
+    responses(
+        (status = 201, description = "Domain registered, publish the TXT record to verify it", body = DomainResponse),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 409, description = "Domain is already registered"),
+        (status = 422, description = "Validation error"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Domains"
+)]
+pub async fn add_domain_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<DomainHandler>>,
+    Json(payload): Json<CreateDomainDTO>,
+) -> Response {
+    if let Err(validation_errors) = payload.validate() {
+        handlers
+            .metrics
+            .inc_errors("validation_error", "domain_handler");
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(format!("Validation error: {:?}", validation_errors)),
+        )
+            .into_response();
+    }
+    match handlers
+        .domain_service
+        .add_domain(payload, user_jwt.id)
+        .await
+    {
+        Ok(domain) => (StatusCode::CREATED, Json(domain)).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/private/domains/{id}/verify",
+    params(
+        ("id" = Uuid, Path, description = "Domain ID")
+    ),
+    responses(
+        (status = 200, description = "Domain verified", body = DomainResponse),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "Domain belongs to another user"),
+        (status = 404, description = "Domain not found"),
+        (status = 422, description = "Verification TXT record not found"),
+        (status = 502, description = "DNS lookup failed"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Domains"
+)]
+pub async fn verify_domain_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<DomainHandler>>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    match handlers
+        .domain_service
+        .verify_domain(id, user_jwt.id, user_jwt.is_admin())
+        .await
+    {
+        Ok(domain) => Json(domain).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/private/domains/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Domain ID")
+    ),
+    responses(
+        (status = 204, description = "Domain removed"),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "Domain belongs to another user"),
+        (status = 404, description = "Domain not found"),
+        (status = 409, description = "Domain still has short links"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Domains"
+)]
+pub async fn delete_domain_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<DomainHandler>>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    match handlers
+        .domain_service
+        .delete_domain(id, user_jwt.id, user_jwt.is_admin())
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
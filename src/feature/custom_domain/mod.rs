@@ -0,0 +1,5 @@
+pub mod entity;
+pub mod handler;
+pub mod repository;
+pub mod resolver;
+pub mod service;
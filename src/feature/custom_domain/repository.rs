@@ -0,0 +1,142 @@
+use crate::feature::custom_domain::entity::{
+    CustomDomain, DOMAINS_COLUMNS, DOMAINS_CREATED_AT, DOMAINS_HOST, DOMAINS_ID, DOMAINS_OWNER_ID,
+    DOMAINS_TABLE, DOMAINS_VERIFICATION_TOKEN, DOMAINS_VERIFIED_AT,
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_query::{Alias, Expr, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::{Error, Pool, Postgres, query_as_with};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait DomainRepositoryTrait: Send + Sync {
+    async fn list_domains(&self, owner: Option<Uuid>) -> Result<Vec<CustomDomain>, Error>;
+    async fn add_domain(
+        &self,
+        host: String,
+        owner_id: Uuid,
+        verification_token: String,
+    ) -> Result<CustomDomain, Error>;
+    async fn get_domain_by_id(&self, id: Uuid) -> Result<Option<CustomDomain>, Error>;
+    /// Unverified claims on a host never shadow the one verified owner.
+    async fn get_verified_domain_by_host(
+        &self,
+        host: String,
+    ) -> Result<Option<CustomDomain>, Error>;
+    async fn mark_verified(&self, id: Uuid) -> Result<Option<CustomDomain>, Error>;
+    async fn delete_domain(&self, id: Uuid) -> Result<Option<CustomDomain>, Error>;
+}
+
+#[derive(Clone)]
+pub struct DomainRepository {
+    primary_db: Pool<Postgres>,
+}
+
+impl DomainRepository {
+    pub fn new_domain_repository(primary_db: Pool<Postgres>) -> Self {
+        Self { primary_db }
+    }
+}
+
+#[async_trait]
+impl DomainRepositoryTrait for DomainRepository {
+    async fn list_domains(&self, owner: Option<Uuid>) -> Result<Vec<CustomDomain>, Error> {
+        let mut query = Query::select();
+        query.columns(DOMAINS_COLUMNS).from(DOMAINS_TABLE);
+        if let Some(owner) = owner {
+            query.and_where(Expr::col(DOMAINS_OWNER_ID).eq(owner));
+        }
+        let (sql, values) = query
+            .order_by(DOMAINS_CREATED_AT, Order::Desc)
+            .build_sqlx(PostgresQueryBuilder);
+        let domains = query_as_with::<_, CustomDomain, _>(&sql, values)
+            .fetch_all(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching domains: {:?}", err);
+                err
+            })?;
+        Ok(domains)
+    }
+    async fn add_domain(
+        &self,
+        host: String,
+        owner_id: Uuid,
+        verification_token: String,
+    ) -> Result<CustomDomain, Error> {
+        let (sql, values) = Query::insert()
+            .into_table(Alias::new(DOMAINS_TABLE))
+            .columns([
+                Alias::new(DOMAINS_HOST),
+                Alias::new(DOMAINS_OWNER_ID),
+                Alias::new(DOMAINS_VERIFICATION_TOKEN),
+            ])
+            .values_panic([host.into(), owner_id.into(), verification_token.into()])
+            .returning(Query::returning().columns(DOMAINS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let domain = query_as_with::<_, CustomDomain, _>(&sql, values)
+            .fetch_one(&self.primary_db)
+            .await?;
+        Ok(domain)
+    }
+    async fn get_domain_by_id(&self, id: Uuid) -> Result<Option<CustomDomain>, Error> {
+        let (sql, values) = Query::select()
+            .columns(DOMAINS_COLUMNS)
+            .from(DOMAINS_TABLE)
+            .and_where(Expr::col(DOMAINS_ID).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+        let domain = query_as_with::<_, CustomDomain, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching domain by id: {:?}", err);
+                err
+            })?;
+        Ok(domain)
+    }
+    async fn get_verified_domain_by_host(
+        &self,
+        host: String,
+    ) -> Result<Option<CustomDomain>, Error> {
+        let (sql, values) = Query::select()
+            .columns(DOMAINS_COLUMNS)
+            .from(DOMAINS_TABLE)
+            .and_where(Expr::col(DOMAINS_HOST).eq(host))
+            .and_where(Expr::col(DOMAINS_VERIFIED_AT).is_not_null())
+            .build_sqlx(PostgresQueryBuilder);
+        let domain = query_as_with::<_, CustomDomain, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching domain by host: {:?}", err);
+                err
+            })?;
+        Ok(domain)
+    }
+    async fn mark_verified(&self, id: Uuid) -> Result<Option<CustomDomain>, Error> {
+        let (sql, values) = Query::update()
+            .table(DOMAINS_TABLE)
+            .value(DOMAINS_VERIFIED_AT, Expr::current_timestamp())
+            .and_where(Expr::col(DOMAINS_ID).eq(id))
+            .returning(Query::returning().columns(DOMAINS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let domain = query_as_with::<_, CustomDomain, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await?;
+        Ok(domain)
+    }
+    async fn delete_domain(&self, id: Uuid) -> Result<Option<CustomDomain>, Error> {
+        let (sql, values) = Query::delete()
+            .from_table(DOMAINS_TABLE)
+            .and_where(Expr::col(DOMAINS_ID).eq(id))
+            .returning(Query::returning().columns(DOMAINS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let domain = query_as_with::<_, CustomDomain, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await?;
+        Ok(domain)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::app::config::{DomainsConfig, TxtResolverBackend};
+use crate::feature::blocklist::entity::normalize_domain;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// Looks up TXT records for domain verification. Kept behind a trait so the
+/// DNS backend can be swapped or stubbed.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait TxtResolver: Send + Sync {
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, String>;
+}
+
+#[derive(Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    data: String,
+}
+
+const DNS_TYPE_TXT: u16 = 16;
+const DNS_STATUS_NXDOMAIN: u32 = 3;
+
+/// Resolves through a DNS-over-HTTPS endpoint using the JSON API.
+pub struct DohTxtResolver {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl DohTxtResolver {
+    pub fn new(config: &DomainsConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.get_resolver_timeout())
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            endpoint: config.doh_url.clone(),
+        }
+    }
+}
+
+/// TXT data comes back as one or more quoted character strings,
+/// e.g. `"abc" "def"`, which together form a single value.
+fn unquote_txt(data: &str) -> String {
+    data.split('"').skip(1).step_by(2).collect::<String>()
+}
+
+#[async_trait]
+impl TxtResolver for DohTxtResolver {
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, String> {
+        let response = self
+            .client
+            .get(&self.endpoint)
+            .query(&[("name", name), ("type", "TXT")])
+            .header("accept", "application/dns-json")
+            .send()
+            .await
+            .map_err(|err| err.to_string())?
+            .error_for_status()
+            .map_err(|err| err.to_string())?
+            .json::<DohResponse>()
+            .await
+            .map_err(|err| err.to_string())?;
+        match response.status {
+            0 | DNS_STATUS_NXDOMAIN => Ok(response
+                .answer
+                .iter()
+                .filter(|answer| answer.record_type == DNS_TYPE_TXT)
+                .map(|answer| unquote_txt(&answer.data))
+                .collect()),
+            status => Err(format!("resolver returned status {}", status)),
+        }
+    }
+}
+
+/// Answers from a fixed list of records taken from the config.
+pub struct StaticTxtResolver {
+    records: Vec<(String, String)>,
+}
+
+impl StaticTxtResolver {
+    pub fn new(records: Vec<(String, String)>) -> Self {
+        Self { records }
+    }
+}
+
+#[async_trait]
+impl TxtResolver for StaticTxtResolver {
+    async fn lookup_txt(&self, name: &str) -> Result<Vec<String>, String> {
+        let name = normalize_domain(name);
+        Ok(self
+            .records
+            .iter()
+            .filter(|(record, _)| *record == name)
+            .map(|(_, value)| value.clone())
+            .collect())
+    }
+}
+
+pub fn new_txt_resolver(config: &DomainsConfig) -> Arc<dyn TxtResolver> {
+    match config.resolver {
+        TxtResolverBackend::Doh => Arc::new(DohTxtResolver::new(config)),
+        TxtResolverBackend::Static => Arc::new(StaticTxtResolver::new(
+            config
+                .static_records
+                .iter()
+                .map(|record| (normalize_domain(&record.name), record.value.clone()))
+                .collect(),
+        )),
+    }
+}
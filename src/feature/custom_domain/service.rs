@@ -0,0 +1,243 @@
+use crate::app::config::DomainsConfig;
+use crate::feature::blocklist::entity::normalize_domain;
+use crate::feature::custom_domain::entity::{
+    CreateDomainDTO, CustomDomain, DomainError, DomainResponse,
+};
+use crate::feature::custom_domain::repository::{DomainRepository, DomainRepositoryTrait};
+use crate::feature::custom_domain::resolver::TxtResolver;
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait DomainServiceTrait: Send + Sync {
+    async fn list_domains(
+        &self,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Vec<DomainResponse>, DomainError>;
+    async fn add_domain(
+        &self,
+        dto: CreateDomainDTO,
+        owner_id: Uuid,
+    ) -> Result<DomainResponse, DomainError>;
+    async fn verify_domain(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<DomainResponse, DomainError>;
+    async fn delete_domain(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<(), DomainError>;
+}
+
+pub struct DomainService {
+    domain_repository: Arc<DomainRepository>,
+    txt_resolver: Arc<dyn TxtResolver>,
+    config: DomainsConfig,
+}
+
+impl DomainService {
+    pub fn new(
+        domain_repository: Arc<DomainRepository>,
+        txt_resolver: Arc<dyn TxtResolver>,
+        config: DomainsConfig,
+    ) -> Self {
+        Self {
+            domain_repository,
+            txt_resolver,
+            config,
+        }
+    }
+
+    fn txt_record_name(&self, host: &str) -> String {
+        format!("{}.{}", self.config.txt_record_prefix, host)
+    }
+
+    fn to_response(&self, domain: CustomDomain) -> DomainResponse {
+        DomainResponse {
+            txt_record_name: self.txt_record_name(&domain.host),
+            txt_record_value: domain.verification_token.clone(),
+            domain,
+        }
+    }
+
+    async fn get_owned_domain(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<CustomDomain, DomainError> {
+        match self.domain_repository.get_domain_by_id(id).await? {
+            Some(domain) if domain.owner_id == user_id || is_admin => Ok(domain),
+            Some(_) => Err(DomainError::Forbidden),
+            None => Err(DomainError::NotFound),
+        }
+    }
+}
+
+/// A host is claimed once per owner and verified by at most one of them.
+fn map_unique_violation(err: sqlx::Error) -> DomainError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => DomainError::AlreadyExists,
+        _ => DomainError::Db(err),
+    }
+}
+
+/// Succeeds when one of the TXT records at `name` holds the token.
+async fn check_txt_record(
+    resolver: &dyn TxtResolver,
+    name: &str,
+    token: &str,
+) -> Result<(), DomainError> {
+    let records = resolver
+        .lookup_txt(name)
+        .await
+        .map_err(DomainError::Resolver)?;
+    if records.iter().any(|record| record.trim() == token) {
+        Ok(())
+    } else {
+        Err(DomainError::VerificationFailed)
+    }
+}
+
+#[async_trait]
+impl DomainServiceTrait for DomainService {
+    async fn list_domains(
+        &self,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Vec<DomainResponse>, DomainError> {
+        let owner = (!is_admin).then_some(user_id);
+        let domains = self.domain_repository.list_domains(owner).await?;
+        Ok(domains
+            .into_iter()
+            .map(|domain| self.to_response(domain))
+            .collect())
+    }
+    async fn add_domain(
+        &self,
+        dto: CreateDomainDTO,
+        owner_id: Uuid,
+    ) -> Result<DomainResponse, DomainError> {
+        let host = normalize_domain(&dto.host);
+        if self
+            .domain_repository
+            .get_verified_domain_by_host(host.clone())
+            .await?
+            .is_some()
+        {
+            return Err(DomainError::AlreadyExists);
+        }
+        let token = format!("shortener-verify={}", Uuid::new_v4().simple());
+        self.domain_repository
+            .add_domain(host, owner_id, token)
+            .await
+            .map(|domain| self.to_response(domain))
+            .map_err(map_unique_violation)
+    }
+    async fn verify_domain(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<DomainResponse, DomainError> {
+        let domain = self.get_owned_domain(id, user_id, is_admin).await?;
+        if domain.is_verified() {
+            return Ok(self.to_response(domain));
+        }
+        check_txt_record(
+            self.txt_resolver.as_ref(),
+            &self.txt_record_name(&domain.host),
+            &domain.verification_token,
+        )
+        .await?;
+        let verified = self
+            .domain_repository
+            .mark_verified(id)
+            .await
+            .map_err(map_unique_violation)?
+            .ok_or(DomainError::NotFound)?;
+        Ok(self.to_response(verified))
+    }
+    async fn delete_domain(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<(), DomainError> {
+        self.get_owned_domain(id, user_id, is_admin).await?;
+        self.domain_repository
+            .delete_domain(id)
+            .await
+            .map_err(|err| match &err {
+                sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                    DomainError::InUse
+                }
+                _ => DomainError::Db(err),
+            })?
+            .ok_or(DomainError::NotFound)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::custom_domain::resolver::MockTxtResolver;
+    use mockall::predicate::eq;
+
+    const RECORD_NAME: &str = "_shortener.links.example.com";
+    const TOKEN: &str = "shortener-verify=abc";
+
+    fn resolver_returning(result: Result<Vec<String>, String>) -> MockTxtResolver {
+        let mut resolver = MockTxtResolver::new();
+        resolver
+            .expect_lookup_txt()
+            .with(eq(RECORD_NAME))
+            .times(1)
+            .return_once(move |_| result);
+        resolver
+    }
+
+    #[tokio::test]
+    async fn accepts_a_matching_record_among_others() {
+        let resolver =
+            resolver_returning(Ok(vec!["v=spf1 -all".to_string(), format!(" {} ", TOKEN)]));
+
+        assert!(
+            check_txt_record(&resolver, RECORD_NAME, TOKEN)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_or_different_records() {
+        for records in [vec![], vec!["shortener-verify=other".to_string()]] {
+            let resolver = resolver_returning(Ok(records));
+
+            assert!(matches!(
+                check_txt_record(&resolver, RECORD_NAME, TOKEN).await,
+                Err(DomainError::VerificationFailed)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_resolver_errors() {
+        let resolver = resolver_returning(Err("timeout".to_string()));
+
+        assert!(matches!(
+            check_txt_record(&resolver, RECORD_NAME, TOKEN).await,
+            Err(DomainError::Resolver(err)) if err == "timeout"
+        ));
+    }
+}
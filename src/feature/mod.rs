@@ -1,2 +1,9 @@
+pub mod analytics;
+pub mod api_key;
 pub mod auth;
+pub mod blocklist;
+pub mod custom_domain;
+pub mod oauth;
+pub mod redirect_rule;
 pub mod url;
+pub mod variant;
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{Error as SqlxError, FromRow};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Deserialize, Validate, Serialize, Debug, ToSchema)]
+pub struct OAuthCallbackDTO {
+    #[validate(length(min = 1))]
+    pub code: String,
+    /// Value of the `state` parameter the provider sent back with the code.
+    #[validate(length(min = 1))]
+    pub state: String,
+}
+
+/// Provider account linked to a user, keyed by the provider's subject id.
+#[derive(Debug, Clone, FromRow)]
+pub struct OAuthIdentity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+}
+
+/// Identity returned by a provider after a successful code exchange.
+pub struct ExternalIdentity {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum OAuthError {
+    UnknownProvider,
+    Config(String),
+    Exchange(String),
+    InvalidCode,
+    InvalidIdToken(String),
+    EmailNotVerified,
+    Db(SqlxError),
+}
+
+impl std::fmt::Display for OAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OAuthError::UnknownProvider => write!(f, "unknown oauth provider"),
+            OAuthError::Config(err) => write!(f, "oauth misconfigured: {}", err),
+            OAuthError::Exchange(err) => write!(f, "provider request failed: {}", err),
+            OAuthError::InvalidCode => write!(f, "authorization code was rejected"),
+            OAuthError::InvalidIdToken(err) => write!(f, "invalid id token: {}", err),
+            OAuthError::EmailNotVerified => write!(f, "email is not verified"),
+            OAuthError::Db(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl From<SqlxError> for OAuthError {
+    fn from(err: SqlxError) -> Self {
+        OAuthError::Db(err)
+    }
+}
+
+pub const OAUTH_IDENTITIES_TABLE: &str = "oauth_identities";
+pub const OAUTH_IDENTITIES_ID: &str = "id";
+pub const OAUTH_IDENTITIES_USER_ID: &str = "user_id";
+pub const OAUTH_IDENTITIES_PROVIDER: &str = "provider";
+pub const OAUTH_IDENTITIES_SUBJECT: &str = "subject";
+pub const OAUTH_IDENTITIES_EMAIL: &str = "email";
+pub const OAUTH_IDENTITIES_LAST_LOGIN_AT: &str = "last_login_at";
+pub const OAUTH_IDENTITIES_COLUMNS: [&str; 2] = [OAUTH_IDENTITIES_ID, OAUTH_IDENTITIES_USER_ID];
@@ -0,0 +1,93 @@
+use crate::app::config::{
+    GoogleConfig, OAuthProviderConfig, OAuthProviderKind, default_oauth_scopes,
+};
+
+pub const GOOGLE_PROVIDER: &str = "google";
+
+/// The `google` provider described by a `[google]` section. Its endpoints
+/// are fixed, so no discovery request is made.
+pub fn google_provider(config: &GoogleConfig) -> OAuthProviderConfig {
+    let mut issuers = config.issuers.iter().cloned();
+    OAuthProviderConfig {
+        name: GOOGLE_PROVIDER.to_string(),
+        kind: OAuthProviderKind::Oidc,
+        issuer: issuers.next(),
+        extra_issuers: issuers.collect(),
+        client_id: config.client_id.clone(),
+        client_secret: config.client_secret.clone(),
+        redirect_uri: config.redirect_uri.clone(),
+        scopes: default_oauth_scopes(),
+        discovery_url: None,
+        authorization_url: Some(config.auth_url.clone()),
+        token_url: Some(config.token_url.clone()),
+        jwks_url: Some(config.certs_url.clone()),
+        userinfo_url: None,
+        pkce: true,
+    }
+}
+
+/// Configured providers, plus the `[google]` preset when `google` is not
+/// among them.
+pub fn with_google_preset(
+    providers: &[OAuthProviderConfig],
+    google: Option<&GoogleConfig>,
+) -> Vec<OAuthProviderConfig> {
+    let mut providers = providers.to_vec();
+    if let Some(google) = google
+        && !providers
+            .iter()
+            .any(|provider| provider.name == GOOGLE_PROVIDER)
+    {
+        providers.push(google_provider(google));
+    }
+    providers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preset_uses_the_google_endpoints_without_discovery() {
+        let provider = google_provider(&GoogleConfig::default());
+        assert_eq!(provider.name, GOOGLE_PROVIDER);
+        assert_eq!(
+            provider.issuer.as_deref(),
+            Some("https://accounts.google.com")
+        );
+        assert_eq!(
+            provider.extra_issuers,
+            vec!["accounts.google.com".to_string()]
+        );
+        assert_eq!(
+            provider.jwks_url.as_deref(),
+            Some("https://www.googleapis.com/oauth2/v3/certs")
+        );
+        assert!(provider.authorization_url.is_some() && provider.token_url.is_some());
+        assert_eq!(provider.client_id, "env:GOOGLE_CLIENT_ID");
+    }
+
+    #[test]
+    fn explicit_google_provider_wins_over_the_preset() {
+        let google = GoogleConfig {
+            token_url: "http://127.0.0.1:9/token".to_string(),
+            ..Default::default()
+        };
+        let providers = with_google_preset(&[], Some(&google));
+        assert_eq!(providers.len(), 1);
+        assert_eq!(
+            providers[0].token_url.as_deref(),
+            Some("http://127.0.0.1:9/token")
+        );
+
+        let explicit = OAuthProviderConfig {
+            issuer: Some("https://issuer.example.com".to_string()),
+            ..google_provider(&GoogleConfig::default())
+        };
+        let providers = with_google_preset(std::slice::from_ref(&explicit), Some(&google));
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].issuer, explicit.issuer);
+
+        assert!(with_google_preset(&[], None).is_empty());
+    }
+}
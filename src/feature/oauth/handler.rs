@@ -0,0 +1,272 @@
+use crate::feature::auth::token_service::{TokenService, TokenServiceTrait};
+use crate::feature::oauth::entity::{OAuthCallbackDTO, OAuthError};
+use crate::feature::oauth::service::{OAuthService, OAuthServiceTrait};
+use crate::metrics::PrometheusMetrics;
+use crate::utils::constants::{OAUTH_STATE_COOKIE, OAUTH_STATE_LENGTH, PKCE_VERIFIER_LENGTH};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use rand::Rng;
+use rand::distributions::Alphanumeric;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use validator::Validate;
+
+pub struct OAuthHandler {
+    oauth_service: Arc<OAuthService>,
+    token_service: Arc<TokenService>,
+    metrics: Arc<PrometheusMetrics>,
+    state_ttl: Duration,
+}
+
+fn random_token(length: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+/// Login attempt kept in the state cookie between the redirect to the
+/// provider and the callback.
+struct PendingLogin {
+    provider: String,
+    state: String,
+    code_verifier: String,
+}
+
+impl PendingLogin {
+    fn encode(&self) -> String {
+        format!("{}:{}:{}", self.provider, self.state, self.code_verifier)
+    }
+
+    /// State and verifier are alphanumeric, so the provider name is whatever
+    /// precedes the last two separators.
+    fn decode(value: &str) -> Option<Self> {
+        let mut parts = value.rsplitn(3, ':');
+        let code_verifier = parts.next()?.to_string();
+        let state = parts.next()?.to_string();
+        let provider = parts.next()?.to_string();
+        Some(Self {
+            provider,
+            state,
+            code_verifier,
+        })
+    }
+}
+
+impl OAuthHandler {
+    pub fn new_handler(
+        oauth_service: Arc<OAuthService>,
+        token_service: Arc<TokenService>,
+        metrics: Arc<PrometheusMetrics>,
+        state_ttl: Duration,
+    ) -> Self {
+        Self {
+            oauth_service,
+            token_service,
+            metrics,
+            state_ttl,
+        }
+    }
+
+    fn state_cookie(&self, login: &PendingLogin) -> CookieJar {
+        let cookie = Cookie::build((OAUTH_STATE_COOKIE, login.encode()))
+            .http_only(true)
+            .secure(true)
+            .same_site(SameSite::Lax)
+            .max_age(cookie::time::Duration::seconds(
+                self.state_ttl.as_secs() as i64
+            ))
+            .path("/")
+            .build();
+        CookieJar::new().add(cookie)
+    }
+
+    fn error_response(&self, err: OAuthError) -> Response {
+        let status = match err {
+            OAuthError::UnknownProvider => {
+                self.metrics.inc_errors("not_found", "oauth_handler");
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({ "error": "Unknown OAuth provider" })),
+                )
+                    .into_response();
+            }
+            OAuthError::InvalidCode
+            | OAuthError::InvalidIdToken(_)
+            | OAuthError::EmailNotVerified => {
+                self.metrics
+                    .inc_errors("authorization_error", "oauth_handler");
+                StatusCode::UNAUTHORIZED
+            }
+            OAuthError::Exchange(_) => StatusCode::BAD_GATEWAY,
+            OAuthError::Config(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            OAuthError::Db(_) => {
+                self.metrics.inc_errors("database_error", "oauth_handler");
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        eprintln!("❌ OAuth error: {}", err);
+        (status, Json(json!({ "error": "OAuth sign-in failed" }))).into_response()
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/auth/{provider}/url",
+    params(
+        ("provider" = String, Path, description = "Provider name from the OAuth config")
+    ),
+    responses(
+        (status = 302, description = "Redirect to the provider's authorization page"),
+        (status = 404, description = "Unknown provider"),
+        (status = 500, description = "Internal server error"),
+        (status = 502, description = "Provider discovery failed")
+    ),
+    tag = "Auth"
+)]
+pub async fn oauth_url_handler(
+    State(handler): State<Arc<OAuthHandler>>,
+    Path(provider): Path<String>,
+) -> Response {
+    let login = PendingLogin {
+        provider,
+        state: random_token(OAUTH_STATE_LENGTH),
+        code_verifier: random_token(PKCE_VERIFIER_LENGTH),
+    };
+    match handler
+        .oauth_service
+        .authorization_url(&login.provider, &login.state, &login.code_verifier)
+        .await
+    {
+        Ok(url) => (handler.state_cookie(&login), Redirect::temporary(&url)).into_response(),
+        Err(err) => handler.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/auth/{provider}/callback",
+    params(
+        ("provider" = String, Path, description = "Provider name from the OAuth config")
+    ),
+    request_body = OAuthCallbackDTO,
+    responses(
+        (status = 200, description = "Signed in, token cookies set"),
+        (status = 400, description = "OAuth state does not match"),
+        (status = 401, description = "Code or ID token rejected, or email not verified"),
+        (status = 404, description = "Unknown provider"),
+        (status = 422, description = "Validation error"),
+        (status = 500, description = "Internal server error"),
+        (status = 502, description = "Bad gateway")
+    ),
+    tag = "Auth"
+)]
+pub async fn oauth_callback_handler(
+    State(handler): State<Arc<OAuthHandler>>,
+    Path(provider): Path<String>,
+    jar: CookieJar,
+    Json(payload): Json<OAuthCallbackDTO>,
+) -> Response {
+    if let Err(validation_errors) = payload.validate() {
+        handler
+            .metrics
+            .inc_errors("validation_error", "oauth_handler");
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({ "error": validation_errors })),
+        )
+            .into_response();
+    }
+    let Some(login) = jar
+        .get(OAUTH_STATE_COOKIE)
+        .and_then(|cookie| PendingLogin::decode(cookie.value()))
+        .filter(|login| login.provider == provider && login.state == payload.state)
+    else {
+        handler
+            .metrics
+            .inc_errors("validation_error", "oauth_handler");
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Invalid OAuth state" })),
+        )
+            .into_response();
+    };
+
+    let user = match handler
+        .oauth_service
+        .sign_in(&provider, &payload.code, &login.code_verifier)
+        .await
+    {
+        Ok(user) => user,
+        Err(err) => return handler.error_response(err),
+    };
+
+    match handler
+        .token_service
+        .issue_session(user.id, user.role.clone())
+        .await
+    {
+        Ok(cookies) => (
+            StatusCode::OK,
+            jar.remove(Cookie::build(OAUTH_STATE_COOKIE).path("/")),
+            cookies,
+            Json(json!({ "user": user })),
+        )
+            .into_response(),
+        Err(err) => {
+            eprintln!("❌ JWT generation error: {err}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to create session" })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn login(provider: &str) -> PendingLogin {
+        PendingLogin {
+            provider: provider.to_string(),
+            state: random_token(OAUTH_STATE_LENGTH),
+            code_verifier: random_token(PKCE_VERIFIER_LENGTH),
+        }
+    }
+
+    #[test]
+    fn pending_login_round_trips() {
+        for provider in ["google", "corp:keycloak"] {
+            let login = login(provider);
+            let decoded = PendingLogin::decode(&login.encode()).unwrap();
+
+            assert_eq!(decoded.provider, login.provider);
+            assert_eq!(decoded.state, login.state);
+            assert_eq!(decoded.code_verifier, login.code_verifier);
+        }
+    }
+
+    #[test]
+    fn pending_login_rejects_truncated_values() {
+        assert!(PendingLogin::decode("").is_none());
+        assert!(PendingLogin::decode("state").is_none());
+        assert!(PendingLogin::decode("state:verifier").is_none());
+    }
+
+    #[test]
+    fn random_tokens_are_alphanumeric() {
+        let token = random_token(64);
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_ne!(token, random_token(64));
+    }
+}
@@ -0,0 +1,6 @@
+pub mod entity;
+pub mod google;
+pub mod handler;
+pub mod provider;
+pub mod repository;
+pub mod service;
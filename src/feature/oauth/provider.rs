@@ -0,0 +1,602 @@
+use crate::app::config::{GoogleConfig, OAuthConfig, OAuthProviderConfig, OAuthProviderKind};
+use crate::feature::oauth::entity::{ExternalIdentity, OAuthError};
+use crate::feature::oauth::google::with_google_preset;
+use crate::utils::secret::load_material;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header};
+use reqwest::StatusCode;
+use reqwest::header::{ACCEPT, USER_AGENT};
+use serde::{Deserialize, Deserializer};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use url::Url;
+
+const DISCOVERY_PATH: &str = "/.well-known/openid-configuration";
+const GITHUB_AUTHORIZATION_URL: &str = "https://github.com/login/oauth/authorize";
+const GITHUB_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const GITHUB_USER_URL: &str = "https://api.github.com/user";
+const GITHUB_USER_AGENT: &str = "url-shortener";
+
+/// Endpoints after applying config overrides on top of discovery.
+struct Endpoints {
+    authorization: String,
+    token: String,
+    jwks: Option<String>,
+    userinfo: Option<String>,
+    issuers: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct DiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: Option<String>,
+    userinfo_endpoint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    id_token: Option<String>,
+    /// GitHub reports a bad code with status 200 and this field set.
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    email: Option<String>,
+    #[serde(default, deserialize_with = "bool_or_string")]
+    email_verified: bool,
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GithubUser {
+    id: i64,
+    login: String,
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GithubEmail {
+    email: String,
+    primary: bool,
+    verified: bool,
+}
+
+/// Some providers send `email_verified` as the string `"true"`.
+fn bool_or_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrString {
+        Bool(bool),
+        String(String),
+    }
+    Ok(match BoolOrString::deserialize(deserializer)? {
+        BoolOrString::Bool(value) => value,
+        BoolOrString::String(value) => value.eq_ignore_ascii_case("true"),
+    })
+}
+
+/// S256 code challenge sent with the authorization request.
+pub fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+fn exchange_error(err: reqwest::Error) -> OAuthError {
+    OAuthError::Exchange(err.to_string())
+}
+
+pub struct OAuthProvider {
+    config: OAuthProviderConfig,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    client: reqwest::Client,
+    /// Discovery is fetched on first use; a failed attempt is retried.
+    endpoints: OnceCell<Endpoints>,
+}
+
+impl OAuthProvider {
+    fn new(config: &OAuthProviderConfig, client: reqwest::Client) -> Result<Self, String> {
+        Ok(Self {
+            config: config.clone(),
+            client_id: load_material(&config.client_id)?,
+            client_secret: load_material(&config.client_secret)?,
+            redirect_uri: load_material(&config.redirect_uri)?,
+            client,
+            endpoints: OnceCell::new(),
+        })
+    }
+
+    pub async fn authorization_url(
+        &self,
+        state: &str,
+        code_verifier: &str,
+    ) -> Result<String, OAuthError> {
+        let endpoints = self.endpoints().await?;
+        let mut url = Url::parse(&endpoints.authorization)
+            .map_err(|err| OAuthError::Config(format!("invalid authorization url: {}", err)))?;
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("response_type", "code")
+            .append_pair("redirect_uri", &self.redirect_uri)
+            .append_pair("scope", &self.config.scopes.join(" "))
+            .append_pair("state", state);
+        if self.config.pkce {
+            url.query_pairs_mut()
+                .append_pair("code_challenge", &pkce_challenge(code_verifier))
+                .append_pair("code_challenge_method", "S256");
+        }
+        Ok(url.to_string())
+    }
+
+    /// Exchanges an authorization code and reads the identity of the user
+    /// who granted it.
+    pub async fn exchange_code(
+        &self,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<ExternalIdentity, OAuthError> {
+        let endpoints = self.endpoints().await?;
+        let mut params = vec![
+            ("code", code),
+            ("client_id", self.client_id.as_str()),
+            ("client_secret", self.client_secret.as_str()),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("grant_type", "authorization_code"),
+        ];
+        if self.config.pkce {
+            params.push(("code_verifier", code_verifier));
+        }
+        let response = self
+            .client
+            .post(&endpoints.token)
+            .header(ACCEPT, "application/json")
+            .form(&params)
+            .send()
+            .await
+            .map_err(exchange_error)?;
+        if response.status() == StatusCode::BAD_REQUEST {
+            return Err(OAuthError::InvalidCode);
+        }
+        let tokens = response
+            .error_for_status()
+            .map_err(exchange_error)?
+            .json::<TokenResponse>()
+            .await
+            .map_err(exchange_error)?;
+        if tokens.error.is_some() {
+            return Err(OAuthError::InvalidCode);
+        }
+
+        match self.config.kind {
+            OAuthProviderKind::Oidc => {
+                let id_token = tokens.id_token.ok_or_else(|| {
+                    OAuthError::InvalidIdToken("token response has no id_token".to_string())
+                })?;
+                self.verify_id_token(endpoints, &id_token).await
+            }
+            OAuthProviderKind::Github => {
+                let access_token = tokens.access_token.ok_or_else(|| {
+                    OAuthError::Exchange("token response has no access_token".to_string())
+                })?;
+                self.github_identity(endpoints, &access_token).await
+            }
+        }
+    }
+
+    async fn endpoints(&self) -> Result<&Endpoints, OAuthError> {
+        self.endpoints
+            .get_or_try_init(|| self.load_endpoints())
+            .await
+    }
+
+    async fn load_endpoints(&self) -> Result<Endpoints, OAuthError> {
+        let config = &self.config;
+        let explicit = config.authorization_url.is_some()
+            && config.token_url.is_some()
+            && (config.kind == OAuthProviderKind::Github || config.jwks_url.is_some());
+        let discovery = if config.kind == OAuthProviderKind::Oidc
+            && (!explicit || config.discovery_url.is_some())
+        {
+            Some(self.discover().await?)
+        } else {
+            None
+        };
+
+        let (github_authorization, github_token, github_user) = match config.kind {
+            OAuthProviderKind::Github => (
+                Some(GITHUB_AUTHORIZATION_URL.to_string()),
+                Some(GITHUB_TOKEN_URL.to_string()),
+                Some(GITHUB_USER_URL.to_string()),
+            ),
+            OAuthProviderKind::Oidc => (None, None, None),
+        };
+        let authorization = config
+            .authorization_url
+            .clone()
+            .or_else(|| discovery.as_ref().map(|d| d.authorization_endpoint.clone()))
+            .or(github_authorization)
+            .ok_or_else(|| OAuthError::Config("authorization url is missing".to_string()))?;
+        let token = config
+            .token_url
+            .clone()
+            .or_else(|| discovery.as_ref().map(|d| d.token_endpoint.clone()))
+            .or(github_token)
+            .ok_or_else(|| OAuthError::Config("token url is missing".to_string()))?;
+        let jwks = config
+            .jwks_url
+            .clone()
+            .or_else(|| discovery.as_ref().and_then(|d| d.jwks_uri.clone()));
+        let userinfo = config
+            .userinfo_url
+            .clone()
+            .or_else(|| discovery.as_ref().and_then(|d| d.userinfo_endpoint.clone()))
+            .or(github_user);
+
+        let mut issuers: Vec<String> = config
+            .issuer
+            .iter()
+            .chain(discovery.as_ref().map(|d| &d.issuer))
+            .chain(config.extra_issuers.iter())
+            .cloned()
+            .collect();
+        issuers.dedup();
+
+        Ok(Endpoints {
+            authorization,
+            token,
+            jwks,
+            userinfo,
+            issuers,
+        })
+    }
+
+    async fn discover(&self) -> Result<DiscoveryDocument, OAuthError> {
+        let url = match (&self.config.discovery_url, &self.config.issuer) {
+            (Some(url), _) => url.clone(),
+            (None, Some(issuer)) => format!("{}{}", issuer.trim_end_matches('/'), DISCOVERY_PATH),
+            (None, None) => {
+                return Err(OAuthError::Config(
+                    "either issuer or discovery_url is required".to_string(),
+                ));
+            }
+        };
+        self.client
+            .get(&url)
+            .send()
+            .await
+            .map_err(exchange_error)?
+            .error_for_status()
+            .map_err(exchange_error)?
+            .json::<DiscoveryDocument>()
+            .await
+            .map_err(exchange_error)
+    }
+
+    async fn verify_id_token(
+        &self,
+        endpoints: &Endpoints,
+        id_token: &str,
+    ) -> Result<ExternalIdentity, OAuthError> {
+        let jwks_url = endpoints
+            .jwks
+            .as_ref()
+            .ok_or_else(|| OAuthError::Config("jwks url is missing".to_string()))?;
+        if endpoints.issuers.is_empty() {
+            return Err(OAuthError::Config("issuer is missing".to_string()));
+        }
+        let header =
+            decode_header(id_token).map_err(|err| OAuthError::InvalidIdToken(err.to_string()))?;
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            return Err(OAuthError::InvalidIdToken(
+                "symmetric algorithms are not accepted".to_string(),
+            ));
+        }
+        let kid = header
+            .kid
+            .ok_or_else(|| OAuthError::InvalidIdToken("kid is missing".to_string()))?;
+        let jwks = self
+            .client
+            .get(jwks_url)
+            .send()
+            .await
+            .map_err(exchange_error)?
+            .error_for_status()
+            .map_err(exchange_error)?
+            .json::<JwkSet>()
+            .await
+            .map_err(exchange_error)?;
+        let jwk = jwks
+            .find(&kid)
+            .ok_or_else(|| OAuthError::InvalidIdToken(format!("unknown kid {}", kid)))?;
+        let key = DecodingKey::from_jwk(jwk)
+            .map_err(|err| OAuthError::InvalidIdToken(err.to_string()))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[&self.client_id]);
+        validation.set_issuer(&endpoints.issuers);
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|err| OAuthError::InvalidIdToken(err.to_string()))?
+            .claims;
+        Ok(ExternalIdentity {
+            subject: claims.sub,
+            email: claims.email,
+            email_verified: claims.email_verified,
+            name: claims.name,
+        })
+    }
+
+    async fn github_identity(
+        &self,
+        endpoints: &Endpoints,
+        access_token: &str,
+    ) -> Result<ExternalIdentity, OAuthError> {
+        let user_url = endpoints
+            .userinfo
+            .as_ref()
+            .ok_or_else(|| OAuthError::Config("userinfo url is missing".to_string()))?;
+        let user = self
+            .github_get::<GithubUser>(user_url, access_token)
+            .await?;
+        // The profile email is optional and may be unverified, so the primary
+        // address is taken from the emails endpoint instead.
+        let emails = self
+            .github_get::<Vec<GithubEmail>>(&format!("{}/emails", user_url), access_token)
+            .await?;
+        let email = emails
+            .into_iter()
+            .find(|email| email.primary && email.verified)
+            .map(|email| email.email);
+        Ok(ExternalIdentity {
+            subject: user.id.to_string(),
+            email_verified: email.is_some(),
+            email,
+            name: user.name.or(Some(user.login)),
+        })
+    }
+
+    async fn github_get<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        access_token: &str,
+    ) -> Result<T, OAuthError> {
+        self.client
+            .get(url)
+            .bearer_auth(access_token)
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(USER_AGENT, GITHUB_USER_AGENT)
+            .send()
+            .await
+            .map_err(exchange_error)?
+            .error_for_status()
+            .map_err(exchange_error)?
+            .json::<T>()
+            .await
+            .map_err(exchange_error)
+    }
+}
+
+/// Configured providers by name. Providers whose credentials cannot be
+/// loaded are left out so the rest keep working.
+pub struct OAuthProviderRegistry {
+    providers: HashMap<String, Arc<OAuthProvider>>,
+}
+
+impl OAuthProviderRegistry {
+    pub fn from_config(config: &OAuthConfig, google: Option<&GoogleConfig>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.get_timeout())
+            .build()
+            .unwrap_or_default();
+        let mut providers = HashMap::new();
+        for provider in &with_google_preset(&config.providers, google) {
+            match OAuthProvider::new(provider, client.clone()) {
+                Ok(loaded) => {
+                    providers.insert(provider.name.clone(), Arc::new(loaded));
+                }
+                Err(err) => {
+                    log::warn!("OAuth provider {} is disabled: {}", provider.name, err);
+                }
+            }
+        }
+        Self { providers }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<OAuthProvider>> {
+        self.providers.get(name).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Form, State};
+    use axum::http::StatusCode as HttpStatusCode;
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+    use jsonwebtoken::{EncodingKey, Header, encode};
+    use rsa::RsaPrivateKey;
+    use rsa::pkcs1::EncodeRsaPrivateKey;
+    use rsa::traits::PublicKeyParts;
+    use serde_json::{Value, json};
+    use std::sync::OnceLock;
+
+    const CLIENT_ID: &str = "test-client";
+    const CODE: &str = "test-code";
+    const CODE_VERIFIER: &str = "test-verifier";
+    const KID: &str = "test-key";
+
+    #[derive(Clone)]
+    struct MockProvider {
+        issuer: String,
+        jwks: Value,
+        id_token: String,
+    }
+
+    async fn discovery_endpoint(State(mock): State<Arc<MockProvider>>) -> Json<Value> {
+        Json(json!({
+            "issuer": mock.issuer,
+            "authorization_endpoint": format!("{}/authorize", mock.issuer),
+            "token_endpoint": format!("{}/token", mock.issuer),
+            "jwks_uri": format!("{}/jwks", mock.issuer),
+        }))
+    }
+
+    async fn jwks_endpoint(State(mock): State<Arc<MockProvider>>) -> Json<Value> {
+        Json(mock.jwks.clone())
+    }
+
+    /// Only hands out the ID token for the expected code and PKCE verifier.
+    async fn token_endpoint(
+        State(mock): State<Arc<MockProvider>>,
+        Form(params): Form<HashMap<String, String>>,
+    ) -> Response {
+        let param = |name: &str| params.get(name).map(String::as_str);
+        if param("code") != Some(CODE)
+            || param("code_verifier") != Some(CODE_VERIFIER)
+            || param("client_id") != Some(CLIENT_ID)
+        {
+            return (
+                HttpStatusCode::BAD_REQUEST,
+                Json(json!({"error": "invalid_grant"})),
+            )
+                .into_response();
+        }
+        Json(json!({"access_token": "access", "id_token": mock.id_token})).into_response()
+    }
+
+    /// Generating RSA keys is slow in debug builds, so the tests share one.
+    fn signing_key() -> &'static RsaPrivateKey {
+        static KEY: OnceLock<RsaPrivateKey> = OnceLock::new();
+        KEY.get_or_init(|| RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap())
+    }
+
+    /// Starts an OIDC provider on a local port that signs ID tokens with a
+    /// fresh RSA key, and returns a client configured against it.
+    async fn mock_provider(claims: impl Fn(&str) -> Value) -> OAuthProvider {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let issuer = format!("http://{}", listener.local_addr().unwrap());
+
+        let key = signing_key();
+        let pem = key.to_pkcs1_pem(Default::default()).unwrap();
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(KID.to_string());
+        let id_token = encode(
+            &header,
+            &claims(&issuer),
+            &EncodingKey::from_rsa_pem(pem.as_bytes()).unwrap(),
+        )
+        .unwrap();
+        let jwks = json!({"keys": [{
+            "kty": "RSA",
+            "kid": KID,
+            "alg": "RS256",
+            "use": "sig",
+            "n": URL_SAFE_NO_PAD.encode(key.n().to_bytes_be()),
+            "e": URL_SAFE_NO_PAD.encode(key.e().to_bytes_be()),
+        }]});
+
+        let mock = Arc::new(MockProvider {
+            issuer: issuer.clone(),
+            jwks,
+            id_token,
+        });
+        let router = Router::new()
+            .route(DISCOVERY_PATH, get(discovery_endpoint))
+            .route("/jwks", get(jwks_endpoint))
+            .route("/token", post(token_endpoint))
+            .with_state(mock);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let config = OAuthProviderConfig {
+            name: "mock".to_string(),
+            kind: OAuthProviderKind::Oidc,
+            issuer: Some(issuer),
+            extra_issuers: Vec::new(),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: "test-secret".to_string(),
+            redirect_uri: "http://localhost/callback".to_string(),
+            scopes: vec!["openid".to_string(), "email".to_string()],
+            discovery_url: None,
+            authorization_url: None,
+            token_url: None,
+            jwks_url: None,
+            userinfo_url: None,
+            pkce: true,
+        };
+        OAuthProvider::new(&config, reqwest::Client::new()).unwrap()
+    }
+
+    fn valid_claims(issuer: &str) -> Value {
+        json!({
+            "iss": issuer,
+            "aud": CLIENT_ID,
+            "sub": "subject-1",
+            "email": "user@example.com",
+            "email_verified": "true",
+            "name": "Test User",
+            "exp": chrono::Utc::now().timestamp() + 300,
+        })
+    }
+
+    #[tokio::test]
+    async fn authorization_url_uses_discovery_and_pkce() {
+        let provider = mock_provider(valid_claims).await;
+        let url = provider
+            .authorization_url("state-1", CODE_VERIFIER)
+            .await
+            .unwrap();
+        let url = Url::parse(&url).unwrap();
+        let query: HashMap<_, _> = url.query_pairs().into_owned().collect();
+
+        assert_eq!(url.path(), "/authorize");
+        assert_eq!(query["state"], "state-1");
+        assert_eq!(query["client_id"], CLIENT_ID);
+        assert_eq!(query["code_challenge"], pkce_challenge(CODE_VERIFIER));
+        assert_eq!(query["code_challenge_method"], "S256");
+    }
+
+    #[tokio::test]
+    async fn exchange_code_verifies_the_id_token() {
+        let provider = mock_provider(valid_claims).await;
+        let identity = provider.exchange_code(CODE, CODE_VERIFIER).await.unwrap();
+
+        assert_eq!(identity.subject, "subject-1");
+        assert_eq!(identity.email.as_deref(), Some("user@example.com"));
+        assert!(identity.email_verified);
+        assert_eq!(identity.name.as_deref(), Some("Test User"));
+    }
+
+    #[tokio::test]
+    async fn exchange_code_rejects_a_wrong_verifier() {
+        let provider = mock_provider(valid_claims).await;
+        let result = provider.exchange_code(CODE, "other-verifier").await;
+
+        assert!(matches!(result, Err(OAuthError::InvalidCode)));
+    }
+
+    #[tokio::test]
+    async fn exchange_code_rejects_another_audience() {
+        let provider = mock_provider(|issuer| {
+            let mut claims = valid_claims(issuer);
+            claims["aud"] = json!("other-client");
+            claims
+        })
+        .await;
+        let result = provider.exchange_code(CODE, CODE_VERIFIER).await;
+
+        assert!(matches!(result, Err(OAuthError::InvalidIdToken(_))));
+    }
+}
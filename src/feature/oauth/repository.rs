@@ -0,0 +1,120 @@
+use crate::feature::oauth::entity::{
+    OAUTH_IDENTITIES_COLUMNS, OAUTH_IDENTITIES_EMAIL, OAUTH_IDENTITIES_ID,
+    OAUTH_IDENTITIES_LAST_LOGIN_AT, OAUTH_IDENTITIES_PROVIDER, OAUTH_IDENTITIES_SUBJECT,
+    OAUTH_IDENTITIES_TABLE, OAUTH_IDENTITIES_USER_ID, OAuthIdentity,
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_query::{Alias, Expr, OnConflict, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::{Error, Pool, Postgres, query_as_with, query_with};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait OAuthIdentityRepositoryTrait: Send + Sync {
+    async fn find_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> Result<Option<OAuthIdentity>, Error>;
+    async fn link_identity(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+        email: Option<String>,
+    ) -> Result<(), Error>;
+    async fn touch_identity(&self, id: Uuid) -> Result<(), Error>;
+}
+
+#[derive(Clone)]
+pub struct OAuthIdentityRepository {
+    primary_db: Pool<Postgres>,
+}
+
+impl OAuthIdentityRepository {
+    pub fn new_oauth_identity_repository(primary_db: Pool<Postgres>) -> Self {
+        Self { primary_db }
+    }
+}
+
+#[async_trait]
+impl OAuthIdentityRepositoryTrait for OAuthIdentityRepository {
+    async fn find_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> Result<Option<OAuthIdentity>, Error> {
+        let (sql, values) = Query::select()
+            .columns(OAUTH_IDENTITIES_COLUMNS)
+            .from(OAUTH_IDENTITIES_TABLE)
+            .and_where(Expr::col(OAUTH_IDENTITIES_PROVIDER).eq(provider))
+            .and_where(Expr::col(OAUTH_IDENTITIES_SUBJECT).eq(subject))
+            .build_sqlx(PostgresQueryBuilder);
+        let identity = query_as_with::<_, OAuthIdentity, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching oauth identity: {:?}", err);
+                err
+            })?;
+        Ok(identity)
+    }
+    /// Linking the same provider account twice is a no-op.
+    async fn link_identity(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+        email: Option<String>,
+    ) -> Result<(), Error> {
+        let (sql, values) = Query::insert()
+            .into_table(Alias::new(OAUTH_IDENTITIES_TABLE))
+            .columns([
+                Alias::new(OAUTH_IDENTITIES_USER_ID),
+                Alias::new(OAUTH_IDENTITIES_PROVIDER),
+                Alias::new(OAUTH_IDENTITIES_SUBJECT),
+                Alias::new(OAUTH_IDENTITIES_EMAIL),
+            ])
+            .values_panic([
+                user_id.into(),
+                provider.into(),
+                subject.into(),
+                email.into(),
+            ])
+            .on_conflict(
+                OnConflict::columns([
+                    Alias::new(OAUTH_IDENTITIES_PROVIDER),
+                    Alias::new(OAUTH_IDENTITIES_SUBJECT),
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+        query_with(&sql, values)
+            .execute(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error linking oauth identity: {:?}", err);
+                err
+            })?;
+        Ok(())
+    }
+    async fn touch_identity(&self, id: Uuid) -> Result<(), Error> {
+        let (sql, values) = Query::update()
+            .table(OAUTH_IDENTITIES_TABLE)
+            .value(OAUTH_IDENTITIES_LAST_LOGIN_AT, Expr::current_timestamp())
+            .and_where(Expr::col(OAUTH_IDENTITIES_ID).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+        query_with(&sql, values)
+            .execute(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error updating oauth identity: {:?}", err);
+                err
+            })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use crate::feature::auth::entity::UserDB;
+use crate::feature::auth::repository::{UserRepository, UserRepositoryTrait};
+use crate::feature::oauth::entity::OAuthError;
+use crate::feature::oauth::provider::OAuthProviderRegistry;
+use crate::feature::oauth::repository::{OAuthIdentityRepository, OAuthIdentityRepositoryTrait};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use std::sync::Arc;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait OAuthServiceTrait: Send + Sync {
+    async fn authorization_url(
+        &self,
+        provider: &str,
+        state: &str,
+        code_verifier: &str,
+    ) -> Result<String, OAuthError>;
+    async fn sign_in(
+        &self,
+        provider: &str,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<UserDB, OAuthError>;
+}
+
+pub struct OAuthService {
+    registry: OAuthProviderRegistry,
+    identity_repository: Arc<OAuthIdentityRepository>,
+    user_repository: Arc<UserRepository>,
+}
+
+impl OAuthService {
+    pub fn new(
+        registry: OAuthProviderRegistry,
+        identity_repository: Arc<OAuthIdentityRepository>,
+        user_repository: Arc<UserRepository>,
+    ) -> Self {
+        Self {
+            registry,
+            identity_repository,
+            user_repository,
+        }
+    }
+
+    /// Returns the account with this email, creating a password-less one
+    /// when there is none.
+    async fn find_or_create_user(
+        &self,
+        email: String,
+        title: String,
+    ) -> Result<UserDB, OAuthError> {
+        if let Some(user) = self
+            .user_repository
+            .get_user_by_email(email.clone())
+            .await?
+        {
+            return Ok(user);
+        }
+        match self
+            .user_repository
+            .create_user(title, email.clone(), Vec::new())
+            .await
+        {
+            Ok(user) => Ok(user),
+            Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Ok(self
+                .user_repository
+                .get_user_by_email(email)
+                .await?
+                .ok_or(sqlx::Error::RowNotFound)?),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl OAuthServiceTrait for OAuthService {
+    async fn authorization_url(
+        &self,
+        provider: &str,
+        state: &str,
+        code_verifier: &str,
+    ) -> Result<String, OAuthError> {
+        let client = self
+            .registry
+            .get(provider)
+            .ok_or(OAuthError::UnknownProvider)?;
+        client.authorization_url(state, code_verifier).await
+    }
+    /// A known provider account signs in as the user it is linked to. A new
+    /// one is linked by verified email, to an existing account if there is one.
+    /// Accounts created by the former Google-only login have no identity row
+    /// yet and are linked this way on their next sign-in.
+    async fn sign_in(
+        &self,
+        provider: &str,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<UserDB, OAuthError> {
+        let client = self
+            .registry
+            .get(provider)
+            .ok_or(OAuthError::UnknownProvider)?;
+        let identity = client.exchange_code(code, code_verifier).await?;
+
+        if let Some(linked) = self
+            .identity_repository
+            .find_identity(provider, &identity.subject)
+            .await?
+        {
+            self.identity_repository.touch_identity(linked.id).await?;
+            return Ok(self
+                .user_repository
+                .get_user_by_id(linked.user_id)
+                .await?
+                .ok_or(sqlx::Error::RowNotFound)?);
+        }
+
+        let email = identity
+            .email
+            .filter(|_| identity.email_verified)
+            .ok_or(OAuthError::EmailNotVerified)?;
+        let title = identity.name.unwrap_or_else(|| email.clone());
+        let user = self.find_or_create_user(email.clone(), title).await?;
+        self.identity_repository
+            .link_identity(user.id, provider, &identity.subject, Some(email))
+            .await?;
+        Ok(user)
+    }
+}
@@ -0,0 +1,298 @@
+use crate::feature::url::policy::PolicyViolation;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::{Error as SqlxError, FromRow};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Ios,
+    Android,
+    Windows,
+    Macos,
+    Linux,
+    Other,
+}
+
+impl Platform {
+    pub fn detect(user_agent: &str) -> Self {
+        let user_agent = user_agent.to_ascii_lowercase();
+        if ["iphone", "ipad", "ipod"]
+            .iter()
+            .any(|device| user_agent.contains(device))
+        {
+            Platform::Ios
+        } else if user_agent.contains("android") {
+            Platform::Android
+        } else if user_agent.contains("windows") {
+            Platform::Windows
+        } else if user_agent.contains("macintosh") || user_agent.contains("mac os x") {
+            Platform::Macos
+        } else if user_agent.contains("linux") || user_agent.contains("x11") {
+            Platform::Linux
+        } else {
+            Platform::Other
+        }
+    }
+}
+
+/// Most preferred language of an `Accept-Language` header, lowercased.
+pub fn preferred_language(accept_language: &str) -> Option<String> {
+    let mut best: Option<(&str, f32)> = None;
+    for entry in accept_language.split(',') {
+        let mut parts = entry.split(';');
+        let tag = parts.next().unwrap_or_default().trim();
+        let quality = parts
+            .find_map(|param| param.trim().strip_prefix("q="))
+            .and_then(|q| q.parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if tag.is_empty() || tag == "*" || quality <= 0.0 {
+            continue;
+        }
+        if best.is_none_or(|(_, best_quality)| quality > best_quality) {
+            best = Some((tag, quality));
+        }
+    }
+    best.map(|(tag, _)| tag.to_ascii_lowercase())
+}
+
+/// What a rule is matched against, taken from the redirect request.
+#[derive(Debug, Clone)]
+pub struct VisitorContext {
+    pub platform: Platform,
+    pub country: Option<String>,
+    pub language: Option<String>,
+    pub user_agent: String,
+}
+
+/// Every non-empty condition has to match; a list matches when any of its
+/// values does.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, ToSchema)]
+pub struct RuleConditions {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<Platform>,
+    /// ISO 3166-1 alpha-2 codes as reported by the GeoIP provider.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(example = json!(["DE", "AT"]))]
+    pub countries: Vec<String>,
+    /// Matched against the visitor's preferred language; `pt` also matches
+    /// `pt-BR`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(example = json!(["de"]))]
+    pub languages: Vec<String>,
+    /// Case-insensitive substring of the `User-Agent` header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent_contains: Option<String>,
+}
+
+impl RuleConditions {
+    pub fn is_empty(&self) -> bool {
+        self.platforms.is_empty()
+            && self.countries.is_empty()
+            && self.languages.is_empty()
+            && self.user_agent_contains.is_none()
+    }
+
+    pub fn matches(&self, visitor: &VisitorContext) -> bool {
+        let platform = self.platforms.is_empty() || self.platforms.contains(&visitor.platform);
+        let country = self.countries.is_empty()
+            || visitor.country.as_ref().is_some_and(|country| {
+                self.countries
+                    .iter()
+                    .any(|rule| rule.eq_ignore_ascii_case(country))
+            });
+        let language = self.languages.is_empty()
+            || visitor.language.as_ref().is_some_and(|language| {
+                self.languages.iter().any(|rule| {
+                    let rule = rule.to_ascii_lowercase();
+                    *language == rule || language.starts_with(&format!("{}-", rule))
+                })
+            });
+        let user_agent = self.user_agent_contains.as_ref().is_none_or(|needle| {
+            visitor
+                .user_agent
+                .to_ascii_lowercase()
+                .contains(&needle.to_ascii_lowercase())
+        });
+        platform && country && language && user_agent
+    }
+}
+
+fn validate_conditions(conditions: &RuleConditions) -> Result<(), ValidationError> {
+    if conditions.is_empty() {
+        return Err(ValidationError::new("conditions_empty"));
+    }
+    let valid_country =
+        |country: &String| country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic());
+    if !conditions.countries.iter().all(valid_country) {
+        return Err(ValidationError::new("invalid_country"));
+    }
+    let valid_language = |language: &String| {
+        !language.is_empty()
+            && language.len() <= 35
+            && language
+                .split('-')
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()))
+    };
+    if !conditions.languages.iter().all(valid_language) {
+        return Err(ValidationError::new("invalid_language"));
+    }
+    if conditions
+        .user_agent_contains
+        .as_ref()
+        .is_some_and(|needle| needle.trim().is_empty() || needle.len() > 256)
+    {
+        return Err(ValidationError::new("invalid_user_agent"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct RedirectRule {
+    pub id: Uuid,
+    pub url_id: Uuid,
+    /// Rules are tried in ascending order; the first match wins.
+    pub position: i32,
+    #[schema(value_type = RuleConditions)]
+    pub conditions: Json<RuleConditions>,
+    pub destination: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RedirectRuleDTO {
+    #[validate(url)]
+    #[schema(example = "https://apps.apple.com/app/id0000000000")]
+    pub destination: String,
+    #[validate(custom(function = "validate_conditions"))]
+    pub conditions: RuleConditions,
+    /// Appended after the existing rules when omitted.
+    #[validate(range(min = 0))]
+    pub position: Option<i32>,
+}
+
+#[derive(Debug)]
+pub enum RuleError {
+    NotFound,
+    PolicyViolation(Vec<PolicyViolation>),
+    Db(SqlxError),
+}
+
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleError::NotFound => write!(f, "redirect rule not found"),
+            RuleError::PolicyViolation(violations) => {
+                let reasons: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+                write!(f, "destination rejected: {}", reasons.join("; "))
+            }
+            RuleError::Db(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl From<SqlxError> for RuleError {
+    fn from(err: SqlxError) -> Self {
+        RuleError::Db(err)
+    }
+}
+
+pub const REDIRECT_RULES_TABLE: &str = "redirect_rules";
+pub const REDIRECT_RULES_ID: &str = "id";
+pub const REDIRECT_RULES_URL_ID: &str = "url_id";
+pub const REDIRECT_RULES_POSITION: &str = "position";
+pub const REDIRECT_RULES_CONDITIONS: &str = "conditions";
+pub const REDIRECT_RULES_DESTINATION: &str = "destination";
+pub const REDIRECT_RULES_CREATED_AT: &str = "created_at";
+pub const REDIRECT_RULES_COLUMNS: [&str; 6] = [
+    REDIRECT_RULES_ID,
+    REDIRECT_RULES_URL_ID,
+    REDIRECT_RULES_POSITION,
+    REDIRECT_RULES_CONDITIONS,
+    REDIRECT_RULES_DESTINATION,
+    REDIRECT_RULES_CREATED_AT,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visitor() -> VisitorContext {
+        VisitorContext {
+            platform: Platform::Android,
+            country: Some("DE".to_string()),
+            language: Some("pt-br".to_string()),
+            user_agent: "Mozilla/5.0 (Linux; Android 14) Chrome/126".to_string(),
+        }
+    }
+
+    #[test]
+    fn preferred_language_picks_the_highest_quality() {
+        assert_eq!(
+            preferred_language("en;q=0.8, de-DE, fr;q=0.9").as_deref(),
+            Some("de-de")
+        );
+        assert_eq!(
+            preferred_language("fr;q=0.5,en;q=0.5").as_deref(),
+            Some("fr")
+        );
+        assert_eq!(preferred_language("*, es;q=0.1").as_deref(), Some("es"));
+        assert_eq!(preferred_language("de;q=0"), None);
+        assert_eq!(preferred_language(""), None);
+    }
+
+    #[test]
+    fn platform_is_detected_from_the_user_agent() {
+        assert_eq!(
+            Platform::detect("Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X)"),
+            Platform::Ios
+        );
+        assert_eq!(Platform::detect(&visitor().user_agent), Platform::Android);
+        assert_eq!(Platform::detect("curl/8.0"), Platform::Other);
+    }
+
+    #[test]
+    fn conditions_match_when_every_condition_does() {
+        let conditions = RuleConditions {
+            platforms: vec![Platform::Ios, Platform::Android],
+            countries: vec!["de".to_string(), "AT".to_string()],
+            languages: vec!["PT".to_string()],
+            user_agent_contains: Some("chrome".to_string()),
+        };
+        assert!(conditions.matches(&visitor()));
+
+        let other_country = VisitorContext {
+            country: Some("FR".to_string()),
+            ..visitor()
+        };
+        assert!(!conditions.matches(&other_country));
+        let unknown_country = VisitorContext {
+            country: None,
+            ..visitor()
+        };
+        assert!(!conditions.matches(&unknown_country));
+        let desktop = VisitorContext {
+            platform: Platform::Windows,
+            ..visitor()
+        };
+        assert!(!conditions.matches(&desktop));
+    }
+
+    #[test]
+    fn language_matches_the_primary_subtag_only() {
+        let conditions = RuleConditions {
+            languages: vec!["pt".to_string()],
+            ..Default::default()
+        };
+        assert!(conditions.matches(&visitor()));
+        let portuguese_like = VisitorContext {
+            language: Some("ptx".to_string()),
+            ..visitor()
+        };
+        assert!(!conditions.matches(&portuguese_like));
+    }
+}
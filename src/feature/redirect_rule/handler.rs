@@ -0,0 +1,192 @@
+use crate::feature::redirect_rule::entity::{RedirectRule, RedirectRuleDTO, RuleError};
+use crate::feature::redirect_rule::service::{RedirectRuleService, RedirectRuleServiceTrait};
+use crate::feature::url::entity::UrlRejection;
+use crate::feature::url::resource::LinkResourceHandler;
+use crate::feature::url::service::UrlService;
+use crate::metrics::PrometheusMetrics;
+use crate::servers::http::middleware::UserJWT;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct RedirectRuleHandler {
+    rule_service: Arc<RedirectRuleService>,
+    link: LinkResourceHandler,
+}
+
+impl RedirectRuleHandler {
+    pub fn new_handler(
+        rule_service: Arc<RedirectRuleService>,
+        url_service: Arc<UrlService>,
+        metrics: Arc<PrometheusMetrics>,
+    ) -> Self {
+        Self {
+            rule_service,
+            link: LinkResourceHandler::new(
+                url_service,
+                metrics,
+                "redirect_rule_handler",
+                "Redirect rule not found",
+            ),
+        }
+    }
+
+    fn error_response(&self, err: RuleError) -> Response {
+        match err {
+            RuleError::NotFound => self.link.not_found(),
+            RuleError::PolicyViolation(violations) => self.link.policy_violation(violations),
+            RuleError::Db(err) => self.link.internal_error(err),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/private/url/{id}/rules",
+    params(
+        ("id" = Uuid, Path, description = "URL ID")
+    ),
+    responses(
+        (status = 200, description = "Redirect rules in evaluation order", body = Vec<RedirectRule>),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Rules"
+)]
+pub async fn list_rules_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<RedirectRuleHandler>>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(response) = handlers.link.check_owner(id, &user_jwt).await {
+        return response;
+    }
+    match handlers.rule_service.list_rules(id).await {
+        Ok(rules) => Json(rules).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/private/url/{id}/rules",
+    params(
+        ("id" = Uuid, Path, description = "URL ID")
+    ),
+    request_body = RedirectRuleDTO,
+    responses(
+        (status = 201, description = "Redirect rule created", body = RedirectRule),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL not found"),
+        (status = 422, description = "Validation error or destination rejected by URL policy", body = UrlRejection),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Rules"
+)]
+pub async fn create_rule_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<RedirectRuleHandler>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RedirectRuleDTO>,
+) -> Response {
+    if let Some(response) = handlers.link.validation_error(&payload) {
+        return response;
+    }
+    if let Err(response) = handlers.link.check_owner(id, &user_jwt).await {
+        return response;
+    }
+    match handlers.rule_service.create_rule(id, payload).await {
+        Ok(rule) => (StatusCode::CREATED, Json(rule)).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/private/url/{id}/rules/{rule_id}",
+    params(
+        ("id" = Uuid, Path, description = "URL ID"),
+        ("rule_id" = Uuid, Path, description = "Redirect rule ID")
+    ),
+    request_body = RedirectRuleDTO,
+    responses(
+        (status = 200, description = "Redirect rule updated", body = RedirectRule),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL or redirect rule not found"),
+        (status = 422, description = "Validation error or destination rejected by URL policy", body = UrlRejection),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Rules"
+)]
+pub async fn update_rule_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<RedirectRuleHandler>>,
+    Path((id, rule_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<RedirectRuleDTO>,
+) -> Response {
+    if let Some(response) = handlers.link.validation_error(&payload) {
+        return response;
+    }
+    if let Err(response) = handlers.link.check_owner(id, &user_jwt).await {
+        return response;
+    }
+    match handlers
+        .rule_service
+        .update_rule(id, rule_id, payload)
+        .await
+    {
+        Ok(rule) => Json(rule).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/private/url/{id}/rules/{rule_id}",
+    params(
+        ("id" = Uuid, Path, description = "URL ID"),
+        ("rule_id" = Uuid, Path, description = "Redirect rule ID")
+    ),
+    responses(
+        (status = 204, description = "Redirect rule deleted"),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL or redirect rule not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Rules"
+)]
+pub async fn delete_rule_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<RedirectRuleHandler>>,
+    Path((id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(response) = handlers.link.check_owner(id, &user_jwt).await {
+        return response;
+    }
+    match handlers.rule_service.delete_rule(id, rule_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
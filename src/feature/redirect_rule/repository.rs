@@ -0,0 +1,150 @@
+use crate::feature::redirect_rule::entity::{
+    REDIRECT_RULES_COLUMNS, REDIRECT_RULES_CONDITIONS, REDIRECT_RULES_CREATED_AT,
+    REDIRECT_RULES_DESTINATION, REDIRECT_RULES_ID, REDIRECT_RULES_POSITION, REDIRECT_RULES_TABLE,
+    REDIRECT_RULES_URL_ID, RedirectRule,
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_query::{Alias, Expr, Order, PostgresQueryBuilder, Query, SimpleExpr};
+use sea_query_binder::SqlxBinder;
+use serde_json::Value;
+use sqlx::{Error, Pool, Postgres, query_as_with};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RedirectRuleRepositoryTrait: Send + Sync {
+    async fn list_rules(&self, url_id: Uuid) -> Result<Vec<RedirectRule>, Error>;
+    async fn add_rule(
+        &self,
+        url_id: Uuid,
+        conditions: Value,
+        destination: String,
+        position: Option<i32>,
+    ) -> Result<RedirectRule, Error>;
+    async fn update_rule(
+        &self,
+        url_id: Uuid,
+        rule_id: Uuid,
+        conditions: Value,
+        destination: String,
+        position: Option<i32>,
+    ) -> Result<Option<RedirectRule>, Error>;
+    async fn delete_rule(&self, url_id: Uuid, rule_id: Uuid)
+    -> Result<Option<RedirectRule>, Error>;
+}
+
+#[derive(Clone)]
+pub struct RedirectRuleRepository {
+    primary_db: Pool<Postgres>,
+}
+
+impl RedirectRuleRepository {
+    pub fn new_redirect_rule_repository(primary_db: Pool<Postgres>) -> Self {
+        Self { primary_db }
+    }
+}
+
+/// Position after the link's last rule.
+fn next_position(url_id: Uuid) -> SimpleExpr {
+    Expr::cust_with_values(
+        "COALESCE((SELECT MAX(position) + 1 FROM redirect_rules WHERE url_id = $1), 0)",
+        [url_id],
+    )
+}
+
+#[async_trait]
+impl RedirectRuleRepositoryTrait for RedirectRuleRepository {
+    async fn list_rules(&self, url_id: Uuid) -> Result<Vec<RedirectRule>, Error> {
+        let (sql, values) = Query::select()
+            .columns(REDIRECT_RULES_COLUMNS)
+            .from(REDIRECT_RULES_TABLE)
+            .and_where(Expr::col(REDIRECT_RULES_URL_ID).eq(url_id))
+            .order_by(REDIRECT_RULES_POSITION, Order::Asc)
+            .order_by(REDIRECT_RULES_CREATED_AT, Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+        let rules = query_as_with::<_, RedirectRule, _>(&sql, values)
+            .fetch_all(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching redirect rules: {:?}", err);
+                err
+            })?;
+        Ok(rules)
+    }
+    async fn add_rule(
+        &self,
+        url_id: Uuid,
+        conditions: Value,
+        destination: String,
+        position: Option<i32>,
+    ) -> Result<RedirectRule, Error> {
+        let position = match position {
+            Some(position) => position.into(),
+            None => next_position(url_id),
+        };
+        let (sql, values) = Query::insert()
+            .into_table(Alias::new(REDIRECT_RULES_TABLE))
+            .columns([
+                Alias::new(REDIRECT_RULES_URL_ID),
+                Alias::new(REDIRECT_RULES_POSITION),
+                Alias::new(REDIRECT_RULES_CONDITIONS),
+                Alias::new(REDIRECT_RULES_DESTINATION),
+            ])
+            .values_panic([
+                url_id.into(),
+                position,
+                conditions.into(),
+                destination.into(),
+            ])
+            .returning(Query::returning().columns(REDIRECT_RULES_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let rule = query_as_with::<_, RedirectRule, _>(&sql, values)
+            .fetch_one(&self.primary_db)
+            .await?;
+        Ok(rule)
+    }
+    async fn update_rule(
+        &self,
+        url_id: Uuid,
+        rule_id: Uuid,
+        conditions: Value,
+        destination: String,
+        position: Option<i32>,
+    ) -> Result<Option<RedirectRule>, Error> {
+        let mut update = Query::update();
+        update
+            .table(REDIRECT_RULES_TABLE)
+            .value(REDIRECT_RULES_CONDITIONS, conditions)
+            .value(REDIRECT_RULES_DESTINATION, destination)
+            .and_where(Expr::col(REDIRECT_RULES_ID).eq(rule_id))
+            .and_where(Expr::col(REDIRECT_RULES_URL_ID).eq(url_id));
+        if let Some(position) = position {
+            update.value(REDIRECT_RULES_POSITION, position);
+        }
+        let (sql, values) = update
+            .returning(Query::returning().columns(REDIRECT_RULES_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let rule = query_as_with::<_, RedirectRule, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await?;
+        Ok(rule)
+    }
+    async fn delete_rule(
+        &self,
+        url_id: Uuid,
+        rule_id: Uuid,
+    ) -> Result<Option<RedirectRule>, Error> {
+        let (sql, values) = Query::delete()
+            .from_table(REDIRECT_RULES_TABLE)
+            .and_where(Expr::col(REDIRECT_RULES_ID).eq(rule_id))
+            .and_where(Expr::col(REDIRECT_RULES_URL_ID).eq(url_id))
+            .returning(Query::returning().columns(REDIRECT_RULES_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let rule = query_as_with::<_, RedirectRule, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await?;
+        Ok(rule)
+    }
+}
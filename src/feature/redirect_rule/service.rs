@@ -0,0 +1,127 @@
+use crate::feature::redirect_rule::entity::{
+    RedirectRule, RedirectRuleDTO, RuleError, VisitorContext,
+};
+use crate::feature::redirect_rule::repository::{
+    RedirectRuleRepository, RedirectRuleRepositoryTrait,
+};
+use crate::feature::url::policy::UrlPolicyChain;
+use crate::utils::cache::{Cache, get_or_load};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait RedirectRuleServiceTrait: Send + Sync {
+    async fn list_rules(&self, url_id: Uuid) -> Result<Vec<RedirectRule>, RuleError>;
+    async fn create_rule(
+        &self,
+        url_id: Uuid,
+        dto: RedirectRuleDTO,
+    ) -> Result<RedirectRule, RuleError>;
+    async fn update_rule(
+        &self,
+        url_id: Uuid,
+        rule_id: Uuid,
+        dto: RedirectRuleDTO,
+    ) -> Result<RedirectRule, RuleError>;
+    async fn delete_rule(&self, url_id: Uuid, rule_id: Uuid) -> Result<(), RuleError>;
+    async fn match_destination(
+        &self,
+        url_id: Uuid,
+        visitor: &VisitorContext,
+    ) -> Result<Option<String>, RuleError>;
+}
+
+pub struct RedirectRuleService {
+    rule_repository: Arc<RedirectRuleRepository>,
+    url_policy: Arc<UrlPolicyChain>,
+    /// Rules are read on every redirect, keyed by link id.
+    rule_cache: Arc<dyn Cache<Vec<RedirectRule>>>,
+}
+
+impl RedirectRuleService {
+    pub fn new(
+        rule_repository: Arc<RedirectRuleRepository>,
+        url_policy: Arc<UrlPolicyChain>,
+        rule_cache: Arc<dyn Cache<Vec<RedirectRule>>>,
+    ) -> Self {
+        Self {
+            rule_repository,
+            url_policy,
+            rule_cache,
+        }
+    }
+
+    async fn check_policy(&self, destination: &str) -> Result<(), RuleError> {
+        self.url_policy
+            .check(destination)
+            .await
+            .map_err(RuleError::PolicyViolation)
+    }
+
+    async fn invalidate(&self, url_id: Uuid) {
+        self.rule_cache.invalidate(&url_id.to_string()).await;
+    }
+}
+
+#[async_trait]
+impl RedirectRuleServiceTrait for RedirectRuleService {
+    async fn list_rules(&self, url_id: Uuid) -> Result<Vec<RedirectRule>, RuleError> {
+        Ok(self.rule_repository.list_rules(url_id).await?)
+    }
+    async fn create_rule(
+        &self,
+        url_id: Uuid,
+        dto: RedirectRuleDTO,
+    ) -> Result<RedirectRule, RuleError> {
+        self.check_policy(&dto.destination).await?;
+        let conditions = serde_json::to_value(&dto.conditions).unwrap_or_default();
+        let rule = self
+            .rule_repository
+            .add_rule(url_id, conditions, dto.destination, dto.position)
+            .await?;
+        self.invalidate(url_id).await;
+        Ok(rule)
+    }
+    async fn update_rule(
+        &self,
+        url_id: Uuid,
+        rule_id: Uuid,
+        dto: RedirectRuleDTO,
+    ) -> Result<RedirectRule, RuleError> {
+        self.check_policy(&dto.destination).await?;
+        let conditions = serde_json::to_value(&dto.conditions).unwrap_or_default();
+        let rule = self
+            .rule_repository
+            .update_rule(url_id, rule_id, conditions, dto.destination, dto.position)
+            .await?
+            .ok_or(RuleError::NotFound)?;
+        self.invalidate(url_id).await;
+        Ok(rule)
+    }
+    async fn delete_rule(&self, url_id: Uuid, rule_id: Uuid) -> Result<(), RuleError> {
+        self.rule_repository
+            .delete_rule(url_id, rule_id)
+            .await?
+            .ok_or(RuleError::NotFound)?;
+        self.invalidate(url_id).await;
+        Ok(())
+    }
+    async fn match_destination(
+        &self,
+        url_id: Uuid,
+        visitor: &VisitorContext,
+    ) -> Result<Option<String>, RuleError> {
+        let rules = get_or_load(self.rule_cache.as_ref(), &url_id.to_string(), || async {
+            Ok::<_, RuleError>(self.rule_repository.list_rules(url_id).await?)
+        })
+        .await?;
+        Ok(rules
+            .iter()
+            .find(|rule| rule.conditions.matches(visitor))
+            .map(|rule| rule.destination.clone()))
+    }
+}
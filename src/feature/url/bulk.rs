@@ -0,0 +1,118 @@
+use crate::feature::url::entity::{BulkRow, CreateUrlDTO, UrlError};
+use csv::{ReaderBuilder, Trim};
+
+/// Rejects the whole batch before any row is checked or inserted.
+pub fn check_row_limit(rows: &[BulkRow], max_rows: usize) -> Result<(), UrlError> {
+    if rows.len() > max_rows {
+        return Err(UrlError::TooManyRows(max_rows));
+    }
+    Ok(())
+}
+
+pub fn parse_json_rows(body: &[u8]) -> Result<Vec<BulkRow>, String> {
+    let values: Vec<serde_json::Value> =
+        serde_json::from_slice(body).map_err(|err| format!("Expected a JSON array: {}", err))?;
+    Ok(values
+        .into_iter()
+        .map(|value| {
+            serde_json::from_value::<CreateUrlDTO>(value)
+                .map_err(|err| format!("Invalid row: {}", err))
+                .and_then(CreateUrlDTO::into_bulk_row)
+        })
+        .collect())
+}
+
+/// Expects a header row; `url` is required, the other `CreateUrlDTO` columns
+/// (`alias`, `expires_at`, `ttl_seconds`, `max_clicks`, `domain`, `utm_*`,
+/// `forward_query`) are optional.
+pub fn parse_csv_rows(body: &[u8]) -> Result<Vec<BulkRow>, String> {
+    let mut reader = ReaderBuilder::new()
+        .trim(Trim::All)
+        .flexible(true)
+        .from_reader(body);
+    let headers = reader
+        .headers()
+        .map_err(|err| format!("Invalid CSV header: {}", err))?;
+    if !headers.iter().any(|header| header == "url") {
+        return Err("CSV header must contain a `url` column".to_string());
+    }
+    Ok(reader
+        .deserialize::<CreateUrlDTO>()
+        .map(|row| {
+            row.map_err(|err| format!("Invalid row: {}", err))
+                .and_then(CreateUrlDTO::into_bulk_row)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_header_may_carry_optional_columns_in_any_order() {
+        let body =
+            b"ttl_seconds,url,alias\n,https://a.example.com,\n3600,https://b.example.com,promo\n";
+        let rows = parse_csv_rows(body).unwrap();
+        assert_eq!(rows.len(), 2);
+        let first = rows[0].as_ref().unwrap();
+        assert_eq!(first.url, "https://a.example.com");
+        assert_eq!(first.alias, None);
+        assert_eq!(first.expires_at, None);
+        let second = rows[1].as_ref().unwrap();
+        assert_eq!(second.alias.as_deref(), Some("promo"));
+        assert!(second.expires_at.is_some());
+    }
+
+    #[test]
+    fn csv_without_a_url_column_is_rejected() {
+        assert!(parse_csv_rows(b"alias,ttl_seconds\npromo,60\n").is_err());
+    }
+
+    #[test]
+    fn empty_bodies() {
+        assert!(parse_csv_rows(b"").is_err());
+        assert!(parse_csv_rows(b"url\n").unwrap().is_empty());
+        assert!(parse_json_rows(b"").is_err());
+        assert!(parse_json_rows(b"[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn malformed_rows_keep_their_position() {
+        let csv = b"url,max_clicks\nhttps://a.example.com,1\nhttps://b.example.com,many\nnot a url,\nhttps://c.example.com,\n";
+        let rows = parse_csv_rows(csv).unwrap();
+        let failed: Vec<usize> = rows
+            .iter()
+            .enumerate()
+            .filter_map(|(index, row)| row.is_err().then_some(index))
+            .collect();
+        assert_eq!(failed, vec![1, 2]);
+
+        let json = br#"[{"url": "https://a.example.com"}, {"alias": "no-url"}, {"url": "https://b.example.com", "alias": "x"}, {"url": "https://c.example.com"}]"#;
+        let rows = parse_json_rows(json).unwrap();
+        let failed: Vec<usize> = rows
+            .iter()
+            .enumerate()
+            .filter_map(|(index, row)| row.is_err().then_some(index))
+            .collect();
+        assert_eq!(failed, vec![1, 2]);
+    }
+
+    #[test]
+    fn json_must_be_an_array_not_an_object_wrapper() {
+        let rows = parse_json_rows(br#"[{"url": "https://a.example.com"}]"#).unwrap();
+        assert_eq!(rows.len(), 1);
+        let err = parse_json_rows(br#"{"urls": [{"url": "https://a.example.com"}]}"#).unwrap_err();
+        assert!(err.starts_with("Expected a JSON array"));
+    }
+
+    #[test]
+    fn row_limit_is_inclusive() {
+        let rows: Vec<BulkRow> = vec![Err("bad".to_string()); 3];
+        assert!(check_row_limit(&rows, 3).is_ok());
+        assert!(matches!(
+            check_row_limit(&rows, 2),
+            Err(UrlError::TooManyRows(2))
+        ));
+    }
+}
@@ -1,5 +1,17 @@
+use crate::domain::url::Url;
+use crate::feature::blocklist::entity::validate_domain;
+use crate::feature::url::policy::PolicyViolation;
+use crate::feature::url::qr::parse_hex_color;
+use crate::utils::constants::{
+    ALIAS_MAX_LENGTH, ALIAS_MIN_LENGTH, DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT, QR_MAX_MARGIN,
+    QR_MAX_SIZE, QR_MIN_SIZE, RESERVED_ALIASES, URL_MAX_TTL_SECONDS,
+};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use sha2::{Digest, Sha256};
+use sqlx::Error as SqlxError;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
@@ -11,14 +23,824 @@ fn validate_not_nil(id: &Uuid) -> Result<(), ValidationError> {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate, ToSchema)]
-pub struct RedirectDto {
-    #[validate(length(min = 1))]
-    pub id: String,
+pub fn validate_alias(alias: &str) -> Result<(), ValidationError> {
+    if alias.len() < ALIAS_MIN_LENGTH || alias.len() > ALIAS_MAX_LENGTH {
+        return Err(ValidationError::new("alias_length"));
+    }
+    if !alias
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ValidationError::new("alias_charset"));
+    }
+    if RESERVED_ALIASES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(alias))
+    {
+        return Err(ValidationError::new("alias_reserved"));
+    }
+    Ok(())
+}
+
+/// `None` when the expiry cannot be represented.
+fn ttl_expiry(ttl_seconds: i64) -> Option<DateTime<Utc>> {
+    TimeDelta::try_seconds(ttl_seconds).and_then(|ttl| Utc::now().checked_add_signed(ttl))
+}
+
+fn validate_future_date(date: &DateTime<Utc>) -> Result<(), ValidationError> {
+    if *date <= Utc::now() {
+        Err(ValidationError::new("date_must_be_in_future"))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum UrlError {
+    NotFound,
+    Forbidden,
+    InvalidCursor,
+    Expired,
+    AliasAlreadyExists,
+    UrlAlreadyExists,
+    AliasGenerationFailed,
+    PasswordRequired,
+    InvalidPassword,
+    PasswordHashFailed,
+    PolicyViolation(Vec<PolicyViolation>),
+    DomainNotAvailable(String),
+    TooManyRows(usize),
+    Db(SqlxError),
+}
+
+impl std::fmt::Display for UrlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UrlError::NotFound => write!(f, "url not found"),
+            UrlError::Forbidden => write!(f, "url belongs to another user"),
+            UrlError::InvalidCursor => write!(f, "invalid pagination cursor"),
+            UrlError::Expired => write!(f, "url has expired"),
+            UrlError::AliasAlreadyExists => write!(f, "alias is already taken"),
+            UrlError::UrlAlreadyExists => write!(f, "url is already shortened"),
+            UrlError::AliasGenerationFailed => write!(f, "could not generate a free alias"),
+            UrlError::PasswordRequired => write!(f, "url is password protected"),
+            UrlError::InvalidPassword => write!(f, "invalid url password"),
+            UrlError::PasswordHashFailed => write!(f, "could not hash url password"),
+            UrlError::PolicyViolation(violations) => {
+                let reasons: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+                write!(f, "url rejected: {}", reasons.join("; "))
+            }
+            UrlError::DomainNotAvailable(host) => {
+                write!(
+                    f,
+                    "domain `{}` is not a verified domain of this account",
+                    host
+                )
+            }
+            UrlError::TooManyRows(max) => write!(f, "at most {} rows are allowed", max),
+            UrlError::Db(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl From<SqlxError> for UrlError {
+    fn from(err: SqlxError) -> Self {
+        UrlError::Db(err)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Validate, ToSchema)]
 pub struct CreateUrlDTO {
     #[validate(url)]
     pub url: String,
+    #[validate(custom(function = "validate_alias"))]
+    #[schema(example = "spring-sale")]
+    pub alias: Option<String>,
+    #[validate(custom(function = "validate_future_date"))]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[validate(range(min = 1, max = URL_MAX_TTL_SECONDS))]
+    #[schema(example = 86400, maximum = 315_360_000)]
+    pub ttl_seconds: Option<i64>,
+    #[validate(range(min = 1))]
+    pub max_clicks: Option<i64>,
+    #[validate(length(min = 4, max = 128))]
+    pub password: Option<String>,
+    /// Show a "you are leaving" page instead of redirecting right away.
+    #[serde(default)]
+    pub interstitial: bool,
+    /// Seconds before the interstitial page continues on its own.
+    #[validate(range(min = 1, max = 60))]
+    pub countdown_seconds: Option<i32>,
+    /// Verified custom domain to serve the link from; the alias only has to
+    /// be unique on that domain.
+    #[validate(custom(function = "validate_domain"))]
+    #[schema(example = "go.example.com")]
+    pub domain: Option<String>,
+    #[validate(length(min = 1, max = 256))]
+    #[schema(example = "newsletter")]
+    pub utm_source: Option<String>,
+    #[validate(length(min = 1, max = 256))]
+    #[schema(example = "email")]
+    pub utm_medium: Option<String>,
+    #[validate(length(min = 1, max = 256))]
+    #[schema(example = "spring-sale")]
+    pub utm_campaign: Option<String>,
+    #[validate(length(min = 1, max = 256))]
+    pub utm_term: Option<String>,
+    #[validate(length(min = 1, max = 256))]
+    pub utm_content: Option<String>,
+    /// Pass the query string of the short-link request on to the destination.
+    #[serde(default)]
+    pub forward_query: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UrlSortField {
+    #[default]
+    CreatedAt,
+    Alias,
+    /// Click counts change while paging, so a link may move between pages;
+    /// ties are broken by id.
+    Clicks,
+}
+
+impl UrlSortField {
+    pub fn column(&self) -> &'static str {
+        match self {
+            UrlSortField::CreatedAt => URL_CREATED_AT,
+            UrlSortField::Alias => URL_ALIAS,
+            UrlSortField::Clicks => URL_CLICKS,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListUrlsQuery {
+    pub limit: Option<u64>,
+    /// Opaque `next_cursor` value from the previous page.
+    pub cursor: Option<String>,
+    /// Owner filter, only honoured for admins.
+    pub owner: Option<Uuid>,
+    /// Destination host, subdomains included.
+    pub domain: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    /// Substring search over alias and destination URL.
+    pub search: Option<String>,
+    #[serde(default)]
+    #[param(inline)]
+    pub sort: UrlSortField,
+    #[serde(default)]
+    #[param(inline)]
+    pub order: SortOrder,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UrlCursor {
+    pub sort: UrlSortField,
+    pub order: SortOrder,
+    /// `UrlFilter::fingerprint` of the listing the cursor was issued for.
+    pub filter: String,
+    pub value: serde_json::Value,
+    pub id: Uuid,
+}
+
+impl UrlCursor {
+    pub fn from_url(url: &Url, filter: &UrlFilter) -> Self {
+        let value = match filter.sort {
+            UrlSortField::CreatedAt => serde_json::json!(url.created_at),
+            UrlSortField::Alias => serde_json::json!(url.alias),
+            UrlSortField::Clicks => serde_json::json!(url.clicks),
+        };
+        Self {
+            sort: filter.sort,
+            order: filter.order,
+            filter: filter.fingerprint(),
+            value,
+            id: url.id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        serde_json::from_slice(&bytes)
+            .ok()
+            .filter(Self::has_sort_typed_value)
+    }
+
+    /// A tampered value of the wrong type would otherwise only fail once it
+    /// is bound into the keyset query.
+    fn has_sort_typed_value(&self) -> bool {
+        match self.sort {
+            UrlSortField::CreatedAt => {
+                serde_json::from_value::<DateTime<Utc>>(self.value.clone()).is_ok()
+            }
+            UrlSortField::Alias => self.value.is_string(),
+            UrlSortField::Clicks => self.value.is_i64(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UrlFilter {
+    pub owner: Option<Uuid>,
+    pub domain: Option<String>,
+    pub created_from: Option<DateTime<Utc>>,
+    pub created_to: Option<DateTime<Utc>>,
+    pub search: Option<String>,
+    pub sort: UrlSortField,
+    pub order: SortOrder,
+    pub limit: u64,
+    pub after: Option<UrlCursor>,
+}
+
+impl UrlFilter {
+    /// A cursor is only valid for the filters it was issued with.
+    pub fn from_query(query: ListUrlsQuery, owner: Option<Uuid>) -> Result<Self, UrlError> {
+        let mut filter = Self {
+            owner: owner.or(query.owner),
+            domain: query.domain.map(|domain| domain.trim().to_lowercase()),
+            created_from: query.created_from,
+            created_to: query.created_to,
+            search: query.search.filter(|search| !search.trim().is_empty()),
+            sort: query.sort,
+            order: query.order,
+            limit: query
+                .limit
+                .unwrap_or(DEFAULT_PAGE_LIMIT)
+                .clamp(1, MAX_PAGE_LIMIT),
+            after: None,
+        };
+        if let Some(cursor) = query.cursor.as_deref() {
+            let cursor = UrlCursor::decode(cursor).ok_or(UrlError::InvalidCursor)?;
+            if cursor.sort != filter.sort
+                || cursor.order != filter.order
+                || cursor.filter != filter.fingerprint()
+            {
+                return Err(UrlError::InvalidCursor);
+            }
+            filter.after = Some(cursor);
+        }
+        Ok(filter)
+    }
+
+    /// Hash of the filters that select the listed links.
+    pub fn fingerprint(&self) -> String {
+        let filters = serde_json::json!([
+            self.owner,
+            self.domain,
+            self.created_from,
+            self.created_to,
+            self.search,
+        ]);
+        hex::encode(Sha256::digest(filters.to_string().as_bytes()))[..16].to_string()
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UrlPage {
+    pub items: Vec<Url>,
+    pub next_cursor: Option<String>,
+}
+
+/// A bulk row that passed parsing and validation, or the reason it did not.
+pub type BulkRow = Result<NewUrl, String>;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkUrlResult {
+    /// Zero-based position of the row in the request.
+    pub index: usize,
+    pub alias: Option<String>,
+    pub short_url: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkCreateResponse {
+    pub created: usize,
+    pub failed: usize,
+    pub results: Vec<BulkUrlResult>,
+}
+
+impl BulkUrlResult {
+    pub fn failed(index: usize, error: String) -> Self {
+        Self {
+            index,
+            alias: None,
+            short_url: None,
+            error: Some(error),
+        }
+    }
+}
+
+impl From<Vec<BulkUrlResult>> for BulkCreateResponse {
+    fn from(results: Vec<BulkUrlResult>) -> Self {
+        let created = results.iter().filter(|row| row.error.is_none()).count();
+        Self {
+            created,
+            failed: results.len() - created,
+            results,
+        }
+    }
+}
+
+fn double_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct UpdateUrlDTO {
+    #[validate(url)]
+    pub url: Option<String>,
+    #[validate(custom(function = "validate_alias"))]
+    pub alias: Option<String>,
+    /// `null` removes the expiry, a missing field keeps it unchanged.
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(custom(function = "validate_future_date"))]
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub expires_at: Option<Option<DateTime<Utc>>>,
+    pub enabled: Option<bool>,
+    /// `null` removes the password, a missing field keeps it unchanged.
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(min = 4, max = 128))]
+    #[schema(value_type = Option<String>)]
+    pub password: Option<Option<String>>,
+    pub interstitial: Option<bool>,
+    /// `null` removes the countdown, a missing field keeps it unchanged.
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(range(min = 1, max = 60))]
+    #[schema(value_type = Option<i32>)]
+    pub countdown_seconds: Option<Option<i32>>,
+    /// For the `utm_*` fields `null` removes the parameter, a missing field
+    /// keeps it unchanged.
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(min = 1, max = 256))]
+    #[schema(value_type = Option<String>)]
+    pub utm_source: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(min = 1, max = 256))]
+    #[schema(value_type = Option<String>)]
+    pub utm_medium: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(min = 1, max = 256))]
+    #[schema(value_type = Option<String>)]
+    pub utm_campaign: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(min = 1, max = 256))]
+    #[schema(value_type = Option<String>)]
+    pub utm_term: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    #[validate(length(min = 1, max = 256))]
+    #[schema(value_type = Option<String>)]
+    pub utm_content: Option<Option<String>>,
+    pub forward_query: Option<bool>,
+}
+
+impl UpdateUrlDTO {
+    pub fn is_empty(&self) -> bool {
+        self.url.is_none()
+            && self.alias.is_none()
+            && self.expires_at.is_none()
+            && self.enabled.is_none()
+            && self.password.is_none()
+            && self.interstitial.is_none()
+            && self.countdown_seconds.is_none()
+            && self.utm_source.is_none()
+            && self.utm_medium.is_none()
+            && self.utm_campaign.is_none()
+            && self.utm_term.is_none()
+            && self.utm_content.is_none()
+            && self.forward_query.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UrlChanges {
+    pub url: Option<String>,
+    pub alias: Option<String>,
+    pub expires_at: Option<Option<DateTime<Utc>>>,
+    pub enabled: Option<bool>,
+    /// Plain-text password from the request until `UrlService` replaces it
+    /// with its argon2 hash.
+    pub password: Option<Option<String>>,
+    pub interstitial: Option<bool>,
+    pub countdown_seconds: Option<Option<i32>>,
+    pub utm_source: Option<Option<String>>,
+    pub utm_medium: Option<Option<String>>,
+    pub utm_campaign: Option<Option<String>>,
+    pub utm_term: Option<Option<String>>,
+    pub utm_content: Option<Option<String>>,
+    pub forward_query: Option<bool>,
+}
+
+impl From<UpdateUrlDTO> for UrlChanges {
+    fn from(dto: UpdateUrlDTO) -> Self {
+        Self {
+            url: dto.url,
+            alias: dto.alias,
+            expires_at: dto.expires_at,
+            enabled: dto.enabled,
+            password: dto.password,
+            interstitial: dto.interstitial,
+            countdown_seconds: dto.countdown_seconds,
+            utm_source: dto.utm_source,
+            utm_medium: dto.utm_medium,
+            utm_campaign: dto.utm_campaign,
+            utm_term: dto.utm_term,
+            utm_content: dto.utm_content,
+            forward_query: dto.forward_query,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema, sqlx::FromRow)]
+pub struct UrlHistoryEntry {
+    pub id: i64,
+    pub url_id: Uuid,
+    pub changed_by: Uuid,
+    pub changed_at: DateTime<Utc>,
+    #[schema(value_type = Object)]
+    pub changes: serde_json::Value,
+}
+
+/// Body of a 422 response for a destination rejected by the URL policy.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UrlRejection {
+    pub error: String,
+    pub violations: Vec<PolicyViolation>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ShortenedUrl {
+    #[serde(flatten)]
+    pub url: Url,
+    #[schema(example = "https://sho.rt/spring-sale")]
+    pub short_url: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RedirectQuery {
+    /// Password for protected links; the `X-Link-Password` header works too.
+    pub password: Option<String>,
+}
+
+fn validate_hex_color(color: &str) -> Result<(), ValidationError> {
+    match parse_hex_color(color) {
+        Some(_) => Ok(()),
+        None => Err(ValidationError::new("invalid_color")),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, ToSchema)]
+pub enum QrErrorCorrection {
+    L,
+    #[default]
+    M,
+    Q,
+    H,
+}
+
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+pub struct QrQuery {
+    #[serde(default)]
+    pub format: QrFormat,
+    /// Width and height in pixels.
+    #[validate(range(min = QR_MIN_SIZE, max = QR_MAX_SIZE))]
+    pub size: Option<u32>,
+    /// Quiet zone in modules, 4 by default.
+    #[validate(range(max = QR_MAX_MARGIN))]
+    pub margin: Option<u32>,
+    #[serde(default)]
+    pub ecc: QrErrorCorrection,
+    /// Foreground colour as `#rrggbb` or `#rgb`.
+    #[validate(custom(function = "validate_hex_color"))]
+    pub fg: Option<String>,
+    /// Background colour as `#rrggbb` or `#rgb`.
+    #[validate(custom(function = "validate_hex_color"))]
+    pub bg: Option<String>,
+    /// Custom domain the alias belongs to.
+    pub domain: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LinkPasswordForm {
+    pub password: String,
+}
+
+pub struct ResolvedUrl {
+    pub url: Url,
+    pub click_counted: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewUrl {
+    pub url: String,
+    pub alias: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub max_clicks: Option<i64>,
+    /// Plain-text password from the request until `UrlService` replaces it
+    /// with its argon2 hash.
+    pub password: Option<String>,
+    pub interstitial: bool,
+    pub countdown_seconds: Option<i32>,
+    /// Requested custom host; `UrlService` resolves it into `domain_id`.
+    pub domain: Option<String>,
+    pub domain_id: Option<Uuid>,
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub utm_term: Option<String>,
+    pub utm_content: Option<String>,
+    pub forward_query: bool,
+}
+
+impl TryFrom<CreateUrlDTO> for NewUrl {
+    type Error = ValidationError;
+
+    fn try_from(dto: CreateUrlDTO) -> Result<Self, Self::Error> {
+        let ttl_expires_at = dto
+            .ttl_seconds
+            .map(|ttl| ttl_expiry(ttl).ok_or_else(|| ValidationError::new("ttl_out_of_range")))
+            .transpose()?;
+        let expires_at = match (dto.expires_at, ttl_expires_at) {
+            (Some(at), Some(ttl)) => Some(at.min(ttl)),
+            (at, ttl) => at.or(ttl),
+        };
+        Ok(Self {
+            url: dto.url,
+            alias: dto.alias,
+            expires_at,
+            max_clicks: dto.max_clicks,
+            password: dto.password,
+            interstitial: dto.interstitial,
+            countdown_seconds: dto.countdown_seconds,
+            domain: dto.domain,
+            domain_id: None,
+            utm_source: dto.utm_source,
+            utm_medium: dto.utm_medium,
+            utm_campaign: dto.utm_campaign,
+            utm_term: dto.utm_term,
+            utm_content: dto.utm_content,
+            forward_query: dto.forward_query,
+        })
+    }
+}
+
+impl CreateUrlDTO {
+    pub fn into_bulk_row(self) -> BulkRow {
+        self.validate().map_err(|err| err.to_string())?;
+        NewUrl::try_from(self).map_err(|err| err.to_string())
+    }
+}
+
+impl NewUrl {
+    pub fn from_url(url: String) -> Self {
+        Self {
+            url,
+            alias: None,
+            expires_at: None,
+            max_clicks: None,
+            password: None,
+            interstitial: false,
+            countdown_seconds: None,
+            domain: None,
+            domain_id: None,
+            utm_source: None,
+            utm_medium: None,
+            utm_campaign: None,
+            utm_term: None,
+            utm_content: None,
+            forward_query: false,
+        }
+    }
+}
+
+pub const URL_TABLE: &str = "url";
+pub const URL_ID: &str = "id";
+pub const URL_ALIAS: &str = "alias";
+pub const URL_URL: &str = "url";
+pub const URL_USER_ID: &str = "user_id";
+pub const URL_CREATED_AT: &str = "created_at";
+pub const URL_UPDATED_AT: &str = "updated_at";
+pub const URL_EXPIRES_AT: &str = "expires_at";
+pub const URL_MAX_CLICKS: &str = "max_clicks";
+pub const URL_CLICKS: &str = "clicks";
+pub const URL_ENABLED: &str = "enabled";
+
+pub const URL_PASSWORD_HASH: &str = "password_hash";
+pub const URL_INTERSTITIAL: &str = "interstitial";
+pub const URL_COUNTDOWN_SECONDS: &str = "countdown_seconds";
+pub const URL_DOMAIN_ID: &str = "domain_id";
+pub const URL_UTM_SOURCE: &str = "utm_source";
+pub const URL_UTM_MEDIUM: &str = "utm_medium";
+pub const URL_UTM_CAMPAIGN: &str = "utm_campaign";
+pub const URL_UTM_TERM: &str = "utm_term";
+pub const URL_UTM_CONTENT: &str = "utm_content";
+pub const URL_FORWARD_QUERY: &str = "forward_query";
+pub const URL_COLUMNS: [&str; 20] = [
+    URL_ID,
+    URL_ALIAS,
+    URL_URL,
+    URL_USER_ID,
+    URL_CREATED_AT,
+    URL_UPDATED_AT,
+    URL_EXPIRES_AT,
+    URL_MAX_CLICKS,
+    URL_CLICKS,
+    URL_ENABLED,
+    URL_PASSWORD_HASH,
+    URL_INTERSTITIAL,
+    URL_COUNTDOWN_SECONDS,
+    URL_DOMAIN_ID,
+    URL_UTM_SOURCE,
+    URL_UTM_MEDIUM,
+    URL_UTM_CAMPAIGN,
+    URL_UTM_TERM,
+    URL_UTM_CONTENT,
+    URL_FORWARD_QUERY,
+];
+
+pub const URL_HISTORY_TABLE: &str = "url_history";
+pub const URL_HISTORY_ID: &str = "id";
+pub const URL_HISTORY_URL_ID: &str = "url_id";
+pub const URL_HISTORY_CHANGED_BY: &str = "changed_by";
+pub const URL_HISTORY_CHANGED_AT: &str = "changed_at";
+pub const URL_HISTORY_CHANGES: &str = "changes";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_dto(url: &str) -> CreateUrlDTO {
+        serde_json::from_value(serde_json::json!({ "url": url })).unwrap()
+    }
+
+    fn alias_error(alias: &str) -> Option<String> {
+        validate_alias(alias).err().map(|err| err.code.to_string())
+    }
+
+    #[test]
+    fn alias_charset() {
+        for alias in ["abc", "Promo_2026", "summer-sale", "a-b_c-9"] {
+            assert_eq!(alias_error(alias), None, "{}", alias);
+        }
+        for alias in [
+            "with space",
+            "slash/ed",
+            "dot.ted",
+            "ümlaut",
+            "q?x=1",
+            "#tag",
+        ] {
+            assert_eq!(
+                alias_error(alias).as_deref(),
+                Some("alias_charset"),
+                "{}",
+                alias
+            );
+        }
+    }
+
+    #[test]
+    fn alias_length_bounds() {
+        let shortest = "a".repeat(ALIAS_MIN_LENGTH);
+        let longest = "a".repeat(ALIAS_MAX_LENGTH);
+        assert_eq!(alias_error(&shortest), None);
+        assert_eq!(alias_error(&longest), None);
+        for alias in [
+            String::new(),
+            "a".repeat(ALIAS_MIN_LENGTH - 1),
+            "a".repeat(ALIAS_MAX_LENGTH + 1),
+        ] {
+            assert_eq!(alias_error(&alias).as_deref(), Some("alias_length"));
+        }
+    }
+
+    #[test]
+    fn reserved_aliases_are_rejected_in_any_case() {
+        for alias in ["api", "API", "swagger-ui", "Metrics"] {
+            assert_eq!(
+                alias_error(alias).as_deref(),
+                Some("alias_reserved"),
+                "{}",
+                alias
+            );
+        }
+        assert_eq!(alias_error(".well-known").as_deref(), Some("alias_charset"));
+    }
+
+    fn list_query(params: serde_json::Value) -> ListUrlsQuery {
+        serde_json::from_value(params).unwrap()
+    }
+
+    fn cursor_for(params: serde_json::Value, value: serde_json::Value) -> String {
+        let filter = UrlFilter::from_query(list_query(params), None).unwrap();
+        UrlCursor {
+            sort: filter.sort,
+            order: filter.order,
+            filter: filter.fingerprint(),
+            value,
+            id: Uuid::nil(),
+        }
+        .encode()
+    }
+
+    fn with_cursor(mut params: serde_json::Value, cursor: &str) -> ListUrlsQuery {
+        params["cursor"] = serde_json::json!(cursor);
+        list_query(params)
+    }
+
+    #[test]
+    fn cursor_round_trips_through_the_same_listing() {
+        let params = serde_json::json!({ "sort": "clicks", "search": "promo" });
+        let cursor = cursor_for(params.clone(), serde_json::json!(42));
+        let filter = UrlFilter::from_query(with_cursor(params, &cursor), None).unwrap();
+        let after = filter.after.unwrap();
+        assert_eq!(after.sort, UrlSortField::Clicks);
+        assert_eq!(after.value, serde_json::json!(42));
+        assert_eq!(after.id, Uuid::nil());
+    }
+
+    #[test]
+    fn cursor_is_rejected_for_other_filters() {
+        let params = serde_json::json!({ "sort": "clicks", "search": "promo" });
+        let cursor = cursor_for(params, serde_json::json!(42));
+        for other in [
+            serde_json::json!({ "sort": "clicks", "search": "sale" }),
+            serde_json::json!({ "sort": "clicks" }),
+            serde_json::json!({ "sort": "clicks", "search": "promo", "order": "asc" }),
+            serde_json::json!({ "sort": "alias", "search": "promo" }),
+        ] {
+            assert!(matches!(
+                UrlFilter::from_query(with_cursor(other, &cursor), None),
+                Err(UrlError::InvalidCursor)
+            ));
+        }
+    }
+
+    #[test]
+    fn garbage_or_tampered_cursor_is_invalid() {
+        let params = serde_json::json!({ "sort": "clicks" });
+        let tampered = cursor_for(params.clone(), serde_json::json!("42; DROP TABLE url"));
+        let not_json = URL_SAFE_NO_PAD.encode(b"not json");
+        for cursor in ["%%%", "", not_json.as_str(), tampered.as_str()] {
+            assert!(matches!(
+                UrlFilter::from_query(with_cursor(params.clone(), cursor), None),
+                Err(UrlError::InvalidCursor)
+            ));
+        }
+    }
+
+    #[test]
+    fn ttl_sets_the_expiry() {
+        let dto = CreateUrlDTO {
+            ttl_seconds: Some(60),
+            ..create_dto("https://example.com")
+        };
+        assert!(dto.validate().is_ok());
+        let expires_at = NewUrl::try_from(dto).unwrap().expires_at.unwrap();
+        assert!(expires_at > Utc::now() && expires_at <= Utc::now() + TimeDelta::seconds(60));
+    }
+
+    #[test]
+    fn oversized_ttl_is_rejected_without_panicking() {
+        for ttl in [URL_MAX_TTL_SECONDS + 1, i64::MAX] {
+            let dto = CreateUrlDTO {
+                ttl_seconds: Some(ttl),
+                ..create_dto("https://example.com")
+            };
+            assert!(dto.validate().is_err());
+            assert!(dto.into_bulk_row().is_err());
+        }
+        let unvalidated = CreateUrlDTO {
+            ttl_seconds: Some(i64::MAX),
+            ..create_dto("https://example.com")
+        };
+        assert!(NewUrl::try_from(unvalidated).is_err());
+    }
 }
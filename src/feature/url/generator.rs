@@ -0,0 +1,163 @@
+use crate::app::config::{AliasConfig, AliasStrategy};
+use crate::feature::url::repository::{UrlRepository, UrlRepositoryTrait};
+use crate::utils::random::new_random_string;
+use std::sync::Arc;
+
+pub struct AliasGenerator {
+    config: AliasConfig,
+    alphabet: Vec<char>,
+    shuffled: Vec<char>,
+    url_repository: Arc<UrlRepository>,
+}
+
+impl AliasGenerator {
+    pub fn new(config: AliasConfig, url_repository: Arc<UrlRepository>) -> Self {
+        let mut alphabet: Vec<char> = Vec::new();
+        for c in config.alphabet.chars() {
+            if (c.is_ascii_alphanumeric() || c == '-' || c == '_') && !alphabet.contains(&c) {
+                alphabet.push(c);
+            }
+        }
+        // Sqids takes one character for the prefix and still needs a base of two.
+        if alphabet.len() < 3 {
+            eprintln!("❌ Alias alphabet is too short, falling back to the default one");
+            alphabet = AliasConfig::default().alphabet.chars().collect();
+        }
+        let shuffled = consistent_shuffle(&alphabet, &config.salt);
+        Self {
+            config,
+            alphabet,
+            shuffled,
+            url_repository,
+        }
+    }
+
+    pub fn initial_length(&self) -> usize {
+        self.config.length.max(1)
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.config.max_attempts.max(1)
+    }
+
+    pub fn grow_after(&self) -> u32 {
+        self.config.grow_after.max(1)
+    }
+
+    pub async fn generate(&self, length: usize) -> Result<String, sqlx::Error> {
+        match self.config.strategy {
+            AliasStrategy::Random => new_random_string(&self.alphabet, length)
+                .await
+                .map_err(|_| sqlx::Error::Protocol("random string error".into())),
+            AliasStrategy::Counter => {
+                let seq = self.url_repository.next_alias_sequence().await?;
+                Ok(encode_counter(seq as u64, &self.alphabet, length))
+            }
+            AliasStrategy::Sqids => {
+                let seq = self.url_repository.next_alias_sequence().await?;
+                Ok(encode_sqids(seq as u64, &self.shuffled, length))
+            }
+        }
+    }
+}
+
+/// Base-N representation of `n`, left-padded with the zero digit up to `min_length`.
+fn encode_counter(mut n: u64, alphabet: &[char], min_length: usize) -> String {
+    let base = alphabet.len() as u64;
+    let mut digits = Vec::new();
+    loop {
+        digits.push(alphabet[(n % base) as usize]);
+        n /= base;
+        if n == 0 {
+            break;
+        }
+    }
+    while digits.len() < min_length {
+        digits.push(alphabet[0]);
+    }
+    digits.iter().rev().collect()
+}
+
+/// Sqids-style encoding: the alphabet is rotated by an offset derived from the
+/// number, the first rotated char becomes a prefix and the rest is used as the
+/// digit set. Consecutive sequence values therefore do not look consecutive.
+fn encode_sqids(n: u64, alphabet: &[char], min_length: usize) -> String {
+    let len = alphabet.len();
+    let offset = (alphabet[(n % len as u64) as usize] as usize + n as usize) % len;
+    let mut rotated = alphabet.to_vec();
+    rotated.rotate_left(offset);
+    let prefix = rotated[0];
+    let body = encode_counter(n, &rotated[1..], min_length.saturating_sub(1));
+    format!("{}{}", prefix, body)
+}
+
+/// Deterministic salt-driven shuffle, the same one hashids uses.
+fn consistent_shuffle(alphabet: &[char], salt: &str) -> Vec<char> {
+    let mut result = alphabet.to_vec();
+    let salt: Vec<char> = salt.chars().collect();
+    if salt.is_empty() {
+        return result;
+    }
+    let mut v = 0;
+    let mut p = 0;
+    for i in (1..result.len()).rev() {
+        v %= salt.len();
+        let integer = salt[v] as usize;
+        p += integer;
+        let j = (integer + v + p) % i;
+        result.swap(i, j);
+        v += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn chars(alphabet: &str) -> Vec<char> {
+        alphabet.chars().collect()
+    }
+
+    #[test]
+    fn encode_counter_is_base_n_with_padding() {
+        let binary = chars("01");
+        assert_eq!(encode_counter(0, &binary, 1), "0");
+        assert_eq!(encode_counter(5, &binary, 1), "101");
+        assert_eq!(encode_counter(5, &binary, 6), "000101");
+        assert_eq!(encode_counter(61, &chars("abc"), 0), "cacb");
+    }
+
+    #[test]
+    fn encode_sqids_is_unique_and_respects_min_length() {
+        let alphabet = chars(&AliasConfig::default().alphabet);
+        let mut seen = HashSet::new();
+        for n in 0..10_000 {
+            let alias = encode_sqids(n, &alphabet, 6);
+            assert!(alias.len() >= 6, "{} is too short", alias);
+            assert!(alias.chars().all(|c| alphabet.contains(&c)));
+            assert!(seen.insert(alias), "duplicate alias for {}", n);
+        }
+    }
+
+    #[test]
+    fn encode_sqids_works_with_the_smallest_alphabet() {
+        let alphabet = chars("abc");
+        let aliases: HashSet<_> = (0..100).map(|n| encode_sqids(n, &alphabet, 1)).collect();
+        assert_eq!(aliases.len(), 100);
+    }
+
+    #[test]
+    fn consistent_shuffle_is_a_salted_permutation() {
+        let alphabet = chars("abcdefghij");
+        let shuffled = consistent_shuffle(&alphabet, "salt");
+
+        assert_eq!(shuffled, consistent_shuffle(&alphabet, "salt"));
+        assert_ne!(shuffled, consistent_shuffle(&alphabet, "pepper"));
+        assert_eq!(consistent_shuffle(&alphabet, ""), alphabet);
+        let mut sorted = shuffled.clone();
+        sorted.sort();
+        assert_eq!(sorted, alphabet);
+    }
+}
@@ -1,15 +1,37 @@
+use crate::app::config::Config;
+use crate::feature::analytics::service::{AnalyticsService, AnalyticsServiceTrait, ClickContext};
+use crate::feature::redirect_rule::entity::{Platform, VisitorContext, preferred_language};
+use crate::feature::redirect_rule::service::{RedirectRuleService, RedirectRuleServiceTrait};
+use crate::feature::variant::service::{VariantService, VariantServiceTrait};
 use crate::metrics::PrometheusMetrics;
+use crate::utils::request::{TrustedProxies, client_ip, header_value, request_host};
 use axum::Extension;
-use axum::extract::Path;
-use axum::response::{IntoResponse, Redirect, Response};
+use axum::body::Bytes;
+use axum::extract::{ConnectInfo, Form, FromRequest, Multipart, Path, Query, RawQuery, Request};
+use axum::http::{HeaderMap, header};
+use axum::response::{Html, IntoResponse, Redirect, Response};
 use axum::{Json, extract::State, http::StatusCode};
-use serde_json;
+use axum_extra::extract::cookie::CookieJar;
+use cookie::{Cookie, SameSite};
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::feature::auth::entity::UserRole;
-use crate::feature::url::entity::{CreateUrlDTO, RedirectDto};
+use crate::feature::url::bulk::{parse_csv_rows, parse_json_rows};
+use crate::feature::url::entity::{
+    BulkCreateResponse, BulkRow, CreateUrlDTO, LinkPasswordForm, ListUrlsQuery, NewUrl, QrQuery,
+    RedirectQuery, ShortenedUrl, UpdateUrlDTO, UrlError, UrlFilter, UrlHistoryEntry, UrlPage,
+    UrlRejection,
+};
+use crate::feature::url::limiter::PasswordAttemptLimiter;
+use crate::feature::url::pages::{
+    error_page, gone_page, interstitial_page, not_found_page, password_page, preview_page,
+    too_many_attempts_page,
+};
+use crate::feature::url::qr::{QrOptions, render_qr};
+use crate::utils::constants::{LINK_PASSWORD_HEADER, VARIANT_COOKIE_PREFIX};
 
 use crate::servers::http::middleware::UserJWT;
 use crate::{
@@ -19,40 +41,223 @@ use crate::{
 
 pub struct UrlHandler {
     url_service: Arc<UrlService>,
+    analytics_service: Arc<AnalyticsService>,
+    rule_service: Arc<RedirectRuleService>,
+    variant_service: Arc<VariantService>,
     metrics: Arc<PrometheusMetrics>,
+    password_limiter: PasswordAttemptLimiter,
+    variant_cookie_ttl: Duration,
+    trusted_proxies: TrustedProxies,
+}
+
+/// Request-handling settings of `UrlHandler` taken from the config.
+pub struct UrlHandlerOptions {
+    pub password_limiter: PasswordAttemptLimiter,
+    pub variant_cookie_ttl: Duration,
+    pub trusted_proxies: TrustedProxies,
+}
+
+impl UrlHandlerOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            password_limiter: PasswordAttemptLimiter::new(
+                config.url.password_max_attempts,
+                config.url.password_max_alias_attempts,
+                config.url.get_password_lockout(),
+                config.url.password_limiter_capacity,
+            ),
+            variant_cookie_ttl: config.url.get_variant_cookie_ttl(),
+            trusted_proxies: TrustedProxies::from_config(&config.proxy),
+        }
+    }
 }
 
 impl UrlHandler {
-    pub fn new_handler(url_service: Arc<UrlService>, metrics: Arc<PrometheusMetrics>) -> Self {
+    pub fn new_handler(
+        url_service: Arc<UrlService>,
+        analytics_service: Arc<AnalyticsService>,
+        rule_service: Arc<RedirectRuleService>,
+        variant_service: Arc<VariantService>,
+        metrics: Arc<PrometheusMetrics>,
+        options: UrlHandlerOptions,
+    ) -> Self {
+        let UrlHandlerOptions {
+            password_limiter,
+            variant_cookie_ttl,
+            trusted_proxies,
+        } = options;
         Self {
             url_service,
+            analytics_service,
+            rule_service,
+            variant_service,
             metrics,
+            password_limiter,
+            variant_cookie_ttl,
+            trusted_proxies,
+        }
+    }
+
+    /// Target of the first redirect rule matching the visitor, otherwise of
+    /// an A/B variant, together with the variant id. `None` means the link's
+    /// own destination. Lookup failures never block the redirect.
+    async fn resolve_target(
+        &self,
+        url: &Url,
+        ip: IpAddr,
+        headers: &HeaderMap,
+    ) -> (Option<String>, Option<Uuid>) {
+        let user_agent = header_value(headers, "user-agent").unwrap_or_default();
+        let visitor = VisitorContext {
+            platform: Platform::detect(&user_agent),
+            country: self.analytics_service.country_for(Some(ip)),
+            language: header_value(headers, "accept-language")
+                .as_deref()
+                .and_then(preferred_language),
+            user_agent,
+        };
+        match self.rule_service.match_destination(url.id, &visitor).await {
+            Ok(Some(target)) => return (Some(target), None),
+            Ok(None) => {}
+            Err(err) => {
+                eprintln!("❌ Error evaluating redirect rules: {}", err);
+                self.metrics.inc_errors("database_error", "url_handler");
+            }
+        }
+        let sticky = CookieJar::from_headers(headers)
+            .get(&variant_cookie_name(url.id))
+            .and_then(|cookie| Uuid::parse_str(cookie.value()).ok());
+        match self.variant_service.choose_variant(url.id, sticky).await {
+            Ok(Some(variant)) => (Some(variant.destination), Some(variant.id)),
+            Ok(None) => (None, None),
+            Err(err) => {
+                eprintln!("❌ Error choosing A/B variant: {}", err);
+                self.metrics.inc_errors("database_error", "url_handler");
+                (None, None)
+            }
+        }
+    }
+
+    /// Keeps the visitor on the same variant on their next visits.
+    fn variant_cookie(&self, url_id: Uuid, variant_id: Uuid) -> CookieJar {
+        let cookie = Cookie::build((variant_cookie_name(url_id), variant_id.to_string()))
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .max_age(cookie::time::Duration::seconds(
+                self.variant_cookie_ttl.as_secs() as i64,
+            ))
+            .path("/")
+            .build();
+        CookieJar::new().add(cookie)
+    }
+
+    fn error_response(&self, err: UrlError) -> Response {
+        match err {
+            UrlError::NotFound => {
+                self.metrics.inc_errors("not_found", "url_handler");
+                (StatusCode::NOT_FOUND, Json("URL not found".to_string())).into_response()
+            }
+            UrlError::Forbidden => {
+                self.metrics
+                    .inc_errors("authorization_error", "url_handler");
+                (StatusCode::FORBIDDEN, Json("Forbidden".to_string())).into_response()
+            }
+            UrlError::InvalidCursor => {
+                self.metrics.inc_errors("validation_error", "url_handler");
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json("Invalid pagination cursor".to_string()),
+                )
+                    .into_response()
+            }
+            UrlError::AliasAlreadyExists => {
+                self.metrics.inc_errors("conflict", "url_handler");
+                (
+                    StatusCode::CONFLICT,
+                    Json("Alias is already taken".to_string()),
+                )
+                    .into_response()
+            }
+            UrlError::UrlAlreadyExists => {
+                self.metrics.inc_errors("conflict", "url_handler");
+                (
+                    StatusCode::CONFLICT,
+                    Json("URL is already shortened".to_string()),
+                )
+                    .into_response()
+            }
+            UrlError::AliasGenerationFailed => {
+                self.metrics
+                    .inc_errors("alias_generation_error", "url_handler");
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json("Could not generate a free alias".to_string()),
+                )
+                    .into_response()
+            }
+            UrlError::TooManyRows(_) => {
+                self.metrics.inc_errors("validation_error", "url_handler");
+                (StatusCode::PAYLOAD_TOO_LARGE, Json(err.to_string())).into_response()
+            }
+            UrlError::DomainNotAvailable(_) => {
+                self.metrics.inc_errors("validation_error", "url_handler");
+                (StatusCode::UNPROCESSABLE_ENTITY, Json(err.to_string())).into_response()
+            }
+            UrlError::PolicyViolation(violations) => {
+                self.metrics.inc_errors("policy_violation", "url_handler");
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(UrlRejection {
+                        error: "URL rejected by policy".to_string(),
+                        violations,
+                    }),
+                )
+                    .into_response()
+            }
+            err => {
+                eprintln!("❌ Url handler error: {}", err);
+                self.metrics.inc_errors("database_error", "url_handler");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json("Internal server error".to_string()),
+                )
+                    .into_response()
+            }
         }
     }
 }
 
 #[utoipa::path(
     get,
-    path = "/url",
+    path = "/private/url",
+    params(ListUrlsQuery),
     responses(
-        (status = 200, description = "URLs retrieved successfully", body = Vec<Url>),
+        (status = 200, description = "URLs retrieved successfully", body = UrlPage),
         (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "Forbidden - requires admin role"),
+        (status = 400, description = "Invalid pagination cursor"),
         (status = 500, description = "Internal server error")
     ),
+    security(
+        ("cookie_auth" = [])
+    ),
     tag = "URL"
 )]
 pub async fn get_all_url_handler_axum(
+    Extension(user_jwt): Extension<UserJWT>,
     State(handlers): State<Arc<UrlHandler>>,
-) -> impl IntoResponse {
-    match handlers.url_service.get_all_url().await {
-        Ok(urls) => Ok(Json(urls)),
-        Err(_) => {
-            handlers.metrics.inc_errors("database_error", "url_handler");
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json("Error retrieving URLs".to_string()),
-            ))
-        }
+    Query(query): Query<ListUrlsQuery>,
+) -> Response {
+    if !user_jwt.is_admin() {
+        return handlers.error_response(UrlError::Forbidden);
+    }
+    let result = match UrlFilter::from_query(query, None) {
+        Ok(filter) => handlers.url_service.list_urls(filter).await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(page) => Json(page).into_response(),
+        Err(err) => handlers.error_response(err),
     }
 }
 
@@ -61,10 +266,13 @@ pub async fn get_all_url_handler_axum(
     path = "/url/save",
     request_body = CreateUrlDTO,
     responses(
-        (status = 201, description = "URL created successfully"),
+        (status = 201, description = "URL created successfully", body = ShortenedUrl),
         (status = 401, description = "Unauthorized"),
-        (status = 422, description = "Validation error"),
-        (status = 500, description = "Internal server error")
+        (status = 403, description = "API key without the write scope"),
+        (status = 409, description = "Alias is already taken, or the URL is already shortened in `per_user` mode"),
+        (status = 422, description = "Validation error, unverified custom domain or destination rejected by URL policy", body = UrlRejection),
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "Could not generate a free alias")
     ),
     security(
         ("cookie_auth" = [])
@@ -75,14 +283,7 @@ pub async fn create_url_handler(
     Extension(user_jwt): Extension<UserJWT>,
     State(handlers): State<Arc<UrlHandler>>,
     Json(payload): Json<CreateUrlDTO>,
-) -> impl IntoResponse {
-    let user = user_jwt.clone();
-    if user.role != UserRole::Admin {
-        handlers
-            .metrics
-            .inc_errors("authorization_error", "url_handler");
-        return (StatusCode::FORBIDDEN, Json("Unauthorized".to_string()));
-    }
+) -> Response {
     if let Err(validation_errors) = payload.validate() {
         handlers
             .metrics
@@ -90,23 +291,240 @@ pub async fn create_url_handler(
         return (
             StatusCode::UNPROCESSABLE_ENTITY,
             Json(format!("Validation error: {:?}", validation_errors)),
-        );
+        )
+            .into_response();
     }
+    let new_url = match NewUrl::try_from(payload) {
+        Ok(new_url) => new_url,
+        Err(err) => {
+            handlers
+                .metrics
+                .inc_errors("validation_error", "url_handler");
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(format!("Validation error: {:?}", err)),
+            )
+                .into_response();
+        }
+    };
 
-    match handlers.url_service.create_url(payload.url, user.id).await {
-        Ok(_) => {
+    match handlers.url_service.create_url(new_url, user_jwt.id).await {
+        Ok(created) => {
             handlers.metrics.inc_url_shortening();
-            (StatusCode::CREATED, Json("Saved".to_string()))
+            (StatusCode::CREATED, Json(created)).into_response()
         }
-        Err(_) => {
-            handlers.metrics.inc_errors("database_error", "url_handler");
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json("Error while saving".to_string()),
-            )
+        Err(err) => handlers.error_response(err),
+    }
+}
+/// Reads bulk rows from a JSON array, a `text/csv` body or a multipart upload
+/// whose first file field holds the CSV.
+async fn read_bulk_rows(request: Request) -> Result<Vec<BulkRow>, String> {
+    let content_type = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if content_type.starts_with("multipart/form-data") {
+        let mut multipart = Multipart::from_request(request, &())
+            .await
+            .map_err(|err| err.body_text())?;
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|err| err.body_text())?
+        {
+            if field.file_name().is_none() && field.name() != Some("file") {
+                continue;
+            }
+            let is_json = field.content_type() == Some("application/json");
+            let body = field.bytes().await.map_err(|err| err.body_text())?;
+            return if is_json {
+                parse_json_rows(&body)
+            } else {
+                parse_csv_rows(&body)
+            };
         }
+        return Err("Multipart body must contain a CSV file field".to_string());
+    }
+    let body = Bytes::from_request(request, &())
+        .await
+        .map_err(|err| err.body_text())?;
+    if content_type.starts_with("text/csv") {
+        parse_csv_rows(&body)
+    } else {
+        parse_json_rows(&body)
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/private/url/bulk",
+    request_body(
+        content(
+            (Vec<CreateUrlDTO> = "application/json"),
+            (String = "text/csv"),
+            (String = "multipart/form-data")
+        ),
+        description = "JSON array of links, or CSV with a header row (url, alias, expires_at, ttl_seconds, max_clicks)"
+    ),
+    responses(
+        (status = 200, description = "Per-row results", body = BulkCreateResponse),
+        (status = 400, description = "Malformed body"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "API key without the write scope"),
+        (status = 413, description = "Too many rows"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "URL"
+)]
+pub async fn bulk_create_urls_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<UrlHandler>>,
+    request: Request,
+) -> Response {
+    let rows = match read_bulk_rows(request).await {
+        Ok(rows) => rows,
+        Err(message) => {
+            handlers
+                .metrics
+                .inc_errors("validation_error", "url_handler");
+            return (StatusCode::BAD_REQUEST, Json(message)).into_response();
+        }
+    };
+    match handlers.url_service.create_urls(rows, user_jwt.id).await {
+        Ok(results) => {
+            let response = BulkCreateResponse::from(results);
+            handlers.metrics.add_url_shortenings(response.created);
+            Json(response).into_response()
+        }
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/private/url/mine",
+    params(ListUrlsQuery),
+    responses(
+        (status = 200, description = "URLs owned by the current user", body = UrlPage),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 400, description = "Invalid pagination cursor"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "URL"
+)]
+pub async fn get_my_urls_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<UrlHandler>>,
+    Query(query): Query<ListUrlsQuery>,
+) -> Response {
+    let result = match UrlFilter::from_query(query, Some(user_jwt.id)) {
+        Ok(filter) => handlers.url_service.list_urls(filter).await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(page) => Json(page).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/private/url/{id}",
+    params(
+        ("id" = Uuid, Path, description = "URL ID to update")
+    ),
+    request_body = UpdateUrlDTO,
+    responses(
+        (status = 200, description = "URL updated successfully", body = Url),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL not found"),
+        (status = 409, description = "Alias is already taken"),
+        (status = 422, description = "Validation error or destination rejected by URL policy", body = UrlRejection),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "URL"
+)]
+pub async fn update_url_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<UrlHandler>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateUrlDTO>,
+) -> Response {
+    if let Err(validation_errors) = payload.validate() {
+        handlers
+            .metrics
+            .inc_errors("validation_error", "url_handler");
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(format!("Validation error: {:?}", validation_errors)),
+        )
+            .into_response();
+    }
+    if payload.is_empty() {
+        handlers
+            .metrics
+            .inc_errors("validation_error", "url_handler");
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json("Nothing to update".to_string()),
+        )
+            .into_response();
+    }
+    match handlers
+        .url_service
+        .update_url(id, payload.into(), user_jwt.id, user_jwt.is_admin())
+        .await
+    {
+        Ok(url) => Json(url).into_response(),
+        Err(err) => handlers.error_response(err),
     }
 }
+
+#[utoipa::path(
+    get,
+    path = "/private/url/{id}/history",
+    params(
+        ("id" = Uuid, Path, description = "URL ID")
+    ),
+    responses(
+        (status = 200, description = "Change history of the URL", body = Vec<UrlHistoryEntry>),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "URL"
+)]
+pub async fn get_url_history_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<UrlHandler>>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    match handlers
+        .url_service
+        .get_url_history(id, user_jwt.id, user_jwt.is_admin())
+        .await
+    {
+        Ok(history) => Json(history).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
 #[utoipa::path(
     delete,
     path = "/url/{id}",
@@ -116,6 +534,8 @@ pub async fn create_url_handler(
     responses(
         (status = 201, description = "URL deleted successfully"),
         (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL not found"),
         (status = 500, description = "Internal server error")
     ),
     security(
@@ -124,40 +544,241 @@ pub async fn create_url_handler(
     tag = "URL"
 )]
 pub async fn delete_url_handler(
+    Extension(user_jwt): Extension<UserJWT>,
     State(handlers): State<Arc<UrlHandler>>,
     Path(id): Path<Uuid>,
-) -> impl IntoResponse {
-    match handlers.url_service.delete_url(id).await {
-        Ok(_) => (StatusCode::CREATED, Json("Deleted".to_string())),
-        Err(_) => {
-            handlers.metrics.inc_errors("database_error", "url_handler");
+) -> Response {
+    match handlers
+        .url_service
+        .delete_url(id, user_jwt.id, user_jwt.is_admin())
+        .await
+    {
+        Ok(_) => (StatusCode::CREATED, Json("Deleted".to_string())).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+fn variant_cookie_name(url_id: Uuid) -> String {
+    format!("{}{}", VARIANT_COOKIE_PREFIX, url_id.simple())
+}
+
+async fn follow_and_redirect(
+    handlers: &UrlHandler,
+    alias: String,
+    password: Option<String>,
+    query: Option<String>,
+    peer: SocketAddr,
+    headers: &HeaderMap,
+) -> Response {
+    let ip = client_ip(headers, peer, &handlers.trusted_proxies);
+    if password.is_some() && handlers.password_limiter.is_blocked(ip, &alias) {
+        handlers.metrics.inc_errors("rate_limited", "url_handler");
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Html(too_many_attempts_page(&alias)),
+        )
+            .into_response();
+    }
+    match handlers
+        .url_service
+        .follow_url(
+            request_host(headers, peer, &handlers.trusted_proxies),
+            alias.clone(),
+            password,
+        )
+        .await
+    {
+        Ok(resolved) => {
+            let url = resolved.url;
+            if url.is_protected() {
+                handlers.password_limiter.reset(ip, &alias);
+            }
+            handlers.metrics.inc_url_redirects();
+            let (target, variant_id) = handlers.resolve_target(&url, ip, headers).await;
+            handlers.analytics_service.record_click(
+                url.id,
+                !resolved.click_counted,
+                ClickContext {
+                    ip: Some(ip),
+                    referrer: header_value(headers, "referer"),
+                    user_agent: header_value(headers, "user-agent"),
+                    variant_id,
+                },
+            );
+            let destination = match target {
+                Some(target) => url.destination_with(&target, query.as_deref()),
+                None => url.destination(query.as_deref()),
+            };
+            let response = if url.interstitial {
+                Html(interstitial_page(&url, &destination)).into_response()
+            } else {
+                Redirect::temporary(&destination).into_response()
+            };
+            match variant_id {
+                Some(variant_id) => {
+                    (handlers.variant_cookie(url.id, variant_id), response).into_response()
+                }
+                None => response,
+            }
+        }
+        Err(UrlError::NotFound) => {
+            handlers.metrics.inc_errors("not_found", "url_handler");
+            (StatusCode::NOT_FOUND, Html(not_found_page(&alias))).into_response()
+        }
+        Err(UrlError::Expired) => {
+            handlers.metrics.inc_errors("expired", "url_handler");
+            (StatusCode::GONE, Html(gone_page(&alias))).into_response()
+        }
+        Err(UrlError::PasswordRequired) => (
+            StatusCode::UNAUTHORIZED,
+            Html(password_page(&alias, None, query.as_deref())),
+        )
+            .into_response(),
+        Err(UrlError::InvalidPassword) => {
+            handlers.password_limiter.record_failure(ip, &alias);
+            handlers
+                .metrics
+                .inc_errors("invalid_password", "url_handler");
             (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json("Error while saving".to_string()),
+                StatusCode::UNAUTHORIZED,
+                Html(password_page(
+                    &alias,
+                    Some("Wrong password, try again."),
+                    query.as_deref(),
+                )),
             )
+                .into_response()
+        }
+        Err(err) => {
+            eprintln!("❌ Error resolving alias: {}", err);
+            handlers.metrics.inc_errors("database_error", "url_handler");
+            (StatusCode::INTERNAL_SERVER_ERROR, Html(error_page())).into_response()
+        }
+    }
+}
+
+async fn preview_response(
+    handlers: &UrlHandler,
+    alias: &str,
+    peer: SocketAddr,
+    headers: &HeaderMap,
+) -> Response {
+    match handlers
+        .url_service
+        .preview_url(
+            request_host(headers, peer, &handlers.trusted_proxies),
+            alias.to_string(),
+        )
+        .await
+    {
+        Ok(url) => Html(preview_page(&url)).into_response(),
+        Err(UrlError::NotFound) => {
+            handlers.metrics.inc_errors("not_found", "url_handler");
+            (StatusCode::NOT_FOUND, Html(not_found_page(alias))).into_response()
+        }
+        Err(UrlError::Expired) => {
+            handlers.metrics.inc_errors("expired", "url_handler");
+            (StatusCode::GONE, Html(gone_page(alias))).into_response()
+        }
+        Err(err) => {
+            eprintln!("❌ Error previewing alias: {}", err);
+            handlers.metrics.inc_errors("database_error", "url_handler");
+            (StatusCode::INTERNAL_SERVER_ERROR, Html(error_page())).into_response()
         }
     }
 }
 
 #[utoipa::path(
-    delete,
-    path = "/url/redirect",
+    get,
+    path = "/{alias}",
     params(
-        ("id" = Uuid, Path, description = "URL ID to delete")
+        ("alias" = String, Path, description = "Short link alias on the requested host; a trailing `+` shows a preview page instead"),
+        RedirectQuery,
+        ("X-Link-Password" = Option<String>, Header, description = "Password for protected links")
     ),
     responses(
-        (status = 201, description = "URL deleted successfully"),
-        (status = 401, description = "Unauthorized - requires authentication"),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Interstitial or preview page", content_type = "text/html"),
+        (status = 307, description = "Redirect to the destination URL"),
+        (status = 401, description = "Password required or wrong", content_type = "text/html"),
+        (status = 404, description = "Short link not found", content_type = "text/html"),
+        (status = 410, description = "Short link has expired", content_type = "text/html"),
+        (status = 429, description = "Too many wrong passwords", content_type = "text/html"),
+        (status = 500, description = "Internal server error", content_type = "text/html")
+    ),
+    tag = "Redirect"
+)]
+pub async fn redirect_url_handler(
+    State(handlers): State<Arc<UrlHandler>>,
+    Path(alias): Path<String>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    Query(query): Query<RedirectQuery>,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
+) -> Response {
+    if let Some(alias) = alias.strip_suffix('+') {
+        return preview_response(&handlers, alias, peer, &headers).await;
+    }
+    let password = header_value(&headers, LINK_PASSWORD_HEADER).or(query.password);
+    follow_and_redirect(&handlers, alias, password, raw_query, peer, &headers).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/{alias}",
+    params(
+        ("alias" = String, Path, description = "Short link alias")
     ),
+    request_body(content = LinkPasswordForm, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 307, description = "Redirect to the destination URL"),
+        (status = 401, description = "Wrong password", content_type = "text/html"),
+        (status = 404, description = "Short link not found", content_type = "text/html"),
+        (status = 410, description = "Short link has expired", content_type = "text/html"),
+        (status = 429, description = "Too many wrong passwords", content_type = "text/html")
+    ),
+    tag = "Redirect"
+)]
+pub async fn unlock_url_handler(
+    State(handlers): State<Arc<UrlHandler>>,
+    Path(alias): Path<String>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    RawQuery(raw_query): RawQuery,
+    headers: HeaderMap,
+    Form(form): Form<LinkPasswordForm>,
+) -> Response {
+    follow_and_redirect(
+        &handlers,
+        alias,
+        Some(form.password),
+        raw_query,
+        peer,
+        &headers,
+    )
+    .await
+}
 
+#[utoipa::path(
+    get,
+    path = "/url/{alias}/qr",
+    params(
+        ("alias" = String, Path, description = "Short link alias"),
+        QrQuery
+    ),
+    responses(
+        (status = 200, description = "QR code encoding the short URL, `image/svg+xml` when `format=svg`", content_type = "image/png"),
+        (status = 404, description = "Short link not found"),
+        (status = 410, description = "Short link has expired"),
+        (status = 422, description = "Invalid QR parameters"),
+        (status = 500, description = "Internal server error")
+    ),
     tag = "URL"
 )]
-pub async fn redirect_url_handler(
+pub async fn qr_code_handler(
     State(handlers): State<Arc<UrlHandler>>,
-    Json(payload): Json<RedirectDto>,
+    Path(alias): Path<String>,
+    Query(query): Query<QrQuery>,
 ) -> Response {
-    if let Err(validation_errors) = payload.validate() {
+    if let Err(validation_errors) = query.validate() {
         handlers
             .metrics
             .inc_errors("validation_error", "url_handler");
@@ -167,20 +788,26 @@ pub async fn redirect_url_handler(
         )
             .into_response();
     }
-    match handlers.url_service.get_url_by_hash(payload.id).await {
-        Ok(Some(url)) => {
-            handlers.metrics.inc_url_redirects();
-            Redirect::permanent(&url.url).into_response()
+    let shortened = match handlers
+        .url_service
+        .get_short_url(query.domain.clone(), alias)
+        .await
+    {
+        Ok(shortened) => shortened,
+        Err(UrlError::Expired) => {
+            handlers.metrics.inc_errors("expired", "url_handler");
+            return (StatusCode::GONE, Json("URL has expired".to_string())).into_response();
         }
-        Ok(None) => {
-            handlers.metrics.inc_errors("not_found", "url_handler");
-            (StatusCode::NOT_FOUND, Json("URL not found".to_string())).into_response()
-        }
-        Err(_) => {
-            handlers.metrics.inc_errors("database_error", "url_handler");
+        Err(err) => return handlers.error_response(err),
+    };
+    match render_qr(&shortened.short_url, &QrOptions::from(&query)) {
+        Ok(image) => ([(header::CONTENT_TYPE, image.content_type)], image.bytes).into_response(),
+        Err(err) => {
+            eprintln!("❌ Error rendering QR code: {}", err);
+            handlers.metrics.inc_errors("qr_error", "url_handler");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json("Error while retrieving URL".to_string()),
+                Json("Could not render QR code".to_string()),
             )
                 .into_response()
         }
@@ -0,0 +1,154 @@
+use lru::LruCache;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Failure counts within a window, keeping at most `capacity` keys so that
+/// requests from many clients cannot grow it without bound.
+struct AttemptCounter<K: Hash + Eq> {
+    max: u32,
+    entries: Mutex<LruCache<K, (u32, Instant)>>,
+}
+
+impl<K: Hash + Eq> AttemptCounter<K> {
+    fn new(max: u32, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            max,
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    fn exceeded(&self, key: &K, window: Duration) -> bool {
+        self.entries
+            .lock()
+            .unwrap()
+            .peek(key)
+            .is_some_and(|(count, since)| *count >= self.max && since.elapsed() < window)
+    }
+
+    fn record(&self, key: K, window: Duration) {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_or_insert_mut(key, || (0, Instant::now()));
+        if entry.1.elapsed() >= window {
+            *entry = (0, Instant::now());
+        }
+        entry.0 += 1;
+    }
+
+    fn remove(&self, key: &K) {
+        self.entries.lock().unwrap().pop(key);
+    }
+}
+
+/// Counts failed password attempts within a sliding window, per client and
+/// alias and per alias across all clients.
+pub struct PasswordAttemptLimiter {
+    window: Duration,
+    clients: AttemptCounter<(IpAddr, String)>,
+    aliases: AttemptCounter<String>,
+}
+
+impl PasswordAttemptLimiter {
+    pub fn new(
+        max_attempts: u32,
+        max_alias_attempts: u32,
+        window: Duration,
+        capacity: usize,
+    ) -> Self {
+        Self {
+            window,
+            clients: AttemptCounter::new(max_attempts, capacity),
+            aliases: AttemptCounter::new(max_alias_attempts, capacity),
+        }
+    }
+
+    pub fn is_blocked(&self, ip: IpAddr, alias: &str) -> bool {
+        self.clients.exceeded(&(ip, alias.to_string()), self.window)
+            || self.aliases.exceeded(&alias.to_string(), self.window)
+    }
+
+    pub fn record_failure(&self, ip: IpAddr, alias: &str) {
+        self.clients.record((ip, alias.to_string()), self.window);
+        self.aliases.record(alias.to_string(), self.window);
+    }
+
+    /// Clears the client's own failures. The per-alias count is kept, so a
+    /// successful unlock does not hand out a fresh batch of guesses.
+    pub fn reset(&self, ip: IpAddr, alias: &str) {
+        self.clients.remove(&(ip, alias.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    const WINDOW: Duration = Duration::from_secs(60);
+
+    fn ip(last: u8) -> IpAddr {
+        IpAddr::from([203, 0, 113, last])
+    }
+
+    #[test]
+    fn blocks_a_client_after_max_attempts_on_that_alias() {
+        let limiter = PasswordAttemptLimiter::new(3, 100, WINDOW, 100);
+        for _ in 0..3 {
+            assert!(!limiter.is_blocked(ip(1), "abc"));
+            limiter.record_failure(ip(1), "abc");
+        }
+        assert!(limiter.is_blocked(ip(1), "abc"));
+        assert!(!limiter.is_blocked(ip(1), "other"));
+        assert!(!limiter.is_blocked(ip(2), "abc"));
+    }
+
+    #[test]
+    fn caps_failures_per_alias_across_clients() {
+        let limiter = PasswordAttemptLimiter::new(3, 5, WINDOW, 100);
+        for last in 0..5 {
+            limiter.record_failure(ip(last), "abc");
+        }
+        assert!(limiter.is_blocked(ip(200), "abc"));
+        assert!(!limiter.is_blocked(ip(200), "other"));
+    }
+
+    #[test]
+    fn reset_clears_only_the_clients_own_failures() {
+        let limiter = PasswordAttemptLimiter::new(2, 3, WINDOW, 100);
+        limiter.record_failure(ip(1), "abc");
+        limiter.record_failure(ip(1), "abc");
+        assert!(limiter.is_blocked(ip(1), "abc"));
+
+        limiter.reset(ip(1), "abc");
+        assert!(!limiter.is_blocked(ip(1), "abc"));
+        limiter.record_failure(ip(2), "abc");
+        assert!(limiter.is_blocked(ip(3), "abc"));
+    }
+
+    #[test]
+    fn failures_expire_with_the_window() {
+        let window = Duration::from_millis(20);
+        let limiter = PasswordAttemptLimiter::new(1, 10, window, 100);
+        limiter.record_failure(ip(1), "abc");
+        assert!(limiter.is_blocked(ip(1), "abc"));
+
+        sleep(window);
+        assert!(!limiter.is_blocked(ip(1), "abc"));
+        limiter.record_failure(ip(1), "abc");
+        assert!(limiter.is_blocked(ip(1), "abc"));
+    }
+
+    #[test]
+    fn keeps_at_most_capacity_clients() {
+        let limiter = PasswordAttemptLimiter::new(1, 100, WINDOW, 2);
+        for last in 0..3 {
+            limiter.record_failure(ip(last), "abc");
+        }
+        assert_eq!(limiter.clients.entries.lock().unwrap().len(), 2);
+        assert!(!limiter.is_blocked(ip(0), "abc"));
+        assert!(limiter.is_blocked(ip(2), "abc"));
+    }
+}
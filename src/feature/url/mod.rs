@@ -1,4 +1,12 @@
+pub mod bulk;
 pub mod entity;
+pub mod generator;
 pub mod handler;
+pub mod limiter;
+pub mod pages;
+pub mod policy;
+pub mod qr;
 pub mod repository;
+pub mod resource;
 pub mod service;
+pub mod sweeper;
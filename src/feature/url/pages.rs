@@ -0,0 +1,186 @@
+use crate::domain::url::Url;
+use crate::utils::constants::LINK_PASSWORD_PARAM;
+
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn destination_host(url: &str) -> String {
+    ::url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string())
+}
+
+fn layout(title: &str, body: &str) -> String {
+    layout_with_head(title, "", body)
+}
+
+fn layout_with_head(title: &str, head: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+{head}<style>
+body {{ font-family: system-ui, sans-serif; background: #f5f5f7; color: #1d1d1f; display: flex; align-items: center; justify-content: center; min-height: 100vh; margin: 0; }}
+main {{ background: #fff; border-radius: 12px; padding: 32px 40px; max-width: 480px; box-shadow: 0 4px 24px rgba(0, 0, 0, 0.08); }}
+h1 {{ margin-top: 0; font-size: 24px; }}
+code {{ background: #f0f0f2; padding: 2px 6px; border-radius: 4px; word-break: break-all; }}
+form {{ display: flex; gap: 8px; }}
+input {{ flex: 1; padding: 8px 12px; border: 1px solid #d2d2d7; border-radius: 8px; font-size: 16px; }}
+button {{ padding: 8px 16px; border: 0; border-radius: 8px; background: #0071e3; color: #fff; font-size: 16px; cursor: pointer; }}
+.error {{ color: #d70015; }}
+.button {{ display: inline-block; padding: 8px 16px; border-radius: 8px; background: #0071e3; color: #fff; text-decoration: none; }}
+dl {{ display: grid; grid-template-columns: auto 1fr; gap: 8px 16px; }}
+dt {{ color: #6e6e73; }}
+dd {{ margin: 0; word-break: break-all; }}
+</style>
+</head>
+<body>
+<main>
+{body}
+</main>
+</body>
+</html>"#,
+        title = escape_html(title),
+        head = head,
+        body = body,
+    )
+}
+
+pub fn not_found_page(alias: &str) -> String {
+    layout(
+        "Link not found",
+        &format!(
+            "<h1>404 — link not found</h1>\n<p>The short link <code>/{}</code> does not exist or has been removed.</p>",
+            escape_html(alias)
+        ),
+    )
+}
+
+pub fn gone_page(alias: &str) -> String {
+    layout(
+        "Link expired",
+        &format!(
+            "<h1>410 — link expired</h1>\n<p>The short link <code>/{}</code> has expired and is no longer available.</p>",
+            escape_html(alias)
+        ),
+    )
+}
+
+/// Unlock form target; keeps the request query so it can still be forwarded,
+/// minus any password given in it.
+fn unlock_action(alias: &str, query: Option<&str>) -> String {
+    let query = query
+        .map(|query| {
+            url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(
+                    url::form_urlencoded::parse(query.as_bytes())
+                        .filter(|(key, _)| key != LINK_PASSWORD_PARAM),
+                )
+                .finish()
+        })
+        .unwrap_or_default();
+    if query.is_empty() {
+        format!("/{}", alias)
+    } else {
+        format!("/{}?{}", alias, query)
+    }
+}
+
+pub fn password_page(alias: &str, error: Option<&str>, query: Option<&str>) -> String {
+    let error = error
+        .map(|error| format!("<p class=\"error\">{}</p>\n", escape_html(error)))
+        .unwrap_or_default();
+    layout(
+        "Password required",
+        &format!(
+            "<h1>Password required</h1>\n<p>The short link <code>/{alias}</code> is password protected.</p>\n{error}<form method=\"post\" action=\"{action}\">\n<input type=\"password\" name=\"password\" placeholder=\"Password\" required autofocus>\n<button type=\"submit\">Open</button>\n</form>",
+            alias = escape_html(alias),
+            action = escape_html(&unlock_action(alias, query)),
+            error = error,
+        ),
+    )
+}
+
+pub fn too_many_attempts_page(alias: &str) -> String {
+    layout(
+        "Too many attempts",
+        &format!(
+            "<h1>429 — too many attempts</h1>\n<p>Too many wrong passwords for <code>/{}</code>. Please try again later.</p>",
+            escape_html(alias)
+        ),
+    )
+}
+
+pub fn interstitial_page(url: &Url, destination: &str) -> String {
+    let host = destination_host(destination);
+    let destination = escape_html(destination);
+    let (head, countdown) = match url.countdown_seconds {
+        Some(seconds) => (
+            format!(
+                "<meta http-equiv=\"refresh\" content=\"{};url={}\">\n",
+                seconds, destination
+            ),
+            format!(
+                "<p>You will be redirected automatically in {} seconds.</p>\n",
+                seconds
+            ),
+        ),
+        None => (String::new(), String::new()),
+    };
+    layout_with_head(
+        "You are leaving",
+        &head,
+        &format!(
+            "<h1>You are leaving</h1>\n<p>This link leads to <strong>{host}</strong>:</p>\n<p><code>{destination}</code></p>\n<p>Only continue if you trust this site.</p>\n{countdown}<a class=\"button\" href=\"{destination}\" rel=\"noopener noreferrer\">Continue</a>",
+            host = escape_html(&host),
+            destination = destination,
+            countdown = countdown,
+        ),
+    )
+}
+
+pub fn preview_page(url: &Url) -> String {
+    let destination = if url.is_protected() {
+        "<dd>Hidden — this link is password protected</dd>".to_string()
+    } else {
+        let target = url.destination(None);
+        format!(
+            "<dd><code>{}</code></dd>\n<dt>Domain</dt><dd>{}</dd>",
+            escape_html(&target),
+            escape_html(&destination_host(&target))
+        )
+    };
+    layout(
+        "Link preview",
+        &format!(
+            "<h1>Link preview</h1>\n<dl>\n<dt>Short link</dt><dd><code>/{alias}</code></dd>\n<dt>Destination</dt>{destination}\n<dt>Created</dt><dd>{created}</dd>\n<dt>Clicks</dt><dd>{clicks}</dd>\n</dl>\n<a class=\"button\" href=\"/{alias}\">Open link</a>",
+            alias = escape_html(&url.alias),
+            destination = destination,
+            created = url.created_at.format("%Y-%m-%d %H:%M UTC"),
+            clicks = url.clicks,
+        ),
+    )
+}
+
+pub fn error_page() -> String {
+    layout(
+        "Something went wrong",
+        "<h1>Something went wrong</h1>\n<p>We could not open this link right now. Please try again later.</p>",
+    )
+}
@@ -0,0 +1,323 @@
+use crate::app::config::{PolicyConfig, UrlConfig};
+use crate::feature::blocklist::entity::normalize_domain;
+use crate::feature::blocklist::repository::{BlocklistRepository, BlocklistRepositoryTrait};
+use crate::feature::custom_domain::repository::{DomainRepository, DomainRepositoryTrait};
+use async_trait::async_trait;
+use serde::Serialize;
+use std::net::IpAddr;
+use std::sync::Arc;
+use url::{Host, Url as ParsedUrl};
+use utoipa::ToSchema;
+
+/// One reason a destination URL was rejected.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PolicyViolation {
+    #[schema(example = "private_network")]
+    pub rule: String,
+    pub message: String,
+}
+
+impl PolicyViolation {
+    fn new(rule: &str, message: impl Into<String>) -> Self {
+        Self {
+            rule: rule.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+#[async_trait]
+pub trait UrlPolicy: Send + Sync {
+    async fn check(&self, url: &ParsedUrl) -> Result<(), PolicyViolation>;
+}
+
+pub struct SchemePolicy {
+    allowed: Vec<String>,
+}
+
+#[async_trait]
+impl UrlPolicy for SchemePolicy {
+    async fn check(&self, url: &ParsedUrl) -> Result<(), PolicyViolation> {
+        if self.allowed.iter().any(|scheme| scheme == url.scheme()) {
+            Ok(())
+        } else {
+            Err(PolicyViolation::new(
+                "scheme_not_allowed",
+                format!("scheme `{}` is not allowed", url.scheme()),
+            ))
+        }
+    }
+}
+
+fn is_private_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // 100.64.0.0/10, carrier-grade NAT
+                || (a == 100 && (64..128).contains(&b))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_private_ip(IpAddr::V4(v4)),
+            None => {
+                let first = ip.segments()[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    // fc00::/7 unique local and fe80::/10 link local
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80
+            }
+        },
+    }
+}
+
+/// Rejects loopback, private and link-local targets. Host names are only
+/// resolved when `resolve_dns` is enabled; otherwise only literal addresses
+/// and `localhost` are caught.
+pub struct PrivateNetworkPolicy {
+    resolve_dns: bool,
+}
+
+#[async_trait]
+impl UrlPolicy for PrivateNetworkPolicy {
+    async fn check(&self, url: &ParsedUrl) -> Result<(), PolicyViolation> {
+        let private = match url.host() {
+            Some(Host::Ipv4(ip)) => is_private_ip(IpAddr::V4(ip)),
+            Some(Host::Ipv6(ip)) => is_private_ip(IpAddr::V6(ip)),
+            Some(Host::Domain(domain)) => {
+                let domain = normalize_domain(domain);
+                if domain == "localhost" || domain.ends_with(".localhost") {
+                    true
+                } else if self.resolve_dns {
+                    let port = url.port_or_known_default().unwrap_or(80);
+                    match tokio::net::lookup_host((domain.as_str(), port)).await {
+                        Ok(mut addrs) => addrs.any(|addr| is_private_ip(addr.ip())),
+                        Err(_) => false,
+                    }
+                } else {
+                    false
+                }
+            }
+            None => false,
+        };
+        if private {
+            Err(PolicyViolation::new(
+                "private_network",
+                "links to private or local network addresses are not allowed",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Rejects links back to the shortener itself, on its own hosts or on a
+/// verified custom domain, which would redirect in a loop.
+pub struct SelfDomainPolicy {
+    hosts: Vec<String>,
+    domain_repository: Arc<DomainRepository>,
+}
+
+#[async_trait]
+impl UrlPolicy for SelfDomainPolicy {
+    async fn check(&self, url: &ParsedUrl) -> Result<(), PolicyViolation> {
+        let loop_violation =
+            || PolicyViolation::new("redirect_loop", "links to this shortener are not allowed");
+        let host = url.host_str().map(normalize_domain).unwrap_or_default();
+        if self.hosts.contains(&host) {
+            return Err(loop_violation());
+        }
+        let Some(Host::Domain(_)) = url.host() else {
+            return Ok(());
+        };
+        match self
+            .domain_repository
+            .get_verified_domain_by_host(host)
+            .await
+        {
+            Ok(Some(domain)) if domain.is_verified() => Err(loop_violation()),
+            Ok(_) => Ok(()),
+            Err(_) => Err(PolicyViolation::new(
+                "domains_unavailable",
+                "could not check custom domains, try again later",
+            )),
+        }
+    }
+}
+
+/// Rejects hosts listed in `blocked_domains`, including their subdomains.
+pub struct BlocklistPolicy {
+    blocklist_repository: Arc<BlocklistRepository>,
+}
+
+#[async_trait]
+impl UrlPolicy for BlocklistPolicy {
+    async fn check(&self, url: &ParsedUrl) -> Result<(), PolicyViolation> {
+        let Some(Host::Domain(domain)) = url.host() else {
+            return Ok(());
+        };
+        let domain = normalize_domain(domain);
+        let candidates: Vec<String> = domain
+            .match_indices('.')
+            .map(|(index, _)| domain[index + 1..].to_string())
+            .chain(std::iter::once(domain.clone()))
+            .collect();
+        match self
+            .blocklist_repository
+            .find_blocked_domain(candidates)
+            .await
+        {
+            Ok(None) => Ok(()),
+            Ok(Some(blocked)) => Err(PolicyViolation::new(
+                "domain_blocked",
+                match blocked.reason {
+                    Some(reason) => format!("domain `{}` is blocked: {}", blocked.domain, reason),
+                    None => format!("domain `{}` is blocked", blocked.domain),
+                },
+            )),
+            Err(_) => Err(PolicyViolation::new(
+                "blocklist_unavailable",
+                "could not check the domain blocklist, try again later",
+            )),
+        }
+    }
+}
+
+/// Runs every policy and collects all violations, so the caller sees every
+/// reason at once.
+pub struct UrlPolicyChain {
+    policies: Vec<Box<dyn UrlPolicy>>,
+}
+
+impl UrlPolicyChain {
+    pub fn new(policies: Vec<Box<dyn UrlPolicy>>) -> Self {
+        Self { policies }
+    }
+
+    pub async fn check(&self, url: &str) -> Result<(), Vec<PolicyViolation>> {
+        let parsed = ParsedUrl::parse(url)
+            .map_err(|err| vec![PolicyViolation::new("invalid_url", err.to_string())])?;
+        let mut violations = Vec::new();
+        for policy in &self.policies {
+            if let Err(violation) = policy.check(&parsed).await {
+                violations.push(violation);
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+pub fn new_url_policy_chain(
+    policy: &PolicyConfig,
+    url: &UrlConfig,
+    blocklist_repository: Arc<BlocklistRepository>,
+    domain_repository: Arc<DomainRepository>,
+) -> UrlPolicyChain {
+    let mut self_hosts: Vec<String> = policy
+        .self_hosts
+        .iter()
+        .map(|host| normalize_domain(host))
+        .collect();
+    if let Some(host) = ParsedUrl::parse(&url.public_base_url)
+        .ok()
+        .and_then(|base| base.host_str().map(normalize_domain))
+    {
+        self_hosts.push(host);
+    }
+
+    let mut policies: Vec<Box<dyn UrlPolicy>> = vec![Box::new(SchemePolicy {
+        allowed: policy
+            .allowed_schemes
+            .iter()
+            .map(|scheme| scheme.to_ascii_lowercase())
+            .collect(),
+    })];
+    if policy.block_private_networks {
+        policies.push(Box::new(PrivateNetworkPolicy {
+            resolve_dns: policy.resolve_dns,
+        }));
+    }
+    policies.push(Box::new(SelfDomainPolicy {
+        hosts: self_hosts,
+        domain_repository,
+    }));
+    policies.push(Box::new(BlocklistPolicy {
+        blocklist_repository,
+    }));
+    UrlPolicyChain::new(policies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn is_private_ip_catches_local_ranges() {
+        for value in [
+            "10.0.0.1",
+            "172.16.5.4",
+            "192.168.1.1",
+            "127.0.0.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "255.255.255.255",
+            "100.64.0.1",
+            "100.127.255.255",
+            "::1",
+            "::",
+            "fc00::1",
+            "fd12:3456::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:10.1.2.3",
+        ] {
+            assert!(is_private_ip(ip(value)), "{} should be private", value);
+        }
+    }
+
+    #[test]
+    fn is_private_ip_allows_public_addresses() {
+        for value in [
+            "8.8.8.8",
+            "100.63.255.255",
+            "100.128.0.1",
+            "172.32.0.1",
+            "2001:4860:4860::8888",
+            "::ffff:8.8.8.8",
+        ] {
+            assert!(!is_private_ip(ip(value)), "{} should be public", value);
+        }
+    }
+
+    #[tokio::test]
+    async fn private_network_policy_checks_literal_hosts_without_dns() {
+        let policy = PrivateNetworkPolicy { resolve_dns: false };
+        for url in [
+            "http://127.0.0.1/",
+            "http://[::1]:8080/",
+            "http://localhost/",
+            "http://api.LOCALHOST/",
+        ] {
+            let url = ParsedUrl::parse(url).unwrap();
+            assert!(
+                policy.check(&url).await.is_err(),
+                "{} should be rejected",
+                url
+            );
+        }
+        let url = ParsedUrl::parse("https://example.com/").unwrap();
+        assert!(policy.check(&url).await.is_ok());
+    }
+}
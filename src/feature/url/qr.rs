@@ -0,0 +1,290 @@
+use crate::feature::url::entity::{QrErrorCorrection, QrFormat, QrQuery};
+use crate::utils::constants::{QR_DEFAULT_MARGIN, QR_DEFAULT_SIZE};
+use qrcode::{Color, EcLevel, QrCode};
+use std::fmt::Write;
+
+type Rgb = [u8; 3];
+
+const BLACK: Rgb = [0, 0, 0];
+const WHITE: Rgb = [255, 255, 255];
+
+/// Parses `#rrggbb`, `rrggbb`, `#rgb` or `rgb`.
+pub fn parse_hex_color(value: &str) -> Option<Rgb> {
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    match hex.len() {
+        6 => Some([
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        ]),
+        3 => {
+            let mut rgb = [0; 3];
+            for (i, digit) in hex.chars().enumerate() {
+                rgb[i] = channel(&digit.to_string())? * 0x11;
+            }
+            Some(rgb)
+        }
+        _ => None,
+    }
+}
+
+fn to_hex(rgb: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+pub struct QrOptions {
+    pub format: QrFormat,
+    /// Target width and height in pixels.
+    pub size: u32,
+    /// Quiet zone around the code, in modules.
+    pub margin: u32,
+    pub error_correction: QrErrorCorrection,
+    pub foreground: Rgb,
+    pub background: Rgb,
+}
+
+impl Default for QrOptions {
+    fn default() -> Self {
+        Self {
+            format: QrFormat::Png,
+            size: QR_DEFAULT_SIZE,
+            margin: QR_DEFAULT_MARGIN,
+            error_correction: QrErrorCorrection::M,
+            foreground: BLACK,
+            background: WHITE,
+        }
+    }
+}
+
+impl From<&QrQuery> for QrOptions {
+    fn from(query: &QrQuery) -> Self {
+        Self {
+            format: query.format,
+            size: query.size.unwrap_or(QR_DEFAULT_SIZE),
+            margin: query.margin.unwrap_or(QR_DEFAULT_MARGIN),
+            error_correction: query.ecc,
+            foreground: query
+                .fg
+                .as_deref()
+                .and_then(parse_hex_color)
+                .unwrap_or(BLACK),
+            background: query
+                .bg
+                .as_deref()
+                .and_then(parse_hex_color)
+                .unwrap_or(WHITE),
+        }
+    }
+}
+
+pub struct QrImage {
+    pub content_type: &'static str,
+    pub bytes: Vec<u8>,
+}
+
+/// Grid of dark modules with the quiet zone already added.
+struct QrGrid {
+    width: usize,
+    dark: Vec<bool>,
+}
+
+impl QrGrid {
+    fn new(code: &QrCode, margin: usize) -> Self {
+        let modules = code.to_colors();
+        let code_width = code.width();
+        let width = code_width + 2 * margin;
+        let mut dark = vec![false; width * width];
+        for y in 0..code_width {
+            for x in 0..code_width {
+                dark[(y + margin) * width + x + margin] =
+                    modules[y * code_width + x] == Color::Dark;
+            }
+        }
+        Self { width, dark }
+    }
+}
+
+fn render_png(grid: &QrGrid, options: &QrOptions) -> Result<Vec<u8>, String> {
+    // Whole pixels per module, so the image can come out slightly smaller
+    // than requested.
+    let scale = (options.size as usize / grid.width).max(1);
+    let pixels = grid.width * scale;
+    let mut data = Vec::with_capacity(pixels * pixels * 3);
+    for y in 0..pixels {
+        for x in 0..pixels {
+            let color = if grid.dark[(y / scale) * grid.width + x / scale] {
+                options.foreground
+            } else {
+                options.background
+            };
+            data.extend_from_slice(&color);
+        }
+    }
+
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, pixels as u32, pixels as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    writer
+        .write_image_data(&data)
+        .map_err(|err| err.to_string())?;
+    writer.finish().map_err(|err| err.to_string())?;
+    Ok(bytes)
+}
+
+fn render_svg(grid: &QrGrid, options: &QrOptions) -> String {
+    let mut path = String::new();
+    for (index, dark) in grid.dark.iter().enumerate() {
+        if *dark {
+            let _ = write!(
+                path,
+                "M{} {}h1v1h-1z",
+                index % grid.width,
+                index / grid.width
+            );
+        }
+    }
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {width} {width}" shape-rendering="crispEdges"><rect width="{width}" height="{width}" fill="{background}"/><path fill="{foreground}" d="{path}"/></svg>
+"#,
+        size = options.size,
+        width = grid.width,
+        background = to_hex(options.background),
+        foreground = to_hex(options.foreground),
+        path = path,
+    )
+}
+
+fn ec_level(error_correction: QrErrorCorrection) -> EcLevel {
+    match error_correction {
+        QrErrorCorrection::L => EcLevel::L,
+        QrErrorCorrection::M => EcLevel::M,
+        QrErrorCorrection::Q => EcLevel::Q,
+        QrErrorCorrection::H => EcLevel::H,
+    }
+}
+
+pub fn render_qr(data: &str, options: &QrOptions) -> Result<QrImage, String> {
+    let code = QrCode::with_error_correction_level(data, ec_level(options.error_correction))
+        .map_err(|err| err.to_string())?;
+    let grid = QrGrid::new(&code, options.margin as usize);
+    match options.format {
+        QrFormat::Png => Ok(QrImage {
+            content_type: "image/png",
+            bytes: render_png(&grid, options)?,
+        }),
+        QrFormat::Svg => Ok(QrImage {
+            content_type: "image/svg+xml",
+            bytes: render_svg(&grid, options).into_bytes(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::constants::{QR_MAX_MARGIN, QR_MAX_SIZE, QR_MIN_SIZE};
+    use validator::Validate;
+
+    const LINK: &str = "https://sho.rt/promo";
+
+    fn qr_query(params: serde_json::Value) -> QrQuery {
+        serde_json::from_value(params).unwrap()
+    }
+
+    #[test]
+    fn parses_long_and_short_hex_colors() {
+        assert_eq!(parse_hex_color("#ff8000"), Some([255, 128, 0]));
+        assert_eq!(parse_hex_color("FF8000"), Some([255, 128, 0]));
+        assert_eq!(parse_hex_color("#f80"), Some([255, 136, 0]));
+        assert_eq!(parse_hex_color("abc"), Some([170, 187, 204]));
+    }
+
+    #[test]
+    fn rejects_invalid_hex_colors() {
+        for value in [
+            "", "#", "#12", "#1234", "#1234567", "zzzzzz", "#ff80 0", "#ééé",
+        ] {
+            assert_eq!(parse_hex_color(value), None, "{}", value);
+        }
+        assert!(
+            qr_query(serde_json::json!({ "fg": "#zzz" }))
+                .validate()
+                .is_err()
+        );
+        let options = QrOptions::from(&qr_query(serde_json::json!({ "bg": "nope" })));
+        assert_eq!(options.background, WHITE);
+    }
+
+    #[test]
+    fn size_must_be_within_bounds() {
+        for size in [QR_MIN_SIZE, QR_DEFAULT_SIZE, QR_MAX_SIZE] {
+            assert!(
+                qr_query(serde_json::json!({ "size": size }))
+                    .validate()
+                    .is_ok()
+            );
+        }
+        for size in [0, QR_MIN_SIZE - 1, QR_MAX_SIZE + 1] {
+            assert!(
+                qr_query(serde_json::json!({ "size": size }))
+                    .validate()
+                    .is_err()
+            );
+        }
+        assert!(
+            qr_query(serde_json::json!({ "margin": QR_MAX_MARGIN + 1 }))
+                .validate()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn png_has_the_png_signature_and_fits_the_requested_size() {
+        let image = render_qr(LINK, &QrOptions::default()).unwrap();
+        assert_eq!(image.content_type, "image/png");
+        assert_eq!(&image.bytes[..8], b"\x89PNG\r\n\x1a\n");
+        let width = u32::from_be_bytes(image.bytes[16..20].try_into().unwrap());
+        let height = u32::from_be_bytes(image.bytes[20..24].try_into().unwrap());
+        assert_eq!(width, height);
+        assert!(width <= QR_DEFAULT_SIZE && width > QR_DEFAULT_SIZE / 2);
+    }
+
+    #[test]
+    fn svg_uses_the_requested_size_and_colors() {
+        let options = QrOptions {
+            format: QrFormat::Svg,
+            size: 300,
+            foreground: [0x11, 0x22, 0x33],
+            background: [0xff, 0xee, 0xdd],
+            ..Default::default()
+        };
+        let image = render_qr(LINK, &options).unwrap();
+        assert_eq!(image.content_type, "image/svg+xml");
+        let svg = String::from_utf8(image.bytes).unwrap();
+        assert!(svg.starts_with("<?xml"));
+        assert!(svg.contains(r#"width="300" height="300""#));
+        assert!(svg.contains(r##"fill="#112233""##));
+        assert!(svg.contains(r##"fill="#ffeedd""##));
+    }
+
+    #[test]
+    fn error_correction_maps_onto_the_qr_levels() {
+        assert_eq!(ec_level(QrErrorCorrection::L), EcLevel::L);
+        assert_eq!(ec_level(QrErrorCorrection::M), EcLevel::M);
+        assert_eq!(ec_level(QrErrorCorrection::Q), EcLevel::Q);
+        assert_eq!(ec_level(QrErrorCorrection::H), EcLevel::H);
+        let width = |error_correction| {
+            QrCode::with_error_correction_level(LINK, ec_level(error_correction))
+                .unwrap()
+                .width()
+        };
+        assert!(width(QrErrorCorrection::H) > width(QrErrorCorrection::L));
+    }
+}
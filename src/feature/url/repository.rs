@@ -1,18 +1,62 @@
 use crate::domain::url::Url;
+use crate::feature::url::entity::{
+    NewUrl, SortOrder, URL_ALIAS, URL_CLICKS, URL_COLUMNS, URL_COUNTDOWN_SECONDS, URL_CREATED_AT,
+    URL_DOMAIN_ID, URL_ENABLED, URL_EXPIRES_AT, URL_FORWARD_QUERY, URL_HISTORY_CHANGED_AT,
+    URL_HISTORY_CHANGED_BY, URL_HISTORY_CHANGES, URL_HISTORY_ID, URL_HISTORY_TABLE,
+    URL_HISTORY_URL_ID, URL_ID, URL_INTERSTITIAL, URL_MAX_CLICKS, URL_PASSWORD_HASH, URL_TABLE,
+    URL_URL, URL_USER_ID, URL_UTM_CAMPAIGN, URL_UTM_CONTENT, URL_UTM_MEDIUM, URL_UTM_SOURCE,
+    URL_UTM_TERM, UrlChanges, UrlCursor, UrlFilter, UrlHistoryEntry, UrlSortField,
+};
 use async_trait::async_trait;
-use mockall::{automock, predicate::*};
-use sea_query::{Alias, Expr, PostgresQueryBuilder, Query};
-use sea_query_binder::SqlxBinder;
-use sqlx::{Pool, Postgres, query_as};
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use mockall::automock;
+use sea_query::extension::postgres::PgExpr;
+use sea_query::{
+    Alias, Cond, Expr, Func, LockType, Order, PostgresQueryBuilder, Query, SimpleExpr,
+};
+use sea_query_binder::{SqlxBinder, SqlxValues};
+use serde_json::{Map, Value, json};
+use sqlx::{Pool, Postgres, query_as_with, query_with};
 use uuid::Uuid;
 
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait UrlRepositoryTrait: Send + Sync {
-    async fn get_all_url(&self) -> Result<Vec<Url>, sqlx::Error>;
-    async fn add_url(&self, url: String, aliase: String, id: Uuid) -> Result<(), sqlx::Error>;
-    async fn get_url_by_hash(&self, id: String) -> Result<Option<Url>, sqlx::Error>;
-    async fn delete_url(&self, id: Uuid) -> Result<(), sqlx::Error>;
+    async fn list_urls(&self, filter: &UrlFilter) -> Result<Vec<Url>, sqlx::Error>;
+    async fn add_url(&self, new_url: &NewUrl, alias: String, id: Uuid) -> Result<Url, sqlx::Error>;
+    async fn add_urls(
+        &self,
+        rows: &[(NewUrl, String)],
+        user_id: Uuid,
+    ) -> Result<Vec<Result<Url, sqlx::Error>>, sqlx::Error>;
+    async fn get_url_by_owner(
+        &self,
+        user_id: Uuid,
+        url: String,
+    ) -> Result<Option<Url>, sqlx::Error>;
+    async fn get_url_by_hash(
+        &self,
+        domain_id: Option<Uuid>,
+        id: String,
+    ) -> Result<Option<Url>, sqlx::Error>;
+    async fn get_url_by_id(&self, id: Uuid) -> Result<Option<Url>, sqlx::Error>;
+    async fn register_click(
+        &self,
+        domain_id: Option<Uuid>,
+        alias: String,
+    ) -> Result<Option<Url>, sqlx::Error>;
+    async fn update_url(
+        &self,
+        id: Uuid,
+        changes: &UrlChanges,
+        changed_by: Uuid,
+    ) -> Result<Option<(Url, Url)>, sqlx::Error>;
+    async fn get_url_history(&self, url_id: Uuid) -> Result<Vec<UrlHistoryEntry>, sqlx::Error>;
+    async fn delete_url(&self, id: Uuid) -> Result<Option<Url>, sqlx::Error>;
+    async fn next_alias_sequence(&self) -> Result<i64, sqlx::Error>;
+    async fn delete_expired_urls(&self, limit: i64) -> Result<u64, sqlx::Error>;
+    async fn archive_expired_urls(&self, limit: i64) -> Result<u64, sqlx::Error>;
 }
 
 #[derive(Clone)]
@@ -26,30 +70,179 @@ impl UrlRepository {
     }
 }
 
+fn not_expired_condition() -> Cond {
+    Cond::all()
+        .add(Expr::col(URL_ENABLED).eq(true))
+        .add(
+            Cond::any()
+                .add(Expr::col(URL_EXPIRES_AT).is_null())
+                .add(Expr::col(URL_EXPIRES_AT).gt(Expr::current_timestamp())),
+        )
+        .add(
+            Cond::any()
+                .add(Expr::col(URL_MAX_CLICKS).is_null())
+                .add(Expr::col(URL_CLICKS).lt(Expr::col(URL_MAX_CLICKS))),
+        )
+}
+
+/// Aliases are unique per domain, so every alias lookup is scoped to one.
+fn domain_condition(domain_id: Option<Uuid>) -> SimpleExpr {
+    match domain_id {
+        Some(domain_id) => Expr::col(URL_DOMAIN_ID).eq(domain_id),
+        None => Expr::col(URL_DOMAIN_ID).is_null(),
+    }
+}
+
+fn insert_url_query(new_url: &NewUrl, alias: String, user_id: Uuid) -> (String, SqlxValues) {
+    Query::insert()
+        .into_table(Alias::new(URL_TABLE))
+        .columns([
+            Alias::new(URL_URL),
+            Alias::new(URL_ALIAS),
+            Alias::new(URL_USER_ID),
+            Alias::new(URL_EXPIRES_AT),
+            Alias::new(URL_MAX_CLICKS),
+            Alias::new(URL_PASSWORD_HASH),
+            Alias::new(URL_INTERSTITIAL),
+            Alias::new(URL_COUNTDOWN_SECONDS),
+            Alias::new(URL_DOMAIN_ID),
+            Alias::new(URL_UTM_SOURCE),
+            Alias::new(URL_UTM_MEDIUM),
+            Alias::new(URL_UTM_CAMPAIGN),
+            Alias::new(URL_UTM_TERM),
+            Alias::new(URL_UTM_CONTENT),
+            Alias::new(URL_FORWARD_QUERY),
+        ])
+        .values_panic([
+            new_url.url.clone().into(),
+            alias.into(),
+            user_id.into(),
+            new_url.expires_at.into(),
+            new_url.max_clicks.into(),
+            new_url.password.clone().into(),
+            new_url.interstitial.into(),
+            new_url.countdown_seconds.into(),
+            new_url.domain_id.into(),
+            new_url.utm_source.clone().into(),
+            new_url.utm_medium.clone().into(),
+            new_url.utm_campaign.clone().into(),
+            new_url.utm_term.clone().into(),
+            new_url.utm_content.clone().into(),
+            new_url.forward_query.into(),
+        ])
+        .returning(Query::returning().columns(URL_COLUMNS))
+        .build_sqlx(PostgresQueryBuilder)
+}
+
+/// Lowercased host of the destination URL.
+fn destination_host() -> SimpleExpr {
+    Func::lower(
+        Func::cust(Alias::new("substring"))
+            .arg(Expr::col(URL_URL))
+            .arg(DESTINATION_HOST_PATTERN),
+    )
+    .into()
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn cursor_value(cursor: &UrlCursor) -> Option<Expr> {
+    match cursor.sort {
+        UrlSortField::CreatedAt => serde_json::from_value::<DateTime<Utc>>(cursor.value.clone())
+            .ok()
+            .map(Expr::val),
+        UrlSortField::Alias => cursor
+            .value
+            .as_str()
+            .map(|alias| Expr::val(alias.to_string())),
+        UrlSortField::Clicks => cursor.value.as_i64().map(Expr::val),
+    }
+}
+
+const DESTINATION_HOST_PATTERN: &str = "^[A-Za-z][A-Za-z0-9+.-]*://([^/:?#@]+)";
+
+const EXPIRED_URL_IDS: &str = "SELECT id FROM url \
+     WHERE expires_at <= now() OR (max_clicks IS NOT NULL AND clicks >= max_clicks) \
+     LIMIT $1";
+
 #[async_trait]
 impl UrlRepositoryTrait for UrlRepository {
-    async fn get_all_url(&self) -> Result<Vec<Url>, sqlx::Error> {
-        let (sql, _) = Query::select()
-            .columns(["id", "alias", "url"])
-            .from("url")
-            .build(PostgresQueryBuilder);
-        let urls = query_as::<_, Url>(&sql)
+    async fn list_urls(&self, filter: &UrlFilter) -> Result<Vec<Url>, sqlx::Error> {
+        let mut query = Query::select();
+        query.columns(URL_COLUMNS).from(URL_TABLE);
+        if let Some(owner) = filter.owner {
+            query.and_where(Expr::col(URL_USER_ID).eq(owner));
+        }
+        if let Some(domain) = &filter.domain {
+            let subdomains = format!("%.{}", escape_like(domain));
+            query.cond_where(
+                Cond::any()
+                    .add(Expr::expr(destination_host()).eq(domain.clone()))
+                    .add(Expr::expr(destination_host()).like(subdomains)),
+            );
+        }
+        if let Some(from) = filter.created_from {
+            query.and_where(Expr::col(URL_CREATED_AT).gte(from));
+        }
+        if let Some(to) = filter.created_to {
+            query.and_where(Expr::col(URL_CREATED_AT).lt(to));
+        }
+        if let Some(search) = &filter.search {
+            let pattern = format!("%{}%", escape_like(search.trim()));
+            query.cond_where(
+                Cond::any()
+                    .add(Expr::col(URL_ALIAS).ilike(pattern.clone()))
+                    .add(Expr::col(URL_URL).ilike(pattern)),
+            );
+        }
+        if let Some(after) = &filter.after {
+            let value = cursor_value(after)
+                .ok_or_else(|| sqlx::Error::Decode("invalid cursor value".into()))?;
+            let left = Expr::tuple([
+                Expr::col(filter.sort.column()).into(),
+                Expr::col(URL_ID).into(),
+            ]);
+            let right = Expr::tuple([value.into(), Expr::val(after.id).into()]);
+            query.and_where(match filter.order {
+                SortOrder::Asc => left.gt(right),
+                SortOrder::Desc => left.lt(right),
+            });
+        }
+        let order = match filter.order {
+            SortOrder::Asc => Order::Asc,
+            SortOrder::Desc => Order::Desc,
+        };
+        let (sql, values) = query
+            .order_by(filter.sort.column(), order.clone())
+            .order_by(URL_ID, order)
+            .limit(filter.limit + 1)
+            .build_sqlx(PostgresQueryBuilder);
+        let urls = query_as_with::<_, Url, _>(&sql, values)
             .fetch_all(&self.primary_db)
             .await
             .map_err(|err| {
-                eprintln!("❌ Error fetching urls: {:?}", err);
+                eprintln!("❌ Error listing urls: {:?}", err);
                 err
             })?;
         Ok(urls)
     }
-    async fn get_url_by_hash(&self, id: String) -> Result<Option<Url>, sqlx::Error> {
-        let (sql, _) = Query::select()
-            .columns(["id", "alias", "url"])
-            .from("url")
-            .and_where(Expr::col("id").eq(Expr::val(id.to_string())))
+    async fn get_url_by_hash(
+        &self,
+        domain_id: Option<Uuid>,
+        id: String,
+    ) -> Result<Option<Url>, sqlx::Error> {
+        let (sql, values) = Query::select()
+            .columns(URL_COLUMNS)
+            .from(URL_TABLE)
+            .and_where(Expr::col(URL_ALIAS).eq(id))
+            .and_where(domain_condition(domain_id))
             .build_sqlx(PostgresQueryBuilder);
-        let url = query_as::<_, Url>(&sql)
-            .bind(id)
+        let url = query_as_with::<_, Url, _>(&sql, values)
             .fetch_optional(&self.primary_db)
             .await
             .map_err(|err| {
@@ -58,38 +251,304 @@ impl UrlRepositoryTrait for UrlRepository {
             })?;
         Ok(url)
     }
-    async fn add_url(&self, url: String, aliase: String, user_id: Uuid) -> Result<(), sqlx::Error> {
+    async fn get_url_by_owner(
+        &self,
+        user_id: Uuid,
+        url: String,
+    ) -> Result<Option<Url>, sqlx::Error> {
+        let (sql, values) = Query::select()
+            .columns(URL_COLUMNS)
+            .from(URL_TABLE)
+            .and_where(Expr::col(URL_USER_ID).eq(user_id))
+            .and_where(Expr::col(URL_URL).eq(url))
+            .build_sqlx(PostgresQueryBuilder);
+        let url = query_as_with::<_, Url, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching url by owner: {:?}", err);
+                err
+            })?;
+        Ok(url)
+    }
+    async fn get_url_by_id(&self, id: Uuid) -> Result<Option<Url>, sqlx::Error> {
+        let (sql, values) = Query::select()
+            .columns(URL_COLUMNS)
+            .from(URL_TABLE)
+            .and_where(Expr::col(URL_ID).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+        let url = query_as_with::<_, Url, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching url by id: {:?}", err);
+                err
+            })?;
+        Ok(url)
+    }
+    async fn register_click(
+        &self,
+        domain_id: Option<Uuid>,
+        alias: String,
+    ) -> Result<Option<Url>, sqlx::Error> {
+        let (sql, values) = Query::update()
+            .table(URL_TABLE)
+            .value(URL_CLICKS, Expr::col(URL_CLICKS).add(1))
+            .and_where(Expr::col(URL_ALIAS).eq(alias))
+            .and_where(domain_condition(domain_id))
+            .cond_where(not_expired_condition())
+            .returning(Query::returning().columns(URL_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let url = query_as_with::<_, Url, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error registering click: {:?}", err);
+                err
+            })?;
+        Ok(url)
+    }
+    async fn add_url(
+        &self,
+        new_url: &NewUrl,
+        alias: String,
+        user_id: Uuid,
+    ) -> Result<Url, sqlx::Error> {
+        let (sql, values) = insert_url_query(new_url, alias, user_id);
+
+        let url = query_as_with::<_, Url, _>(&sql, values)
+            .fetch_one(&self.primary_db)
+            .await?;
+
+        Ok(url)
+    }
+    /// Inserts the rows in one transaction. Every row runs under its own
+    /// savepoint, so a conflicting row is reported without aborting the rest.
+    async fn add_urls(
+        &self,
+        rows: &[(NewUrl, String)],
+        user_id: Uuid,
+    ) -> Result<Vec<Result<Url, sqlx::Error>>, sqlx::Error> {
+        let mut tx = self.primary_db.begin().await?;
+        let mut results = Vec::with_capacity(rows.len());
+        for (new_url, alias) in rows {
+            let (sql, values) = insert_url_query(new_url, alias.clone(), user_id);
+            sqlx::query("SAVEPOINT bulk_row").execute(&mut *tx).await?;
+            match query_as_with::<_, Url, _>(&sql, values)
+                .fetch_one(&mut *tx)
+                .await
+            {
+                Ok(url) => {
+                    sqlx::query("RELEASE SAVEPOINT bulk_row")
+                        .execute(&mut *tx)
+                        .await?;
+                    results.push(Ok(url));
+                }
+                Err(err) => {
+                    sqlx::query("ROLLBACK TO SAVEPOINT bulk_row")
+                        .execute(&mut *tx)
+                        .await?;
+                    results.push(Err(err));
+                }
+            }
+        }
+        tx.commit().await.map_err(|err| {
+            eprintln!("❌ Error committing bulk insert: {:?}", err);
+            err
+        })?;
+        Ok(results)
+    }
+    async fn update_url(
+        &self,
+        id: Uuid,
+        changes: &UrlChanges,
+        changed_by: Uuid,
+    ) -> Result<Option<(Url, Url)>, sqlx::Error> {
+        let mut tx = self.primary_db.begin().await?;
+
+        let (sql, values) = Query::select()
+            .columns(URL_COLUMNS)
+            .from(URL_TABLE)
+            .and_where(Expr::col(URL_ID).eq(id))
+            .lock(LockType::Update)
+            .build_sqlx(PostgresQueryBuilder);
+        let Some(old) = query_as_with::<_, Url, _>(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut update = Query::update();
+        update.table(URL_TABLE).and_where(Expr::col(URL_ID).eq(id));
+        let mut diff = Map::new();
+        if let Some(url) = changes.url.as_ref().filter(|url| **url != old.url) {
+            update.value(URL_URL, url.clone());
+            diff.insert(URL_URL.into(), json!({ "old": old.url, "new": url }));
+        }
+        if let Some(alias) = changes.alias.as_ref().filter(|alias| **alias != old.alias) {
+            update.value(URL_ALIAS, alias.clone());
+            diff.insert(URL_ALIAS.into(), json!({ "old": old.alias, "new": alias }));
+        }
+        if let Some(expires_at) = changes.expires_at.filter(|at| *at != old.expires_at) {
+            update.value(URL_EXPIRES_AT, expires_at);
+            diff.insert(
+                URL_EXPIRES_AT.into(),
+                json!({ "old": old.expires_at, "new": expires_at }),
+            );
+        }
+        if let Some(enabled) = changes.enabled.filter(|enabled| *enabled != old.enabled) {
+            update.value(URL_ENABLED, enabled);
+            diff.insert(
+                URL_ENABLED.into(),
+                json!({ "old": old.enabled, "new": enabled }),
+            );
+        }
+        if let Some(interstitial) = changes
+            .interstitial
+            .filter(|interstitial| *interstitial != old.interstitial)
+        {
+            update.value(URL_INTERSTITIAL, interstitial);
+            diff.insert(
+                URL_INTERSTITIAL.into(),
+                json!({ "old": old.interstitial, "new": interstitial }),
+            );
+        }
+        if let Some(countdown) = changes
+            .countdown_seconds
+            .filter(|countdown| *countdown != old.countdown_seconds)
+        {
+            update.value(URL_COUNTDOWN_SECONDS, countdown);
+            diff.insert(
+                URL_COUNTDOWN_SECONDS.into(),
+                json!({ "old": old.countdown_seconds, "new": countdown }),
+            );
+        }
+        for (column, new, old) in [
+            (URL_UTM_SOURCE, &changes.utm_source, &old.utm_source),
+            (URL_UTM_MEDIUM, &changes.utm_medium, &old.utm_medium),
+            (URL_UTM_CAMPAIGN, &changes.utm_campaign, &old.utm_campaign),
+            (URL_UTM_TERM, &changes.utm_term, &old.utm_term),
+            (URL_UTM_CONTENT, &changes.utm_content, &old.utm_content),
+        ] {
+            if let Some(value) = new.as_ref().filter(|value| *value != old) {
+                update.value(column, value.clone());
+                diff.insert(column.into(), json!({ "old": old, "new": value }));
+            }
+        }
+        if let Some(forward_query) = changes
+            .forward_query
+            .filter(|forward_query| *forward_query != old.forward_query)
+        {
+            update.value(URL_FORWARD_QUERY, forward_query);
+            diff.insert(
+                URL_FORWARD_QUERY.into(),
+                json!({ "old": old.forward_query, "new": forward_query }),
+            );
+        }
+        // Only whether the link is protected goes into the history, never the hash.
+        if let Some(password_hash) = changes
+            .password
+            .as_ref()
+            .filter(|hash| hash.is_some() || old.is_protected())
+        {
+            update.value(URL_PASSWORD_HASH, password_hash.clone());
+            diff.insert(
+                "password".into(),
+                json!({ "old": old.is_protected(), "new": password_hash.is_some() }),
+            );
+        }
+        if diff.is_empty() {
+            tx.commit().await?;
+            return Ok(Some((old.clone(), old)));
+        }
+
+        let (sql, values) = update
+            .returning(Query::returning().columns(URL_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let new = query_as_with::<_, Url, _>(&sql, values)
+            .fetch_one(&mut *tx)
+            .await?;
+
         let (sql, values) = Query::insert()
-            .into_table(Alias::new("url"))
+            .into_table(Alias::new(URL_HISTORY_TABLE))
             .columns([
-                Alias::new("url"),
-                Alias::new("alias"),
-                Alias::new("user_id"),
+                Alias::new(URL_HISTORY_URL_ID),
+                Alias::new(URL_HISTORY_CHANGED_BY),
+                Alias::new(URL_HISTORY_CHANGES),
             ])
-            .values_panic([
-                url.clone().into(),
-                aliase.clone().into(),
-                user_id.to_string().into(),
+            .values_panic([id.into(), changed_by.into(), Value::Object(diff).into()])
+            .build_sqlx(PostgresQueryBuilder);
+        query_with(&sql, values).execute(&mut *tx).await?;
+
+        tx.commit().await?;
+        Ok(Some((old, new)))
+    }
+    async fn get_url_history(&self, url_id: Uuid) -> Result<Vec<UrlHistoryEntry>, sqlx::Error> {
+        let (sql, values) = Query::select()
+            .columns([
+                URL_HISTORY_ID,
+                URL_HISTORY_URL_ID,
+                URL_HISTORY_CHANGED_BY,
+                URL_HISTORY_CHANGED_AT,
+                URL_HISTORY_CHANGES,
             ])
-            .build(PostgresQueryBuilder);
+            .from(URL_HISTORY_TABLE)
+            .and_where(Expr::col(URL_HISTORY_URL_ID).eq(url_id))
+            .order_by(URL_HISTORY_CHANGED_AT, Order::Desc)
+            .build_sqlx(PostgresQueryBuilder);
+        let history = query_as_with::<_, UrlHistoryEntry, _>(&sql, values)
+            .fetch_all(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching url history: {:?}", err);
+                err
+            })?;
+        Ok(history)
+    }
+    async fn delete_url(&self, id: Uuid) -> Result<Option<Url>, sqlx::Error> {
+        let (sql, values) = Query::delete()
+            .from_table(URL_TABLE)
+            .and_where(Expr::col(URL_ID).eq(id))
+            .returning(Query::returning().columns(URL_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
 
-        println!("sql string value {:?}", values);
-        sqlx::query(&sql)
-            .bind(&url)
-            .bind(&aliase)
-            .bind(&user_id)
+        let url = query_as_with::<_, Url, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await?;
+        Ok(url)
+    }
+    async fn next_alias_sequence(&self) -> Result<i64, sqlx::Error> {
+        let (sql, values) = Query::select()
+            .expr(Func::cust(Alias::new("nextval")).arg("url_alias_seq"))
+            .build_sqlx(PostgresQueryBuilder);
+        let (seq,): (i64,) = query_as_with(&sql, values)
+            .fetch_one(&self.primary_db)
+            .await?;
+        Ok(seq)
+    }
+    async fn delete_expired_urls(&self, limit: i64) -> Result<u64, sqlx::Error> {
+        let sql = format!("DELETE FROM url WHERE id IN ({})", EXPIRED_URL_IDS);
+        let result = sqlx::query(&sql)
+            .bind(limit)
             .execute(&self.primary_db)
             .await?;
-
-        Ok(())
+        Ok(result.rows_affected())
     }
-    async fn delete_url(&self, id: Uuid) -> Result<(), sqlx::Error> {
-        let (sql, _) = Query::delete()
-            .from_table("url")
-            .and_where(Expr::col("id").eq(Expr::val(id.to_string())))
-            .build(PostgresQueryBuilder);
-
-        sqlx::query(&sql).bind(id).execute(&self.primary_db).await?;
-        Ok(())
+    /// The whole row is kept in `data`, so columns added to `url` later are
+    /// archived without touching this query.
+    async fn archive_expired_urls(&self, limit: i64) -> Result<u64, sqlx::Error> {
+        let sql = format!(
+            "WITH moved AS (DELETE FROM url AS u WHERE id IN ({}) \
+             RETURNING id, alias, url, user_id, created_at, expires_at, max_clicks, clicks, to_jsonb(u) AS data) \
+             INSERT INTO url_archive (id, alias, url, user_id, created_at, expires_at, max_clicks, clicks, data) \
+             SELECT id, alias, url, user_id, created_at, expires_at, max_clicks, clicks, data FROM moved",
+            EXPIRED_URL_IDS
+        );
+        let result = sqlx::query(&sql)
+            .bind(limit)
+            .execute(&self.primary_db)
+            .await?;
+        Ok(result.rows_affected())
     }
 }
@@ -0,0 +1,110 @@
+use crate::feature::url::entity::{UrlError, UrlRejection};
+use crate::feature::url::policy::PolicyViolation;
+use crate::feature::url::service::{UrlService, UrlServiceTrait};
+use crate::metrics::PrometheusMetrics;
+use crate::servers::http::middleware::UserJWT;
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::fmt::Display;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Ownership checks and error responses shared by the handlers of resources
+/// that belong to a short link, such as redirect rules and A/B variants.
+pub struct LinkResourceHandler {
+    url_service: Arc<UrlService>,
+    metrics: Arc<PrometheusMetrics>,
+    /// Handler label used in the error metrics and logs.
+    name: &'static str,
+    not_found_message: &'static str,
+}
+
+impl LinkResourceHandler {
+    pub fn new(
+        url_service: Arc<UrlService>,
+        metrics: Arc<PrometheusMetrics>,
+        name: &'static str,
+        not_found_message: &'static str,
+    ) -> Self {
+        Self {
+            url_service,
+            metrics,
+            name,
+            not_found_message,
+        }
+    }
+
+    /// The resources of a link can only be managed by its owner or an admin.
+    pub async fn check_owner(&self, url_id: Uuid, user_jwt: &UserJWT) -> Result<(), Response> {
+        match self
+            .url_service
+            .get_owned_url(url_id, user_jwt.id, user_jwt.is_admin())
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(UrlError::NotFound) => {
+                Err((StatusCode::NOT_FOUND, Json("URL not found".to_string())).into_response())
+            }
+            Err(UrlError::Forbidden) => {
+                self.metrics.inc_errors("authorization_error", self.name);
+                Err((StatusCode::FORBIDDEN, Json("Forbidden".to_string())).into_response())
+            }
+            Err(err) => {
+                eprintln!("❌ {} error: {}", self.name, err);
+                self.metrics.inc_errors("database_error", self.name);
+                Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json("Error retrieving URL".to_string()),
+                )
+                    .into_response())
+            }
+        }
+    }
+
+    pub fn validation_error(&self, payload: &impl Validate) -> Option<Response> {
+        let validation_errors = payload.validate().err()?;
+        self.metrics.inc_errors("validation_error", self.name);
+        Some(
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(format!("Validation error: {:?}", validation_errors)),
+            )
+                .into_response(),
+        )
+    }
+
+    pub fn not_found(&self) -> Response {
+        self.metrics.inc_errors("not_found", self.name);
+        (
+            StatusCode::NOT_FOUND,
+            Json(self.not_found_message.to_string()),
+        )
+            .into_response()
+    }
+
+    pub fn policy_violation(&self, violations: Vec<PolicyViolation>) -> Response {
+        self.metrics.inc_errors("policy_violation", self.name);
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(UrlRejection {
+                error: "URL rejected by policy".to_string(),
+                violations,
+            }),
+        )
+            .into_response()
+    }
+
+    pub fn internal_error(&self, err: impl Display) -> Response {
+        eprintln!("❌ {} error: {}", self.name, err);
+        self.metrics.inc_errors("database_error", self.name);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json("Internal server error".to_string()),
+        )
+            .into_response()
+    }
+}
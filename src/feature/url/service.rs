@@ -1,46 +1,677 @@
+use crate::app::config::{BulkConfig, Config, DomainsConfig, DuplicateMode, UrlConfig};
 use crate::domain::url::Url;
+use crate::feature::auth::password::{generate_hash_password, verify_password_hash};
+use crate::feature::blocklist::entity::normalize_domain;
+use crate::feature::custom_domain::repository::{DomainRepository, DomainRepositoryTrait};
+use crate::feature::url::bulk::check_row_limit;
+use crate::feature::url::entity::{
+    BulkRow, BulkUrlResult, NewUrl, ResolvedUrl, ShortenedUrl, UrlChanges, UrlCursor, UrlError,
+    UrlFilter, UrlHistoryEntry, UrlPage,
+};
+use crate::feature::url::generator::AliasGenerator;
+use crate::feature::url::policy::UrlPolicyChain;
 use crate::feature::url::repository::{UrlRepository, UrlRepositoryTrait};
-use crate::utils::random::new_random_string;
+use crate::metrics::PrometheusMetrics;
+use crate::utils::cache::Cache;
+use crate::utils::constants::{
+    URL_ALIAS_UNIQUE_CONSTRAINT, URL_DEFAULT_ALIAS_UNIQUE_INDEX, URL_OWNER_UNIQUE_CONSTRAINT,
+};
 use async_trait::async_trait;
+#[cfg(test)]
 use mockall::automock;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
 #[cfg_attr(test, automock)]
 #[async_trait]
 pub trait UrlServiceTrait: Send + Sync {
-    async fn get_all_url(&self) -> Result<Vec<Url>, sqlx::Error>;
-    async fn create_url(&self, url: String, id: Uuid) -> Result<(), sqlx::Error>;
-    async fn get_url_by_hash(&self, id: String) -> Result<Option<Url>, sqlx::Error>;
-    async fn delete_url(&self, id: Uuid) -> Result<(), sqlx::Error>;
+    async fn list_urls(&self, filter: UrlFilter) -> Result<UrlPage, UrlError>;
+    async fn create_url(&self, new_url: NewUrl, id: Uuid) -> Result<ShortenedUrl, UrlError>;
+    async fn create_urls(
+        &self,
+        rows: Vec<BulkRow>,
+        user_id: Uuid,
+    ) -> Result<Vec<BulkUrlResult>, UrlError>;
+    async fn get_url_by_hash(
+        &self,
+        domain_id: Option<Uuid>,
+        id: String,
+    ) -> Result<Option<Url>, sqlx::Error>;
+    async fn get_owned_url(&self, id: Uuid, user_id: Uuid, is_admin: bool)
+    -> Result<Url, UrlError>;
+    async fn follow_url(
+        &self,
+        host: Option<String>,
+        alias: String,
+        password: Option<String>,
+    ) -> Result<ResolvedUrl, UrlError>;
+    async fn preview_url(&self, host: Option<String>, alias: String) -> Result<Url, UrlError>;
+    async fn get_short_url(
+        &self,
+        domain: Option<String>,
+        alias: String,
+    ) -> Result<ShortenedUrl, UrlError>;
+    async fn update_url(
+        &self,
+        id: Uuid,
+        changes: UrlChanges,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Url, UrlError>;
+    async fn get_url_history(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Vec<UrlHistoryEntry>, UrlError>;
+    async fn delete_url(&self, id: Uuid, user_id: Uuid, is_admin: bool) -> Result<(), UrlError>;
 }
+
+/// Cache key of a link; links on custom domains are prefixed with the domain
+/// id since the same alias may exist on several domains.
+fn url_cache_key(domain_id: Option<Uuid>, alias: &str) -> String {
+    match domain_id {
+        Some(domain_id) => format!("{}/{}", domain_id, alias),
+        None => alias.to_string(),
+    }
+}
+
+fn map_conflict(err: sqlx::Error) -> UrlError {
+    match &err {
+        sqlx::Error::Database(db_err)
+            if matches!(
+                db_err.constraint(),
+                Some(URL_ALIAS_UNIQUE_CONSTRAINT | URL_DEFAULT_ALIAS_UNIQUE_INDEX)
+            ) =>
+        {
+            UrlError::AliasAlreadyExists
+        }
+        sqlx::Error::Database(db_err)
+            if db_err.constraint() == Some(URL_OWNER_UNIQUE_CONSTRAINT) =>
+        {
+            UrlError::UrlAlreadyExists
+        }
+        _ => UrlError::Db(err),
+    }
+}
+async fn hash_password(password: Option<String>) -> Result<Option<String>, UrlError> {
+    match password {
+        Some(password) => generate_hash_password(password)
+            .await
+            .map(Some)
+            .map_err(|_| UrlError::PasswordHashFailed),
+        None => Ok(None),
+    }
+}
+
+fn check_password(url: &Url, password: Option<&str>) -> Result<(), UrlError> {
+    match (&url.password_hash, password) {
+        (None, _) => Ok(()),
+        (Some(_), None) => Err(UrlError::PasswordRequired),
+        (Some(hash), Some(password)) if verify_password_hash(password, hash) => Ok(()),
+        (Some(_), Some(_)) => Err(UrlError::InvalidPassword),
+    }
+}
+
 #[derive(Clone)]
 pub struct UrlService {
     url_repository: Arc<UrlRepository>,
+    alias_generator: Arc<AliasGenerator>,
+    url_cache: Arc<dyn Cache<Url>>,
+    metrics: Arc<PrometheusMetrics>,
+    bulk: BulkConfig,
+    url_config: UrlConfig,
+    url_policy: Arc<UrlPolicyChain>,
+    domain_repository: Arc<DomainRepository>,
+    domains: DomainsConfig,
+    primary_host: Option<String>,
 }
 
 impl UrlService {
-    pub fn new(url_repository: Arc<UrlRepository>) -> Self {
-        Self { url_repository }
+    pub fn new(
+        url_repository: Arc<UrlRepository>,
+        alias_generator: Arc<AliasGenerator>,
+        url_cache: Arc<dyn Cache<Url>>,
+        metrics: Arc<PrometheusMetrics>,
+        url_policy: Arc<UrlPolicyChain>,
+        domain_repository: Arc<DomainRepository>,
+        config: &Config,
+    ) -> Self {
+        let primary_host = url::Url::parse(&config.url.public_base_url)
+            .ok()
+            .and_then(|base| base.host_str().map(normalize_domain));
+        Self {
+            url_repository,
+            alias_generator,
+            url_cache,
+            metrics,
+            bulk: config.bulk.clone(),
+            url_config: config.url.clone(),
+            url_policy,
+            domain_repository,
+            domains: config.domains.clone(),
+            primary_host,
+        }
+    }
+
+    async fn check_policy(&self, url: &str) -> Result<(), UrlError> {
+        self.url_policy
+            .check(url)
+            .await
+            .map_err(UrlError::PolicyViolation)
+    }
+
+    /// Binds the link to the requested custom domain, which must be verified
+    /// and owned by the creator. Returns the domain host.
+    async fn resolve_domain(
+        &self,
+        new_url: &mut NewUrl,
+        user_id: Uuid,
+    ) -> Result<Option<String>, UrlError> {
+        let Some(host) = new_url.domain.as_deref().map(normalize_domain) else {
+            return Ok(None);
+        };
+        match self
+            .domain_repository
+            .get_verified_domain_by_host(host.clone())
+            .await?
+        {
+            Some(domain) if domain.owner_id == user_id && domain.is_verified() => {
+                new_url.domain_id = Some(domain.id);
+                Ok(Some(domain.host))
+            }
+            _ => Err(UrlError::DomainNotAvailable(host)),
+        }
+    }
+
+    /// Maps the request host onto the domain whose aliases it serves. Unknown
+    /// or unverified hosts fall back to the shortener's own aliases.
+    async fn resolve_host(&self, host: Option<String>) -> Result<Option<Uuid>, UrlError> {
+        let Some(host) = host.map(|host| normalize_domain(&host)) else {
+            return Ok(None);
+        };
+        if self.primary_host.as_ref() == Some(&host) {
+            return Ok(None);
+        }
+        Ok(self
+            .domain_repository
+            .get_verified_domain_by_host(host)
+            .await?
+            .filter(|domain| domain.is_verified())
+            .map(|domain| domain.id))
+    }
+
+    async fn domain_host(&self, domain_id: Option<Uuid>) -> Result<Option<String>, UrlError> {
+        match domain_id {
+            Some(domain_id) => Ok(self
+                .domain_repository
+                .get_domain_by_id(domain_id)
+                .await?
+                .map(|domain| domain.host)),
+            None => Ok(None),
+        }
+    }
+
+    fn shorten(&self, url: Url, host: Option<&str>) -> ShortenedUrl {
+        let base = match host {
+            Some(host) => format!("{}://{}", self.domains.link_scheme, host),
+            None => self
+                .url_config
+                .public_base_url
+                .trim_end_matches('/')
+                .to_string(),
+        };
+        let short_url = format!("{}/{}", base, url.alias);
+        ShortenedUrl { url, short_url }
+    }
+
+    async fn insert_url(&self, new_url: &NewUrl, alias: String, id: Uuid) -> Result<Url, UrlError> {
+        let url = self
+            .url_repository
+            .add_url(new_url, alias, id)
+            .await
+            .map_err(map_conflict)?;
+        self.url_cache
+            .invalidate(&url_cache_key(url.domain_id, &url.alias))
+            .await;
+        Ok(url)
+    }
+
+    /// In `reuse` mode the owner's existing link is returned, unless a
+    /// different custom alias was requested for it.
+    async fn resolve_duplicate(&self, new_url: &NewUrl, user_id: Uuid) -> Result<Url, UrlError> {
+        if self.url_config.duplicates != DuplicateMode::Reuse {
+            return Err(UrlError::UrlAlreadyExists);
+        }
+        match self
+            .url_repository
+            .get_url_by_owner(user_id, new_url.url.clone())
+            .await?
+        {
+            Some(existing)
+                if new_url
+                    .alias
+                    .as_ref()
+                    .is_none_or(|alias| *alias == existing.alias) =>
+            {
+                Ok(existing)
+            }
+            _ => Err(UrlError::UrlAlreadyExists),
+        }
+    }
+
+    async fn insert_with_generated_alias(
+        &self,
+        new_url: &NewUrl,
+        id: Uuid,
+    ) -> Result<Url, UrlError> {
+        let mut length = self.alias_generator.initial_length();
+        let mut collisions = 0;
+        for _ in 0..self.alias_generator.max_attempts() {
+            let alias = self.alias_generator.generate(length).await?;
+            match self.insert_url(new_url, alias, id).await {
+                Err(UrlError::AliasAlreadyExists) => {
+                    collisions += 1;
+                    if collisions % self.alias_generator.grow_after() == 0 {
+                        length += 1;
+                    }
+                }
+                result => return result,
+            }
+        }
+        eprintln!(
+            "❌ Could not generate a free alias after {} collisions",
+            collisions
+        );
+        Err(UrlError::AliasGenerationFailed)
+    }
+
+    /// Inserts one chunk of valid rows. Rows whose generated alias collided
+    /// are retried one by one through the regular generator loop.
+    async fn insert_chunk(
+        &self,
+        chunk: Vec<(usize, NewUrl)>,
+        user_id: Uuid,
+    ) -> Result<Vec<(usize, Result<Url, UrlError>)>, UrlError> {
+        let mut rows = Vec::with_capacity(chunk.len());
+        for (_, new_url) in &chunk {
+            let alias = match new_url.alias.clone() {
+                Some(alias) => alias,
+                None => {
+                    self.alias_generator
+                        .generate(self.alias_generator.initial_length())
+                        .await?
+                }
+            };
+            rows.push((new_url.clone(), alias));
+        }
+        let inserted = self.url_repository.add_urls(&rows, user_id).await?;
+
+        let mut results = Vec::with_capacity(chunk.len());
+        for ((index, new_url), ((_, alias), result)) in
+            chunk.into_iter().zip(rows.into_iter().zip(inserted))
+        {
+            let result = match result.map_err(map_conflict) {
+                Ok(url) => {
+                    self.url_cache
+                        .invalidate(&url_cache_key(url.domain_id, &alias))
+                        .await;
+                    Ok(url)
+                }
+                Err(UrlError::AliasAlreadyExists) if new_url.alias.is_none() => {
+                    self.insert_with_generated_alias(&new_url, user_id).await
+                }
+                Err(UrlError::UrlAlreadyExists) => self.resolve_duplicate(&new_url, user_id).await,
+                Err(err) => Err(err),
+            };
+            results.push((index, result));
+        }
+        Ok(results)
+    }
+
+    /// Links with a click limit are always counted atomically in the database,
+    /// so they bypass the cache.
+    async fn follow_limited_url(
+        &self,
+        domain_id: Option<Uuid>,
+        alias: String,
+    ) -> Result<ResolvedUrl, UrlError> {
+        match self.url_repository.register_click(domain_id, alias).await? {
+            Some(url) => Ok(ResolvedUrl {
+                url,
+                click_counted: true,
+            }),
+            None => Err(UrlError::Expired),
+        }
     }
 }
 
 #[async_trait]
 impl UrlServiceTrait for UrlService {
-    async fn get_all_url(&self) -> Result<Vec<Url>, sqlx::Error> {
-        self.url_repository.get_all_url().await
+    async fn list_urls(&self, filter: UrlFilter) -> Result<UrlPage, UrlError> {
+        let mut items = self.url_repository.list_urls(&filter).await?;
+        let next_cursor = if items.len() as u64 > filter.limit {
+            items.truncate(filter.limit as usize);
+            items
+                .last()
+                .map(|url| UrlCursor::from_url(url, &filter).encode())
+        } else {
+            None
+        };
+        Ok(UrlPage { items, next_cursor })
+    }
+    async fn create_url(&self, mut new_url: NewUrl, id: Uuid) -> Result<ShortenedUrl, UrlError> {
+        self.check_policy(&new_url.url).await?;
+        let host = self.resolve_domain(&mut new_url, id).await?;
+        new_url.password = hash_password(new_url.password.take()).await?;
+        let result = match new_url.alias.clone() {
+            Some(alias) => self.insert_url(&new_url, alias, id).await,
+            None => self.insert_with_generated_alias(&new_url, id).await,
+        };
+        let url = match result {
+            Err(UrlError::UrlAlreadyExists) => self.resolve_duplicate(&new_url, id).await?,
+            result => result?,
+        };
+        // A reused link may live on another domain than the one requested.
+        let host = if url.domain_id == new_url.domain_id {
+            host
+        } else {
+            self.domain_host(url.domain_id).await?
+        };
+        Ok(self.shorten(url, host.as_deref()))
+    }
+    async fn create_urls(
+        &self,
+        rows: Vec<BulkRow>,
+        user_id: Uuid,
+    ) -> Result<Vec<BulkUrlResult>, UrlError> {
+        check_row_limit(&rows, self.bulk.max_rows)?;
+        let mut results = Vec::with_capacity(rows.len());
+        let mut valid = Vec::new();
+        let mut hosts: HashMap<Uuid, Option<String>> = HashMap::new();
+        for (index, row) in rows.into_iter().enumerate() {
+            match row {
+                Ok(mut new_url) => {
+                    let prepared = match self.check_policy(&new_url.url).await {
+                        Ok(()) => match self.resolve_domain(&mut new_url, user_id).await {
+                            Ok(host) => {
+                                if let Some(domain_id) = new_url.domain_id {
+                                    hosts.insert(domain_id, host);
+                                }
+                                hash_password(new_url.password.take()).await
+                            }
+                            Err(err) => Err(err),
+                        },
+                        Err(err) => Err(err),
+                    };
+                    match prepared {
+                        Ok(password_hash) => {
+                            new_url.password = password_hash;
+                            valid.push((index, new_url));
+                        }
+                        Err(err) => results.push(BulkUrlResult::failed(index, err.to_string())),
+                    }
+                }
+                Err(error) => results.push(BulkUrlResult::failed(index, error)),
+            }
+        }
+
+        let chunk_size = self.bulk.chunk_size.max(1);
+        let mut valid = valid.into_iter().peekable();
+        while valid.peek().is_some() {
+            let chunk: Vec<_> = valid.by_ref().take(chunk_size).collect();
+            for (index, result) in self.insert_chunk(chunk, user_id).await? {
+                results.push(match result {
+                    Ok(url) => {
+                        let host = match url.domain_id {
+                            Some(domain_id) => match hosts.get(&domain_id) {
+                                Some(host) => host.clone(),
+                                None => {
+                                    let host = self.domain_host(Some(domain_id)).await?;
+                                    hosts.insert(domain_id, host.clone());
+                                    host
+                                }
+                            },
+                            None => None,
+                        };
+                        let shortened = self.shorten(url, host.as_deref());
+                        BulkUrlResult {
+                            index,
+                            alias: Some(shortened.url.alias),
+                            short_url: Some(shortened.short_url),
+                            error: None,
+                        }
+                    }
+                    Err(UrlError::Db(err)) => {
+                        eprintln!("❌ Error saving bulk row {}: {}", index, err);
+                        BulkUrlResult::failed(index, "Error while saving".to_string())
+                    }
+                    Err(err) => BulkUrlResult::failed(index, err.to_string()),
+                });
+            }
+        }
+        results.sort_by_key(|row| row.index);
+        Ok(results)
     }
-    async fn create_url(&self, url: String, id: Uuid) -> Result<(), sqlx::Error> {
-        let alias = match new_random_string(6).await {
-            Ok(a) => a,
-            Err(_) => return Err(sqlx::Error::Protocol("random string error".into())),
+    async fn get_url_by_hash(
+        &self,
+        domain_id: Option<Uuid>,
+        id: String,
+    ) -> Result<Option<Url>, sqlx::Error> {
+        self.url_repository.get_url_by_hash(domain_id, id).await
+    }
+    async fn get_owned_url(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Url, UrlError> {
+        match self.url_repository.get_url_by_id(id).await? {
+            Some(url) if url.user_id == user_id || is_admin => Ok(url),
+            Some(_) => Err(UrlError::Forbidden),
+            None => Err(UrlError::NotFound),
+        }
+    }
+    async fn follow_url(
+        &self,
+        host: Option<String>,
+        alias: String,
+        password: Option<String>,
+    ) -> Result<ResolvedUrl, UrlError> {
+        let domain_id = self.resolve_host(host).await?;
+        let cache_key = url_cache_key(domain_id, &alias);
+        let url = match self.url_cache.get(&cache_key).await {
+            Some(url) => {
+                self.metrics.inc_url_cache_hit();
+                url
+            }
+            None => {
+                self.metrics.inc_url_cache_miss();
+                match self.get_url_by_hash(domain_id, alias.clone()).await? {
+                    Some(url) => {
+                        // Limited and protected links are always read from the database.
+                        if !url.is_expired() && url.max_clicks.is_none() && !url.is_protected() {
+                            self.url_cache.set(&cache_key, &url).await;
+                        }
+                        url
+                    }
+                    None => return Err(UrlError::NotFound),
+                }
+            }
         };
-        self.url_repository.add_url(url, alias, id).await
+        if !url.enabled {
+            return Err(UrlError::NotFound);
+        }
+        if url.is_expired() {
+            self.url_cache.invalidate(&cache_key).await;
+            return Err(UrlError::Expired);
+        }
+        check_password(&url, password.as_deref())?;
+        if url.max_clicks.is_some() {
+            return self.follow_limited_url(domain_id, alias).await;
+        }
+        Ok(ResolvedUrl {
+            url,
+            click_counted: false,
+        })
     }
-    async fn get_url_by_hash(&self, id: String) -> Result<Option<Url>, sqlx::Error> {
-        self.url_repository.get_url_by_hash(id).await
+    async fn preview_url(&self, host: Option<String>, alias: String) -> Result<Url, UrlError> {
+        let domain_id = self.resolve_host(host).await?;
+        match self
+            .url_repository
+            .get_url_by_hash(domain_id, alias)
+            .await?
+        {
+            Some(url) if !url.enabled => Err(UrlError::NotFound),
+            Some(url) if url.is_expired() => Err(UrlError::Expired),
+            Some(url) => Ok(url),
+            None => Err(UrlError::NotFound),
+        }
     }
-    async fn delete_url(&self, id: Uuid) -> Result<(), sqlx::Error> {
-        self.url_repository.delete_url(id).await
+    async fn get_short_url(
+        &self,
+        domain: Option<String>,
+        alias: String,
+    ) -> Result<ShortenedUrl, UrlError> {
+        let domain = match domain {
+            Some(host) => Some(
+                self.domain_repository
+                    .get_verified_domain_by_host(normalize_domain(&host))
+                    .await?
+                    .filter(|domain| domain.is_verified())
+                    .ok_or(UrlError::NotFound)?,
+            ),
+            None => None,
+        };
+        let url = match self
+            .url_repository
+            .get_url_by_hash(domain.as_ref().map(|domain| domain.id), alias)
+            .await?
+        {
+            Some(url) if !url.enabled => return Err(UrlError::NotFound),
+            Some(url) if url.is_expired() => return Err(UrlError::Expired),
+            Some(url) => url,
+            None => return Err(UrlError::NotFound),
+        };
+        Ok(self.shorten(url, domain.as_ref().map(|domain| domain.host.as_str())))
+    }
+    async fn update_url(
+        &self,
+        id: Uuid,
+        mut changes: UrlChanges,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Url, UrlError> {
+        self.get_owned_url(id, user_id, is_admin).await?;
+        if let Some(url) = &changes.url {
+            self.check_policy(url).await?;
+        }
+        if let Some(password) = changes.password.take() {
+            changes.password = Some(hash_password(password).await?);
+        }
+        let (old, new) = self
+            .url_repository
+            .update_url(id, &changes, user_id)
+            .await
+            .map_err(map_conflict)?
+            .ok_or(UrlError::NotFound)?;
+        self.url_cache
+            .invalidate(&url_cache_key(old.domain_id, &old.alias))
+            .await;
+        if new.alias != old.alias {
+            self.url_cache
+                .invalidate(&url_cache_key(new.domain_id, &new.alias))
+                .await;
+        }
+        Ok(new)
+    }
+    async fn get_url_history(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        is_admin: bool,
+    ) -> Result<Vec<UrlHistoryEntry>, UrlError> {
+        self.get_owned_url(id, user_id, is_admin).await?;
+        Ok(self.url_repository.get_url_history(id).await?)
+    }
+    async fn delete_url(&self, id: Uuid, user_id: Uuid, is_admin: bool) -> Result<(), UrlError> {
+        self.get_owned_url(id, user_id, is_admin).await?;
+        if let Some(url) = self.url_repository.delete_url(id).await? {
+            self.url_cache
+                .invalidate(&url_cache_key(url.domain_id, &url.alias))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::error::{DatabaseError, ErrorKind};
+    use std::borrow::Cow;
+    use std::error::Error as StdError;
+    use std::fmt;
+
+    #[derive(Debug)]
+    struct UniqueViolation(&'static str);
+
+    impl fmt::Display for UniqueViolation {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "duplicate key value violates unique constraint {}",
+                self.0
+            )
+        }
+    }
+
+    impl StdError for UniqueViolation {}
+
+    impl DatabaseError for UniqueViolation {
+        fn message(&self) -> &str {
+            "duplicate key value violates unique constraint"
+        }
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed("23505"))
+        }
+        fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+            self
+        }
+        fn as_error_mut(&mut self) -> &mut (dyn StdError + Send + Sync + 'static) {
+            self
+        }
+        fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+            self
+        }
+        fn constraint(&self) -> Option<&str> {
+            Some(self.0)
+        }
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::UniqueViolation
+        }
+    }
+
+    fn unique_violation(constraint: &'static str) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(UniqueViolation(constraint)))
+    }
+
+    #[test]
+    fn taken_alias_maps_to_alias_already_exists() {
+        for constraint in [URL_ALIAS_UNIQUE_CONSTRAINT, URL_DEFAULT_ALIAS_UNIQUE_INDEX] {
+            assert!(matches!(
+                map_conflict(unique_violation(constraint)),
+                UrlError::AliasAlreadyExists
+            ));
+        }
+        assert!(matches!(
+            map_conflict(unique_violation(URL_OWNER_UNIQUE_CONSTRAINT)),
+            UrlError::UrlAlreadyExists
+        ));
+        assert!(matches!(
+            map_conflict(unique_violation("some_other_key")),
+            UrlError::Db(_)
+        ));
     }
 }
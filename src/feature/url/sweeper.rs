@@ -0,0 +1,63 @@
+use crate::app::config::{SweeperConfig, SweeperMode};
+use crate::feature::url::repository::{UrlRepository, UrlRepositoryTrait};
+use crate::metrics::PrometheusMetrics;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::task::JoinHandle;
+use tokio::time::{MissedTickBehavior, interval};
+
+pub fn spawn_expired_url_sweeper(
+    config: SweeperConfig,
+    url_repository: Arc<UrlRepository>,
+    metrics: Arc<PrometheusMetrics>,
+) -> Option<JoinHandle<()>> {
+    if !config.enabled {
+        log::info!("Expired url sweeper is disabled");
+        return None;
+    }
+    Some(tokio::spawn(async move {
+        let mut ticker = interval(config.get_interval());
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            sweep_expired_urls(&config, &url_repository, &metrics).await;
+        }
+    }))
+}
+
+async fn sweep_expired_urls(
+    config: &SweeperConfig,
+    url_repository: &UrlRepository,
+    metrics: &PrometheusMetrics,
+) {
+    let start = Instant::now();
+    let batch_size = config.batch_size.max(1);
+    let mode = match config.mode {
+        SweeperMode::Delete => "delete",
+        SweeperMode::Archive => "archive",
+    };
+    let mut removed = 0;
+    loop {
+        let result = match config.mode {
+            SweeperMode::Delete => url_repository.delete_expired_urls(batch_size).await,
+            SweeperMode::Archive => url_repository.archive_expired_urls(batch_size).await,
+        };
+        match result {
+            Ok(count) => {
+                removed += count;
+                if count < batch_size as u64 {
+                    break;
+                }
+            }
+            Err(err) => {
+                eprintln!("❌ Error sweeping expired urls: {:?}", err);
+                metrics.inc_errors("sweeper_error", "url_sweeper");
+                break;
+            }
+        }
+    }
+    if removed > 0 {
+        log::info!("Expired url sweeper removed {} urls ({})", removed, mode);
+    }
+    metrics.observe_url_sweep(mode, removed, start.elapsed().as_secs_f64());
+}
@@ -0,0 +1,143 @@
+use crate::feature::url::policy::PolicyViolation;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error as SqlxError, FromRow};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct UrlVariant {
+    pub id: Uuid,
+    pub url_id: Uuid,
+    pub destination: String,
+    /// Relative share of the traffic, compared with the other variants.
+    pub weight: i32,
+    /// Redirects served by this variant.
+    pub clicks: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct VariantDTO {
+    #[validate(url)]
+    #[schema(example = "https://example.com/landing-b")]
+    pub destination: String,
+    #[validate(range(min = 1, max = 10000))]
+    #[schema(example = 50)]
+    pub weight: i32,
+}
+
+/// Keeps the visitor on their previous variant while it still exists,
+/// otherwise picks one at random proportionally to the weights.
+pub fn pick_variant(variants: &[UrlVariant], sticky: Option<Uuid>) -> Option<&UrlVariant> {
+    if let Some(variant) = sticky.and_then(|id| variants.iter().find(|v| v.id == id)) {
+        return Some(variant);
+    }
+    let total: i64 = variants.iter().map(|v| i64::from(v.weight.max(0))).sum();
+    if total <= 0 {
+        return None;
+    }
+    let mut roll = rand::thread_rng().gen_range(0..total);
+    variants.iter().find(|variant| {
+        let weight = i64::from(variant.weight.max(0));
+        if roll < weight {
+            true
+        } else {
+            roll -= weight;
+            false
+        }
+    })
+}
+
+#[derive(Debug)]
+pub enum VariantError {
+    NotFound,
+    PolicyViolation(Vec<PolicyViolation>),
+    Db(SqlxError),
+}
+
+impl std::fmt::Display for VariantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariantError::NotFound => write!(f, "variant not found"),
+            VariantError::PolicyViolation(violations) => {
+                let reasons: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+                write!(f, "destination rejected: {}", reasons.join("; "))
+            }
+            VariantError::Db(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl From<SqlxError> for VariantError {
+    fn from(err: SqlxError) -> Self {
+        VariantError::Db(err)
+    }
+}
+
+pub const URL_VARIANTS_TABLE: &str = "url_variants";
+pub const URL_VARIANTS_ID: &str = "id";
+pub const URL_VARIANTS_URL_ID: &str = "url_id";
+pub const URL_VARIANTS_DESTINATION: &str = "destination";
+pub const URL_VARIANTS_WEIGHT: &str = "weight";
+pub const URL_VARIANTS_CLICKS: &str = "clicks";
+pub const URL_VARIANTS_CREATED_AT: &str = "created_at";
+pub const URL_VARIANTS_COLUMNS: [&str; 6] = [
+    URL_VARIANTS_ID,
+    URL_VARIANTS_URL_ID,
+    URL_VARIANTS_DESTINATION,
+    URL_VARIANTS_WEIGHT,
+    URL_VARIANTS_CLICKS,
+    URL_VARIANTS_CREATED_AT,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(weight: i32) -> UrlVariant {
+        UrlVariant {
+            id: Uuid::new_v4(),
+            url_id: Uuid::nil(),
+            destination: format!("https://example.com/{}", weight),
+            weight,
+            clicks: 0,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn pick_variant_keeps_an_existing_sticky_variant() {
+        let variants = vec![variant(1), variant(1000)];
+        let picked = pick_variant(&variants, Some(variants[0].id)).unwrap();
+        assert_eq!(picked.id, variants[0].id);
+    }
+
+    #[test]
+    fn pick_variant_ignores_a_removed_sticky_variant() {
+        let variants = vec![variant(0), variant(5)];
+        let picked = pick_variant(&variants, Some(Uuid::new_v4())).unwrap();
+        assert_eq!(picked.id, variants[1].id);
+    }
+
+    #[test]
+    fn pick_variant_never_picks_zero_or_negative_weights() {
+        let variants = vec![variant(0), variant(-3), variant(1)];
+        for _ in 0..100 {
+            assert_eq!(pick_variant(&variants, None).unwrap().id, variants[2].id);
+        }
+        assert!(pick_variant(&[variant(0)], None).is_none());
+        assert!(pick_variant(&[], None).is_none());
+    }
+
+    #[test]
+    fn pick_variant_follows_the_weights() {
+        let variants = vec![variant(1), variant(3)];
+        let heavy = (0..4000)
+            .filter(|_| pick_variant(&variants, None).unwrap().id == variants[1].id)
+            .count();
+        assert!((2700..3300).contains(&heavy), "picked {} of 4000", heavy);
+    }
+}
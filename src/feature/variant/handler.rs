@@ -0,0 +1,196 @@
+use crate::feature::url::entity::UrlRejection;
+use crate::feature::url::resource::LinkResourceHandler;
+use crate::feature::url::service::UrlService;
+use crate::feature::variant::entity::{UrlVariant, VariantDTO, VariantError};
+use crate::feature::variant::service::{VariantService, VariantServiceTrait};
+use crate::metrics::PrometheusMetrics;
+use crate::servers::http::middleware::UserJWT;
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct VariantHandler {
+    variant_service: Arc<VariantService>,
+    link: LinkResourceHandler,
+}
+
+impl VariantHandler {
+    pub fn new_handler(
+        variant_service: Arc<VariantService>,
+        url_service: Arc<UrlService>,
+        metrics: Arc<PrometheusMetrics>,
+    ) -> Self {
+        Self {
+            variant_service,
+            link: LinkResourceHandler::new(
+                url_service,
+                metrics,
+                "variant_handler",
+                "Variant not found",
+            ),
+        }
+    }
+
+    fn error_response(&self, err: VariantError) -> Response {
+        match err {
+            VariantError::NotFound => self.link.not_found(),
+            VariantError::PolicyViolation(violations) => self.link.policy_violation(violations),
+            VariantError::Db(err) => self.link.internal_error(err),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/private/url/{id}/variants",
+    params(
+        ("id" = Uuid, Path, description = "URL ID")
+    ),
+    responses(
+        (status = 200, description = "A/B variants with their click counters", body = Vec<UrlVariant>),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Variants"
+)]
+pub async fn list_variants_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<VariantHandler>>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    if let Err(response) = handlers.link.check_owner(id, &user_jwt).await {
+        return response;
+    }
+    match handlers.variant_service.list_variants(id).await {
+        Ok(variants) => Json(variants).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/private/url/{id}/variants",
+    params(
+        ("id" = Uuid, Path, description = "URL ID")
+    ),
+    request_body = VariantDTO,
+    responses(
+        (status = 201, description = "Variant created", body = UrlVariant),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL not found"),
+        (status = 422, description = "Validation error or destination rejected by URL policy", body = UrlRejection),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Variants"
+)]
+pub async fn create_variant_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<VariantHandler>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<VariantDTO>,
+) -> Response {
+    if let Some(response) = handlers.link.validation_error(&payload) {
+        return response;
+    }
+    if let Err(response) = handlers.link.check_owner(id, &user_jwt).await {
+        return response;
+    }
+    match handlers.variant_service.create_variant(id, payload).await {
+        Ok(variant) => (StatusCode::CREATED, Json(variant)).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/private/url/{id}/variants/{variant_id}",
+    params(
+        ("id" = Uuid, Path, description = "URL ID"),
+        ("variant_id" = Uuid, Path, description = "Variant ID")
+    ),
+    request_body = VariantDTO,
+    responses(
+        (status = 200, description = "Variant updated", body = UrlVariant),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL or variant not found"),
+        (status = 422, description = "Validation error or destination rejected by URL policy", body = UrlRejection),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Variants"
+)]
+pub async fn update_variant_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<VariantHandler>>,
+    Path((id, variant_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<VariantDTO>,
+) -> Response {
+    if let Some(response) = handlers.link.validation_error(&payload) {
+        return response;
+    }
+    if let Err(response) = handlers.link.check_owner(id, &user_jwt).await {
+        return response;
+    }
+    match handlers
+        .variant_service
+        .update_variant(id, variant_id, payload)
+        .await
+    {
+        Ok(variant) => Json(variant).into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/private/url/{id}/variants/{variant_id}",
+    params(
+        ("id" = Uuid, Path, description = "URL ID"),
+        ("variant_id" = Uuid, Path, description = "Variant ID")
+    ),
+    responses(
+        (status = 204, description = "Variant deleted"),
+        (status = 401, description = "Unauthorized - requires authentication"),
+        (status = 403, description = "URL belongs to another user"),
+        (status = 404, description = "URL or variant not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("cookie_auth" = [])
+    ),
+    tag = "Variants"
+)]
+pub async fn delete_variant_handler(
+    Extension(user_jwt): Extension<UserJWT>,
+    State(handlers): State<Arc<VariantHandler>>,
+    Path((id, variant_id)): Path<(Uuid, Uuid)>,
+) -> Response {
+    if let Err(response) = handlers.link.check_owner(id, &user_jwt).await {
+        return response;
+    }
+    match handlers
+        .variant_service
+        .delete_variant(id, variant_id)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => handlers.error_response(err),
+    }
+}
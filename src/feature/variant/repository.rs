@@ -0,0 +1,123 @@
+use crate::feature::variant::entity::{
+    URL_VARIANTS_COLUMNS, URL_VARIANTS_CREATED_AT, URL_VARIANTS_DESTINATION, URL_VARIANTS_ID,
+    URL_VARIANTS_TABLE, URL_VARIANTS_URL_ID, URL_VARIANTS_WEIGHT, UrlVariant,
+};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use sea_query::{Alias, Expr, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::{Error, Pool, Postgres, query_as_with};
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait VariantRepositoryTrait: Send + Sync {
+    async fn list_variants(&self, url_id: Uuid) -> Result<Vec<UrlVariant>, Error>;
+    async fn add_variant(
+        &self,
+        url_id: Uuid,
+        destination: String,
+        weight: i32,
+    ) -> Result<UrlVariant, Error>;
+    async fn update_variant(
+        &self,
+        url_id: Uuid,
+        variant_id: Uuid,
+        destination: String,
+        weight: i32,
+    ) -> Result<Option<UrlVariant>, Error>;
+    async fn delete_variant(
+        &self,
+        url_id: Uuid,
+        variant_id: Uuid,
+    ) -> Result<Option<UrlVariant>, Error>;
+}
+
+#[derive(Clone)]
+pub struct VariantRepository {
+    primary_db: Pool<Postgres>,
+}
+
+impl VariantRepository {
+    pub fn new_variant_repository(primary_db: Pool<Postgres>) -> Self {
+        Self { primary_db }
+    }
+}
+
+#[async_trait]
+impl VariantRepositoryTrait for VariantRepository {
+    async fn list_variants(&self, url_id: Uuid) -> Result<Vec<UrlVariant>, Error> {
+        let (sql, values) = Query::select()
+            .columns(URL_VARIANTS_COLUMNS)
+            .from(URL_VARIANTS_TABLE)
+            .and_where(Expr::col(URL_VARIANTS_URL_ID).eq(url_id))
+            .order_by(URL_VARIANTS_CREATED_AT, Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+        let variants = query_as_with::<_, UrlVariant, _>(&sql, values)
+            .fetch_all(&self.primary_db)
+            .await
+            .map_err(|err| {
+                eprintln!("❌ Error fetching url variants: {:?}", err);
+                err
+            })?;
+        Ok(variants)
+    }
+    async fn add_variant(
+        &self,
+        url_id: Uuid,
+        destination: String,
+        weight: i32,
+    ) -> Result<UrlVariant, Error> {
+        let (sql, values) = Query::insert()
+            .into_table(Alias::new(URL_VARIANTS_TABLE))
+            .columns([
+                Alias::new(URL_VARIANTS_URL_ID),
+                Alias::new(URL_VARIANTS_DESTINATION),
+                Alias::new(URL_VARIANTS_WEIGHT),
+            ])
+            .values_panic([url_id.into(), destination.into(), weight.into()])
+            .returning(Query::returning().columns(URL_VARIANTS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let variant = query_as_with::<_, UrlVariant, _>(&sql, values)
+            .fetch_one(&self.primary_db)
+            .await?;
+        Ok(variant)
+    }
+    async fn update_variant(
+        &self,
+        url_id: Uuid,
+        variant_id: Uuid,
+        destination: String,
+        weight: i32,
+    ) -> Result<Option<UrlVariant>, Error> {
+        let (sql, values) = Query::update()
+            .table(URL_VARIANTS_TABLE)
+            .value(URL_VARIANTS_DESTINATION, destination)
+            .value(URL_VARIANTS_WEIGHT, weight)
+            .and_where(Expr::col(URL_VARIANTS_ID).eq(variant_id))
+            .and_where(Expr::col(URL_VARIANTS_URL_ID).eq(url_id))
+            .returning(Query::returning().columns(URL_VARIANTS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let variant = query_as_with::<_, UrlVariant, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await?;
+        Ok(variant)
+    }
+    async fn delete_variant(
+        &self,
+        url_id: Uuid,
+        variant_id: Uuid,
+    ) -> Result<Option<UrlVariant>, Error> {
+        let (sql, values) = Query::delete()
+            .from_table(URL_VARIANTS_TABLE)
+            .and_where(Expr::col(URL_VARIANTS_ID).eq(variant_id))
+            .and_where(Expr::col(URL_VARIANTS_URL_ID).eq(url_id))
+            .returning(Query::returning().columns(URL_VARIANTS_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+        let variant = query_as_with::<_, UrlVariant, _>(&sql, values)
+            .fetch_optional(&self.primary_db)
+            .await?;
+        Ok(variant)
+    }
+}
@@ -0,0 +1,118 @@
+use crate::feature::url::policy::UrlPolicyChain;
+use crate::feature::variant::entity::{UrlVariant, VariantDTO, VariantError, pick_variant};
+use crate::feature::variant::repository::{VariantRepository, VariantRepositoryTrait};
+use crate::utils::cache::{Cache, get_or_load};
+use async_trait::async_trait;
+#[cfg(test)]
+use mockall::automock;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait VariantServiceTrait: Send + Sync {
+    async fn list_variants(&self, url_id: Uuid) -> Result<Vec<UrlVariant>, VariantError>;
+    async fn create_variant(
+        &self,
+        url_id: Uuid,
+        dto: VariantDTO,
+    ) -> Result<UrlVariant, VariantError>;
+    async fn update_variant(
+        &self,
+        url_id: Uuid,
+        variant_id: Uuid,
+        dto: VariantDTO,
+    ) -> Result<UrlVariant, VariantError>;
+    async fn delete_variant(&self, url_id: Uuid, variant_id: Uuid) -> Result<(), VariantError>;
+    async fn choose_variant(
+        &self,
+        url_id: Uuid,
+        sticky: Option<Uuid>,
+    ) -> Result<Option<UrlVariant>, VariantError>;
+}
+
+pub struct VariantService {
+    variant_repository: Arc<VariantRepository>,
+    url_policy: Arc<UrlPolicyChain>,
+    /// Variants are read on every redirect, keyed by link id.
+    variant_cache: Arc<dyn Cache<Vec<UrlVariant>>>,
+}
+
+impl VariantService {
+    pub fn new(
+        variant_repository: Arc<VariantRepository>,
+        url_policy: Arc<UrlPolicyChain>,
+        variant_cache: Arc<dyn Cache<Vec<UrlVariant>>>,
+    ) -> Self {
+        Self {
+            variant_repository,
+            url_policy,
+            variant_cache,
+        }
+    }
+
+    async fn check_policy(&self, destination: &str) -> Result<(), VariantError> {
+        self.url_policy
+            .check(destination)
+            .await
+            .map_err(VariantError::PolicyViolation)
+    }
+
+    async fn invalidate(&self, url_id: Uuid) {
+        self.variant_cache.invalidate(&url_id.to_string()).await;
+    }
+}
+
+#[async_trait]
+impl VariantServiceTrait for VariantService {
+    async fn list_variants(&self, url_id: Uuid) -> Result<Vec<UrlVariant>, VariantError> {
+        Ok(self.variant_repository.list_variants(url_id).await?)
+    }
+    async fn create_variant(
+        &self,
+        url_id: Uuid,
+        dto: VariantDTO,
+    ) -> Result<UrlVariant, VariantError> {
+        self.check_policy(&dto.destination).await?;
+        let variant = self
+            .variant_repository
+            .add_variant(url_id, dto.destination, dto.weight)
+            .await?;
+        self.invalidate(url_id).await;
+        Ok(variant)
+    }
+    async fn update_variant(
+        &self,
+        url_id: Uuid,
+        variant_id: Uuid,
+        dto: VariantDTO,
+    ) -> Result<UrlVariant, VariantError> {
+        self.check_policy(&dto.destination).await?;
+        let variant = self
+            .variant_repository
+            .update_variant(url_id, variant_id, dto.destination, dto.weight)
+            .await?
+            .ok_or(VariantError::NotFound)?;
+        self.invalidate(url_id).await;
+        Ok(variant)
+    }
+    async fn delete_variant(&self, url_id: Uuid, variant_id: Uuid) -> Result<(), VariantError> {
+        self.variant_repository
+            .delete_variant(url_id, variant_id)
+            .await?
+            .ok_or(VariantError::NotFound)?;
+        self.invalidate(url_id).await;
+        Ok(())
+    }
+    async fn choose_variant(
+        &self,
+        url_id: Uuid,
+        sticky: Option<Uuid>,
+    ) -> Result<Option<UrlVariant>, VariantError> {
+        let variants = get_or_load(self.variant_cache.as_ref(), &url_id.to_string(), || async {
+            Ok::<_, VariantError>(self.variant_repository.list_variants(url_id).await?)
+        })
+        .await?;
+        Ok(pick_variant(&variants, sticky).cloned())
+    }
+}
@@ -21,11 +21,15 @@ mod utils;
 #[cfg(not(target_os = "windows"))]
 use jemallocator::Jemalloc as GlobalAlloc;
 
+use crate::feature::auth::keyring::init_keyring;
+use crate::feature::url::entity::{NewUrl, UrlError};
+use crate::feature::url::qr::{QrOptions, render_qr};
 use crate::feature::url::service::UrlServiceTrait;
+use crate::feature::url::sweeper::spawn_expired_url_sweeper;
 use crate::utils::url::extract_first_valid_url_from_message;
 #[cfg(target_os = "windows")]
 use mimalloc::MiMalloc as GlobalAlloc;
-use teloxide::types::{BotCommand, BotCommandScope};
+use teloxide::types::{BotCommand, BotCommandScope, InputFile};
 
 #[global_allocator]
 static GLOBAL: GlobalAlloc = GlobalAlloc;
@@ -59,15 +63,21 @@ async fn main() -> std::io::Result<()> {
     log::info!("tk token: {}", token);
     let bot = Bot::from_env();
     let config = Config::new().await;
+    init_keyring(&config.jwt).expect("Could not load JWT keys");
     let http_server = config.server.clone().unwrap_or_else(|| {
         panic!("HTTP server configuration not found");
     });
 
     let pool = init_primary_db(&config).await.expect("Count not init db");
     let repo = Arc::new(Repositories::new(pool.clone()));
-    let services = Arc::new(Services::new(repo));
     let metrics = Arc::new(PrometheusMetrics::new().expect("Failed to create Prometheus metrics"));
-    let handlers = Arc::new(Handlers::new(services.clone(), metrics.clone()));
+    let services = Arc::new(Services::new(repo.clone(), &config, metrics.clone()).await);
+    let handlers = Arc::new(Handlers::new(services.clone(), metrics.clone(), &config));
+    spawn_expired_url_sweeper(
+        config.sweeper.clone(),
+        repo.url_repository.clone(),
+        metrics.clone(),
+    );
     let metrics_clone = metrics.clone();
     let http_task = async {
         run_http_server(
@@ -111,11 +121,10 @@ async fn command_handler(
     msg: Message,
     cmd: Command,
     dialogue: MyDialogue,
-    metrics: Arc<PrometheusMetrics>,
 ) -> anyhow::Result<()> {
     match cmd {
         Command::Start => {
-            start(bot, dialogue, msg, metrics)
+            start(bot, dialogue, msg)
                 .await
                 .expect("TODO: panic message");
         }
@@ -146,12 +155,7 @@ async fn set_bot_commands(bot: &Bot) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn start(
-    bot: Bot,
-    dialogue: MyDialogue,
-    msg: Message,
-    metrics: Arc<PrometheusMetrics>,
-) -> HandlerResult {
+async fn start(bot: Bot, dialogue: MyDialogue, msg: Message) -> HandlerResult {
     if let Some(txt) = msg.text() {
         if txt != "/start" {
             bot.send_message(msg.chat.id, "Please, write /start")
@@ -186,13 +190,39 @@ async fn receive_full_url(
         let user_id_uuid = user_id_to_uuid(user_id);
         match services
             .url_service
-            .create_url(valid_url.to_string(), user_id_uuid)
+            .create_url(NewUrl::from_url(valid_url.to_string()), user_id_uuid)
             .await
         {
             Ok(created_url) => {
                 metrics.inc_url_shortening();
-                bot.send_message(msg.chat.id, format!("✅ Saved url: {:?}", created_url))
-                    .await?;
+                bot.send_message(
+                    msg.chat.id,
+                    format!("✅ Saved url: {}", created_url.short_url),
+                )
+                .await?;
+                match render_qr(&created_url.short_url, &QrOptions::default()) {
+                    Ok(qr) => {
+                        bot.send_photo(
+                            msg.chat.id,
+                            InputFile::memory(qr.bytes)
+                                .file_name(format!("{}.png", created_url.url.alias)),
+                        )
+                        .await?;
+                    }
+                    Err(e) => log::error!("Failed to render QR code: {}", e),
+                }
+            }
+            Err(UrlError::PolicyViolation(violations)) => {
+                metrics.inc_errors("policy_violation", "telegram_bot");
+                let reasons: Vec<String> = violations
+                    .iter()
+                    .map(|v| format!("• {}", v.message))
+                    .collect();
+                bot.send_message(
+                    msg.chat.id,
+                    format!("❌ URL rejected:\n{}", reasons.join("\n")),
+                )
+                .await?;
             }
             Err(e) => {
                 metrics.inc_errors("url_creation_error", "telegram_bot");
@@ -1,6 +1,6 @@
 use prometheus::{
-    CounterVec, Encoder, HistogramOpts, HistogramVec, IntCounter, IntGauge, Opts, Registry,
-    TextEncoder,
+    CounterVec, Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec,
+    IntGauge, Opts, Registry, TextEncoder,
 };
 use std::sync::Arc;
 
@@ -13,7 +13,12 @@ pub struct PrometheusMetrics {
     pub database_connections_active: IntGauge,
     pub url_shortening_total: IntCounter,
     pub url_redirects_total: IntCounter,
+    pub clicks_recorded_total: IntCounter,
+    pub url_cache_requests_total: IntCounterVec,
     pub telegram_messages_processed: IntCounter,
+    pub url_sweeper_runs_total: IntCounter,
+    pub url_sweeper_removed_total: IntCounterVec,
+    pub url_sweeper_duration_seconds: Histogram,
     pub errors_total: CounterVec,
 }
 
@@ -68,6 +73,23 @@ impl PrometheusMetrics {
                 .namespace("url_shortener"),
         )?;
 
+        let clicks_recorded_total = IntCounter::with_opts(
+            Opts::new(
+                "clicks_recorded_total",
+                "Total number of click events written to the database",
+            )
+            .namespace("url_shortener"),
+        )?;
+
+        let url_cache_requests_total = IntCounterVec::new(
+            Opts::new(
+                "url_cache_requests_total",
+                "Total number of redirect cache lookups",
+            )
+            .namespace("url_shortener"),
+            &["result"],
+        )?;
+
         let telegram_messages_processed = IntCounter::with_opts(
             Opts::new(
                 "telegram_messages_processed_total",
@@ -76,6 +98,33 @@ impl PrometheusMetrics {
             .namespace("url_shortener"),
         )?;
 
+        // Очистка просроченных ссылок
+        let url_sweeper_runs_total = IntCounter::with_opts(
+            Opts::new(
+                "url_sweeper_runs_total",
+                "Total number of expired URL sweeper runs",
+            )
+            .namespace("url_shortener"),
+        )?;
+
+        let url_sweeper_removed_total = IntCounterVec::new(
+            Opts::new(
+                "url_sweeper_removed_total",
+                "Total number of expired URLs removed by the sweeper",
+            )
+            .namespace("url_shortener"),
+            &["mode"],
+        )?;
+
+        let url_sweeper_duration_seconds = Histogram::with_opts(
+            HistogramOpts::new(
+                "url_sweeper_duration_seconds",
+                "Expired URL sweeper run duration in seconds",
+            )
+            .namespace("url_shortener")
+            .buckets(vec![0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0]),
+        )?;
+
         // Ошибки
         let errors_total = CounterVec::new(
             Opts::new("errors_total", "Total number of errors").namespace("url_shortener"),
@@ -89,7 +138,12 @@ impl PrometheusMetrics {
         registry.register(Box::new(database_connections_active.clone()))?;
         registry.register(Box::new(url_shortening_total.clone()))?;
         registry.register(Box::new(url_redirects_total.clone()))?;
+        registry.register(Box::new(clicks_recorded_total.clone()))?;
+        registry.register(Box::new(url_cache_requests_total.clone()))?;
         registry.register(Box::new(telegram_messages_processed.clone()))?;
+        registry.register(Box::new(url_sweeper_runs_total.clone()))?;
+        registry.register(Box::new(url_sweeper_removed_total.clone()))?;
+        registry.register(Box::new(url_sweeper_duration_seconds.clone()))?;
         registry.register(Box::new(errors_total.clone()))?;
 
         Ok(Self {
@@ -100,7 +154,12 @@ impl PrometheusMetrics {
             database_connections_active,
             url_shortening_total,
             url_redirects_total,
+            clicks_recorded_total,
+            url_cache_requests_total,
             telegram_messages_processed,
+            url_sweeper_runs_total,
+            url_sweeper_removed_total,
+            url_sweeper_duration_seconds,
             errors_total,
         })
     }
@@ -147,16 +206,49 @@ impl PrometheusMetrics {
         self.url_shortening_total.inc();
     }
 
+    /// Увеличивает счетчик сокращенных URL на количество ссылок из пакетной загрузки
+    pub fn add_url_shortenings(&self, count: usize) {
+        self.url_shortening_total.inc_by(count as u64);
+    }
+
     /// Увеличивает счетчик переходов по сокращенным URL
     pub fn inc_url_redirects(&self) {
         self.url_redirects_total.inc();
     }
 
+    /// Увеличивает счетчик записанных переходов
+    pub fn inc_clicks_recorded(&self, count: u64) {
+        self.clicks_recorded_total.inc_by(count);
+    }
+
+    /// Увеличивает счетчик попаданий в кэш ссылок
+    pub fn inc_url_cache_hit(&self) {
+        self.url_cache_requests_total
+            .with_label_values(&["hit"])
+            .inc();
+    }
+
+    /// Увеличивает счетчик промахов кэша ссылок
+    pub fn inc_url_cache_miss(&self) {
+        self.url_cache_requests_total
+            .with_label_values(&["miss"])
+            .inc();
+    }
+
     /// Увеличивает счетчик обработанных сообщений Telegram
     pub fn inc_telegram_messages(&self) {
         self.telegram_messages_processed.inc();
     }
 
+    /// Записывает результат запуска очистки просроченных ссылок
+    pub fn observe_url_sweep(&self, mode: &str, removed: u64, duration: f64) {
+        self.url_sweeper_runs_total.inc();
+        self.url_sweeper_removed_total
+            .with_label_values(&[mode])
+            .inc_by(removed);
+        self.url_sweeper_duration_seconds.observe(duration);
+    }
+
     /// Записывает ошибку
     pub fn inc_errors(&self, error_type: &str, component: &str) {
         self.errors_total
@@ -1,15 +1,21 @@
+use crate::feature::api_key::entity::{ApiKeyOwner, effective_role, scopes_allow};
+use crate::feature::api_key::service::{ApiKeyService, ApiKeyServiceTrait};
 use crate::feature::auth::entity::UserRole;
-use crate::feature::auth::jwt::decode_jwt;
-use crate::utils::constants::{ACCESS_TOKEN_COOKIE, REFRESH_TOKEN_COOKIE};
+use crate::feature::auth::jwt::{TokenType, decode_jwt};
+use crate::utils::constants::ACCESS_TOKEN_COOKIE;
 use axum::{
-    extract::Request,
-    http::{StatusCode, header::COOKIE},
+    extract::{Request, State},
+    http::{
+        Method, StatusCode,
+        header::{AUTHORIZATION, COOKIE},
+    },
     middleware::Next,
     response::Response,
 };
 use cookie::Cookie;
 use log::{error, info};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,70 +24,135 @@ pub struct UserJWT {
     pub role: UserRole,
 }
 
-pub async fn auth_middleware(mut req: Request, next: Next) -> Result<Response, StatusCode> {
-    if let Some(tokens) = extract_tokens_from_request(&req) {
-        if let Some(access_token) = tokens.access_token {
-            match decode_jwt(&access_token).await {
-                Ok(user_jwt) => {
-                    req.extensions_mut().insert(UserJWT {
-                        id: user_jwt.id,
-                        role: user_jwt.role,
-                    });
-                    info!("Valid access token for user:",);
-                }
-                Err(e) => {
-                    error!("Invalid access token: {:?}", e);
-                }
-            }
-        }
+impl UserJWT {
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin
+    }
+}
 
-        if let Some(refresh_token) = tokens.refresh_token {
-            match decode_jwt(&refresh_token).await {
-                Ok(user_jwt) => {
-                    req.extensions_mut().insert(UserJWT {
-                        id: user_jwt.id,
-                        role: user_jwt.role,
-                    });
-                    info!("Valid access token for user");
-                }
-                Err(e) => {
-                    error!("Invalid access token: {:?}", e);
-                }
+/// Set alongside `UserJWT` when the request was authenticated with an API key.
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuth {
+    pub id: Uuid,
+}
+
+pub async fn auth_middleware(
+    State(api_keys): State<Arc<ApiKeyService>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    if let Some(key) = extract_bearer_token(&req) {
+        let owner = match api_keys.authenticate(&key).await {
+            Ok(Some(owner)) => owner,
+            Ok(None) => {
+                error!("Rejected unknown api key");
+                return Err(StatusCode::UNAUTHORIZED);
             }
-        }
-    } else {
+            Err(e) => {
+                error!("Could not resolve api key: {}", e);
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+        };
+        let Some(user) = api_key_user(&owner, req.method()) else {
+            error!("Api key {} lacks scope for {}", owner.id, req.method());
+            return Err(StatusCode::FORBIDDEN);
+        };
+        req.extensions_mut().insert(user);
+        req.extensions_mut().insert(ApiKeyAuth { id: owner.id });
+        info!("Valid api key for user: {}", owner.user_id);
+        return Ok(next.run(req).await);
+    }
+
+    let Some(access_token) = extract_access_token(&req) else {
         return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    match decode_jwt(&access_token).await {
+        Ok(claims) if claims.typ == TokenType::Access => {
+            req.extensions_mut().insert(UserJWT {
+                id: claims.id,
+                role: claims.role,
+            });
+            info!("Valid access token for user: {}", claims.id);
+        }
+        Ok(_) => {
+            error!("Rejected non-access token");
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        Err(e) => {
+            error!("Invalid access token: {:?}", e);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
     }
 
     Ok(next.run(req).await)
 }
 
-struct Tokens {
-    access_token: Option<String>,
-    refresh_token: Option<String>,
+/// Identity a request made with the key acts as, or `None` when the key's
+/// scopes do not cover the request method.
+fn api_key_user(owner: &ApiKeyOwner, method: &Method) -> Option<UserJWT> {
+    scopes_allow(&owner.scopes, method).then(|| UserJWT {
+        id: owner.user_id,
+        role: effective_role(&owner.scopes, owner.role.clone()),
+    })
+}
+
+fn extract_bearer_token(req: &Request) -> Option<String> {
+    let header = req.headers().get(AUTHORIZATION)?.to_str().ok()?;
+    let token = header.strip_prefix("Bearer ")?.trim();
+    (!token.is_empty()).then(|| token.to_string())
 }
 
-fn extract_tokens_from_request(req: &Request) -> Option<Tokens> {
+fn extract_access_token(req: &Request) -> Option<String> {
     let cookie_header = req.headers().get(COOKIE)?;
     let cookie_str = cookie_header.to_str().ok()?;
 
-    let cookies: Vec<Cookie> = cookie_str
+    cookie_str
         .split(';')
         .filter_map(|cookie| Cookie::parse(cookie.trim()).ok())
-        .collect();
-
-    let access_token = cookies
-        .iter()
         .find(|cookie| cookie.name() == ACCESS_TOKEN_COOKIE)
-        .map(|cookie| cookie.value().to_string());
+        .map(|cookie| cookie.value().to_string())
+}
 
-    let refresh_token = cookies
-        .iter()
-        .find(|cookie| cookie.name() == REFRESH_TOKEN_COOKIE)
-        .map(|cookie| cookie.value().to_string());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feature::api_key::entity::ApiKeyScope;
+    use sqlx::types::Json;
 
-    Some(Tokens {
-        access_token,
-        refresh_token,
-    })
+    fn owner(role: UserRole, scopes: &[ApiKeyScope]) -> ApiKeyOwner {
+        ApiKeyOwner {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            role,
+            scopes: Json(scopes.to_vec()),
+            last_used_at: None,
+        }
+    }
+
+    #[test]
+    fn write_scoped_key_can_create_links_as_its_owner() {
+        let owner = owner(UserRole::User, &[ApiKeyScope::Write]);
+        let user = api_key_user(&owner, &Method::POST).unwrap();
+        assert_eq!(user.id, owner.user_id);
+        assert_eq!(user.role, UserRole::User);
+    }
+
+    #[test]
+    fn read_scoped_key_cannot_create_links() {
+        let owner = owner(UserRole::User, &[ApiKeyScope::Read]);
+        assert!(api_key_user(&owner, &Method::POST).is_none());
+        assert!(api_key_user(&owner, &Method::GET).is_some());
+    }
+
+    #[test]
+    fn admin_owner_keeps_the_admin_role_only_with_the_admin_scope() {
+        let write = owner(UserRole::Admin, &[ApiKeyScope::Write]);
+        assert_eq!(
+            api_key_user(&write, &Method::POST).unwrap().role,
+            UserRole::User
+        );
+        let admin = owner(UserRole::Admin, &[ApiKeyScope::Write, ApiKeyScope::Admin]);
+        assert!(api_key_user(&admin, &Method::POST).unwrap().is_admin());
+    }
 }
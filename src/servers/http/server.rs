@@ -1,25 +1,49 @@
+use crate::feature::analytics::handler::get_url_stats_handler;
+use crate::feature::api_key::handler::{
+    create_api_key_handler, list_api_keys_handler, revoke_api_key_handler,
+};
 use crate::feature::auth::handler::{
-    get_user_by_email_handler, google_oauth_handler, handle_google_code, register_handler,
+    get_user_by_email_handler, jwks_handler, logout_handler, refresh_handler, register_handler,
+};
+use crate::feature::blocklist::handler::{
+    add_blocked_domain_handler, delete_blocked_domain_handler, list_blocked_domains_handler,
+};
+use crate::feature::custom_domain::handler::{
+    add_domain_handler, delete_domain_handler, list_domains_handler, verify_domain_handler,
+};
+use crate::feature::oauth::handler::{oauth_callback_handler, oauth_url_handler};
+use crate::feature::redirect_rule::handler::{
+    create_rule_handler, delete_rule_handler, list_rules_handler, update_rule_handler,
+};
+use crate::feature::url::handler::{
+    bulk_create_urls_handler, create_url_handler, delete_url_handler, get_my_urls_handler,
+    get_url_history_handler, qr_code_handler, redirect_url_handler, unlock_url_handler,
+    update_url_handler,
+};
+use crate::feature::variant::handler::{
+    create_variant_handler, delete_variant_handler, list_variants_handler, update_variant_handler,
 };
-use crate::feature::url::handler::{create_url_handler, delete_url_handler};
 use crate::metrics::{PrometheusMetrics, metrics_middleware, middleware::metrics_handler};
 use crate::servers::http::middleware::auth_middleware;
+use crate::utils::constants::BULK_BODY_LIMIT;
 use crate::{
     app::handlers::Handlers, feature::url::handler::get_all_url_handler_axum,
     swagger::swagger_api::ApiDoc,
 };
 use axum::{
     Router,
-    middleware::{from_fn, from_fn_with_state},
-    routing::{delete, get, post},
+    extract::DefaultBodyLimit,
+    middleware::from_fn_with_state,
+    routing::{delete, get, post, put},
 };
 use sqlx::{Pool, Postgres};
 use tower_http::compression::CompressionLayer;
 
+use std::net::SocketAddr;
 use std::sync::Arc;
+use tokio::net::TcpListener;
 #[cfg(unix)]
 use tokio::signal::unix::{SignalKind, signal};
-use tokio::{net::TcpListener, signal};
 use tower_http::cors::{Any, CorsLayer};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
@@ -34,24 +58,100 @@ pub async fn run_http_server(
     let listener = TcpListener::bind(format!("{}:{}", host, port))
         .await
         .unwrap();
-    let auth_google = Router::new()
-        .route("/url", get(google_oauth_handler))
-        .route("/callback", post(handle_google_code));
+    let auth_oauth = Router::new()
+        .route("/{provider}/url", get(oauth_url_handler))
+        .route("/{provider}/callback", post(oauth_callback_handler))
+        .with_state(handlers.oauth_handler.clone());
     let auth_basic = Router::new()
         .route("/register", post(register_handler))
         .route("/login", post(get_user_by_email_handler))
+        .route("/refresh", post(refresh_handler))
+        .route("/logout", post(logout_handler))
         .with_state(handlers.user_handle.clone());
 
+    let analytics_router = Router::new()
+        .route("/url/{id}/stats", get(get_url_stats_handler))
+        .with_state(handlers.analytics_handler.clone());
+
+    let blocklist_router = Router::new()
+        .route(
+            "/blocklist",
+            get(list_blocked_domains_handler).post(add_blocked_domain_handler),
+        )
+        .route("/blocklist/{id}", delete(delete_blocked_domain_handler))
+        .with_state(handlers.blocklist_handler.clone());
+
+    let domains_router = Router::new()
+        .route(
+            "/domains",
+            get(list_domains_handler).post(add_domain_handler),
+        )
+        .route("/domains/{id}", delete(delete_domain_handler))
+        .route("/domains/{id}/verify", post(verify_domain_handler))
+        .with_state(handlers.domain_handler.clone());
+    let rules_router = Router::new()
+        .route(
+            "/url/{id}/rules",
+            get(list_rules_handler).post(create_rule_handler),
+        )
+        .route(
+            "/url/{id}/rules/{rule_id}",
+            put(update_rule_handler).delete(delete_rule_handler),
+        )
+        .with_state(handlers.rule_handler.clone());
+    let variants_router = Router::new()
+        .route(
+            "/url/{id}/variants",
+            get(list_variants_handler).post(create_variant_handler),
+        )
+        .route(
+            "/url/{id}/variants/{variant_id}",
+            put(update_variant_handler).delete(delete_variant_handler),
+        )
+        .with_state(handlers.variant_handler.clone());
+    let api_keys_router = Router::new()
+        .route(
+            "/api-keys",
+            get(list_api_keys_handler).post(create_api_key_handler),
+        )
+        .route("/api-keys/{id}", delete(revoke_api_key_handler))
+        .with_state(handlers.api_key_handler.clone());
+
     let private_router = Router::new()
+        .route("/url", get(get_all_url_handler_axum))
+        .route("/url/mine", get(get_my_urls_handler))
         .route("/url/save", post(create_url_handler))
-        .route("/url/{id}", delete(delete_url_handler))
+        .route(
+            "/url/bulk",
+            post(bulk_create_urls_handler).layer(DefaultBodyLimit::max(BULK_BODY_LIMIT)),
+        )
+        .route(
+            "/url/{id}",
+            delete(delete_url_handler).patch(update_url_handler),
+        )
+        .route("/url/{id}/history", get(get_url_history_handler))
         .with_state(handlers.url_handler.clone())
-        .layer(from_fn(auth_middleware));
+        .merge(analytics_router)
+        .merge(blocklist_router)
+        .merge(domains_router)
+        .merge(rules_router)
+        .merge(variants_router)
+        .merge(api_keys_router)
+        .layer(from_fn_with_state(
+            handlers.api_key_service.clone(),
+            auth_middleware,
+        ));
 
     let public_routes = Router::new()
-        .route("/url", get(get_all_url_handler_axum))
-        .nest("/auth/google", auth_google)
-        .nest("/auth", auth_basic)
+        .route("/url/{alias}/qr", get(qr_code_handler))
+        .nest("/auth", auth_basic.merge(auth_oauth))
+        .with_state(handlers.url_handler.clone());
+
+    let redirect_routes = Router::new()
+        .route(
+            "/{alias}",
+            get(redirect_url_handler).post(unlock_url_handler),
+        )
         .with_state(handlers.url_handler.clone());
 
     // Добавляем эндпоинт для метрик
@@ -63,16 +163,21 @@ pub async fn run_http_server(
         .nest("/api/v1", public_routes)
         .nest("/api/v1/private", private_router)
         .merge(metrics_route)
+        .route("/.well-known/jwks.json", get(jwks_handler))
+        .merge(redirect_routes)
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .layer(get_cors())
         .layer(CompressionLayer::new())
         .layer(from_fn_with_state(metrics.clone(), metrics_middleware))
         .with_state(metrics);
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal(pool))
-        .await
-        .unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal(pool))
+    .await
+    .unwrap();
 }
 fn get_cors() -> CorsLayer {
     CorsLayer::new()
@@ -102,7 +207,9 @@ pub async fn shutdown_signal(pool: Pool<Postgres>) {
 
     #[cfg(not(unix))]
     {
-        signal::ctrl_c().await.expect("failed to listen for Ctrl+C");
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for Ctrl+C");
         println!("🪟 Received Ctrl+C (Windows)");
         pool.close().await;
         println!("✅ Pool closed gracefully");
@@ -1,6 +1,19 @@
 use crate::domain::url::Url;
-use crate::feature::auth::entity::{AuthGoogleDTO, LoginDTO, RegisterDTO};
-use crate::feature::url::entity::CreateUrlDTO;
+use crate::feature::analytics::entity::{LinkStats, StatsBucket, StatsInterval, VariantClicks};
+use crate::feature::api_key::entity::{ApiKey, ApiKeyScope, CreateApiKeyDTO, CreatedApiKey};
+use crate::feature::auth::entity::{LoginDTO, RegisterDTO};
+use crate::feature::blocklist::entity::{BlockedDomain, CreateBlockedDomainDTO};
+use crate::feature::custom_domain::entity::{CreateDomainDTO, CustomDomain, DomainResponse};
+use crate::feature::oauth::entity::OAuthCallbackDTO;
+use crate::feature::redirect_rule::entity::{
+    Platform, RedirectRule, RedirectRuleDTO, RuleConditions,
+};
+use crate::feature::url::entity::{
+    BulkCreateResponse, BulkUrlResult, CreateUrlDTO, LinkPasswordForm, QrErrorCorrection, QrFormat,
+    ShortenedUrl, SortOrder, UpdateUrlDTO, UrlHistoryEntry, UrlPage, UrlRejection, UrlSortField,
+};
+use crate::feature::url::policy::PolicyViolation;
+use crate::feature::variant::entity::{UrlVariant, VariantDTO};
 use utoipa::OpenApi;
 
 #[derive(utoipa::ToSchema)]
@@ -11,23 +24,59 @@ pub struct CookieAuth;
     info(
         title = "Url shortener",
         version = "0.0.1",
-        description = "API для сокращения URL и аутентификации через Google OAuth"
+        description = "API для сокращения URL и аутентификации через OAuth/OIDC"
     ),
     paths(
         crate::feature::url::handler::get_all_url_handler_axum,
+        crate::feature::url::handler::get_my_urls_handler,
         crate::feature::url::handler::create_url_handler,
+        crate::feature::url::handler::bulk_create_urls_handler,
+        crate::feature::url::handler::update_url_handler,
+        crate::feature::url::handler::get_url_history_handler,
         crate::feature::url::handler::delete_url_handler,
-        crate::feature::auth::handler::google_oauth_handler,
-        crate::feature::auth::handler::handle_google_code,
+        crate::feature::url::handler::redirect_url_handler,
+        crate::feature::url::handler::unlock_url_handler,
+        crate::feature::url::handler::qr_code_handler,
+        crate::feature::analytics::handler::get_url_stats_handler,
+        crate::feature::blocklist::handler::list_blocked_domains_handler,
+        crate::feature::blocklist::handler::add_blocked_domain_handler,
+        crate::feature::blocklist::handler::delete_blocked_domain_handler,
+        crate::feature::custom_domain::handler::list_domains_handler,
+        crate::feature::custom_domain::handler::add_domain_handler,
+        crate::feature::custom_domain::handler::verify_domain_handler,
+        crate::feature::custom_domain::handler::delete_domain_handler,
+        crate::feature::redirect_rule::handler::list_rules_handler,
+        crate::feature::redirect_rule::handler::create_rule_handler,
+        crate::feature::redirect_rule::handler::update_rule_handler,
+        crate::feature::redirect_rule::handler::delete_rule_handler,
+        crate::feature::variant::handler::list_variants_handler,
+        crate::feature::variant::handler::create_variant_handler,
+        crate::feature::variant::handler::update_variant_handler,
+        crate::feature::variant::handler::delete_variant_handler,
+        crate::feature::api_key::handler::list_api_keys_handler,
+        crate::feature::api_key::handler::create_api_key_handler,
+        crate::feature::api_key::handler::revoke_api_key_handler,
+        crate::feature::oauth::handler::oauth_url_handler,
+        crate::feature::oauth::handler::oauth_callback_handler,
+        crate::feature::auth::handler::jwks_handler,
         crate::feature::auth::handler::register_handler,
+        crate::feature::auth::handler::refresh_handler,
+        crate::feature::auth::handler::logout_handler,
         crate::feature::auth::handler::get_user_by_email_handler
     ),
     components(
-        schemas(CreateUrlDTO, AuthGoogleDTO, Url, CookieAuth, RegisterDTO,LoginDTO)
+        schemas(CreateUrlDTO, OAuthCallbackDTO, Url, CookieAuth, RegisterDTO,LoginDTO, LinkStats, StatsBucket, StatsInterval, UrlPage, UrlSortField, SortOrder, UpdateUrlDTO, UrlHistoryEntry, BulkCreateResponse, BulkUrlResult, ShortenedUrl, LinkPasswordForm, QrFormat, QrErrorCorrection, UrlRejection, PolicyViolation, BlockedDomain, CreateBlockedDomainDTO, CustomDomain, DomainResponse, CreateDomainDTO, RedirectRule, RedirectRuleDTO, RuleConditions, Platform, UrlVariant, VariantDTO, VariantClicks, ApiKey, CreatedApiKey, CreateApiKeyDTO, ApiKeyScope)
     ),
     tags(
         (name = "URL", description = "Операции с URL"),
-        (name = "Auth", description = "Аутентификация через Google OAuth и почту с паролем")
+        (name = "Redirect", description = "Переход по короткой ссылке"),
+        (name = "Analytics", description = "Статистика переходов"),
+        (name = "Blocklist", description = "Запрещённые домены для сокращения"),
+        (name = "Domains", description = "Собственные домены для коротких ссылок"),
+        (name = "Rules", description = "Правила перенаправления по устройству, стране и языку"),
+        (name = "Variants", description = "A/B-варианты ссылок с весами и счётчиками переходов"),
+        (name = "API keys", description = "Персональные API-ключи для программного доступа"),
+        (name = "Auth", description = "Аутентификация через OAuth/OIDC-провайдеров и почту с паролем")
     ),
     servers(
         (url = "/api", description = "API base path")
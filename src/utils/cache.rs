@@ -0,0 +1,257 @@
+use crate::app::config::{CacheBackend, CacheConfig};
+use async_trait::async_trait;
+use lru::LruCache;
+use redis::AsyncCommands;
+use redis::aio::ConnectionManager;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[async_trait]
+pub trait Cache<V>: Send + Sync {
+    async fn get(&self, key: &str) -> Option<V>;
+    async fn set(&self, key: &str, value: &V);
+    async fn invalidate(&self, key: &str);
+}
+
+/// Returns the cached value for `key`, loading and caching it on a miss.
+pub async fn get_or_load<V, E, F, Fut>(cache: &dyn Cache<V>, key: &str, load: F) -> Result<V, E>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<V, E>>,
+{
+    if let Some(value) = cache.get(key).await {
+        return Ok(value);
+    }
+    let value = load().await?;
+    cache.set(key, &value).await;
+    Ok(value)
+}
+
+pub struct NoopCache;
+
+#[async_trait]
+impl<V: Send + Sync + 'static> Cache<V> for NoopCache {
+    async fn get(&self, _key: &str) -> Option<V> {
+        None
+    }
+    async fn set(&self, _key: &str, _value: &V) {}
+    async fn invalidate(&self, _key: &str) {}
+}
+
+pub struct MemoryCache<V> {
+    entries: Mutex<LruCache<String, (V, Instant)>>,
+    ttl: Duration,
+}
+
+impl<V> MemoryCache<V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            ttl,
+        }
+    }
+}
+
+#[async_trait]
+impl<V: Clone + Send + Sync + 'static> Cache<V> for MemoryCache<V> {
+    async fn get(&self, key: &str) -> Option<V> {
+        let mut entries = self.entries.lock().ok()?;
+        match entries.get(key) {
+            Some((value, inserted_at)) if inserted_at.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+    async fn set(&self, key: &str, value: &V) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.put(key.to_string(), (value.clone(), Instant::now()));
+        }
+    }
+    async fn invalidate(&self, key: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.pop(key);
+        }
+    }
+}
+
+/// Shared between instances, so an invalidation is seen by all of them.
+pub struct RedisCache {
+    connection: ConnectionManager,
+    ttl: Duration,
+    prefix: String,
+}
+
+impl RedisCache {
+    pub async fn new(redis_url: &str, ttl: Duration, prefix: String) -> redis::RedisResult<Self> {
+        let client = redis::Client::open(redis_url)?;
+        let connection = ConnectionManager::new(client).await?;
+        Ok(Self {
+            connection,
+            ttl,
+            prefix,
+        })
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+}
+
+#[async_trait]
+impl<V: Serialize + DeserializeOwned + Send + Sync + 'static> Cache<V> for RedisCache {
+    async fn get(&self, key: &str) -> Option<V> {
+        let mut connection = self.connection.clone();
+        let value: Option<String> = connection
+            .get(self.key(key))
+            .await
+            .map_err(|err| eprintln!("❌ Redis cache get error: {:?}", err))
+            .ok()?;
+        value.and_then(|value| serde_json::from_str(&value).ok())
+    }
+    async fn set(&self, key: &str, value: &V) {
+        let Ok(value) = serde_json::to_string(value) else {
+            return;
+        };
+        let mut connection = self.connection.clone();
+        let result: redis::RedisResult<()> = connection
+            .set_ex(self.key(key), value, self.ttl.as_secs().max(1))
+            .await;
+        if let Err(err) = result {
+            eprintln!("❌ Redis cache set error: {:?}", err);
+        }
+    }
+    async fn invalidate(&self, key: &str) {
+        let mut connection = self.connection.clone();
+        let result: redis::RedisResult<()> = connection.del(self.key(key)).await;
+        if let Err(err) = result {
+            eprintln!("❌ Redis cache invalidate error: {:?}", err);
+        }
+    }
+}
+
+/// Builds the configured cache backend. `namespace` is appended to the
+/// configured redis prefix so that several caches can share one server.
+pub async fn new_cache<V>(config: &CacheConfig, namespace: &str) -> Arc<dyn Cache<V>>
+where
+    V: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    match config.backend {
+        CacheBackend::None => Arc::new(NoopCache),
+        CacheBackend::Memory => Arc::new(MemoryCache::new(config.capacity, config.get_ttl())),
+        CacheBackend::Redis => {
+            match RedisCache::new(
+                &config.redis_url,
+                config.get_ttl(),
+                format!("{}{}", config.prefix, namespace),
+            )
+            .await
+            {
+                Ok(cache) => Arc::new(cache),
+                Err(err) => {
+                    eprintln!(
+                        "❌ Failed to connect to redis cache, falling back to in-memory: {:?}",
+                        err
+                    );
+                    Arc::new(MemoryCache::new(config.capacity, config.get_ttl()))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    async fn load_counted(
+        cache: &dyn Cache<String>,
+        loads: &AtomicUsize,
+        result: Result<&str, &str>,
+    ) -> Result<String, String> {
+        get_or_load(cache, "promo", || async {
+            loads.fetch_add(1, Ordering::SeqCst);
+            result.map(str::to_string).map_err(str::to_string)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn memory_cache_hit_miss_and_invalidate() {
+        let cache = MemoryCache::new(10, TTL);
+        assert_eq!(Cache::<String>::get(&cache, "promo").await, None);
+        cache.set("promo", &"https://example.com".to_string()).await;
+        assert_eq!(
+            cache.get("promo").await.as_deref(),
+            Some("https://example.com")
+        );
+        Cache::<String>::invalidate(&cache, "promo").await;
+        assert_eq!(Cache::<String>::get(&cache, "promo").await, None);
+    }
+
+    #[tokio::test]
+    async fn memory_cache_expires_entries_and_evicts_the_oldest() {
+        let expired = MemoryCache::new(10, Duration::ZERO);
+        expired.set("promo", &1).await;
+        assert_eq!(expired.get("promo").await, None);
+
+        let full = MemoryCache::new(2, TTL);
+        full.set("a", &1).await;
+        full.set("b", &2).await;
+        full.set("c", &3).await;
+        assert_eq!(full.get("a").await, None);
+        assert_eq!(full.get("c").await, Some(3));
+    }
+
+    #[tokio::test]
+    async fn get_or_load_only_loads_on_a_miss() {
+        let cache = MemoryCache::new(10, TTL);
+        let loads = AtomicUsize::new(0);
+        assert_eq!(
+            load_counted(&cache, &loads, Ok("first")).await.as_deref(),
+            Ok("first")
+        );
+        assert_eq!(
+            load_counted(&cache, &loads, Ok("second")).await.as_deref(),
+            Ok("first")
+        );
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+        Cache::<String>::invalidate(&cache, "promo").await;
+        assert_eq!(
+            load_counted(&cache, &loads, Ok("second")).await.as_deref(),
+            Ok("second")
+        );
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn get_or_load_does_not_cache_errors() {
+        let cache = MemoryCache::new(10, TTL);
+        let loads = AtomicUsize::new(0);
+        assert!(load_counted(&cache, &loads, Err("down")).await.is_err());
+        assert_eq!(Cache::<String>::get(&cache, "promo").await, None);
+        assert!(load_counted(&cache, &loads, Ok("up")).await.is_ok());
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn noop_cache_always_loads() {
+        let loads = AtomicUsize::new(0);
+        for _ in 0..2 {
+            assert!(load_counted(&NoopCache, &loads, Ok("value")).await.is_ok());
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+        NoopCache.set("promo", &"value".to_string()).await;
+        assert_eq!(Cache::<String>::get(&NoopCache, "promo").await, None);
+    }
+}
@@ -1,2 +1,48 @@
 pub const ACCESS_TOKEN_COOKIE: &str = "access_token";
 pub const REFRESH_TOKEN_COOKIE: &str = "refresh_token";
+pub const OAUTH_STATE_COOKIE: &str = "oauth_state";
+pub const OAUTH_STATE_LENGTH: usize = 32;
+pub const PKCE_VERIFIER_LENGTH: usize = 64;
+
+pub const API_KEY_PREFIX: &str = "usk_";
+pub const API_KEY_LENGTH: usize = 40;
+pub const API_KEY_DISPLAY_LENGTH: usize = 12;
+pub const API_KEY_TOUCH_INTERVAL_SECS: i64 = 60;
+
+pub const ALIAS_MIN_LENGTH: usize = 3;
+pub const ALIAS_MAX_LENGTH: usize = 32;
+pub const RESERVED_ALIASES: &[&str] = &[
+    "api",
+    "api-docs",
+    "auth",
+    "admin",
+    "metrics",
+    "swagger-ui",
+    "health",
+    "static",
+];
+
+/// Ten years; a longer TTL is almost certainly a mistake.
+pub const URL_MAX_TTL_SECONDS: i64 = 315_360_000;
+
+pub const DEFAULT_PAGE_LIMIT: u64 = 20;
+pub const MAX_PAGE_LIMIT: u64 = 100;
+
+pub const LINK_PASSWORD_HEADER: &str = "x-link-password";
+pub const LINK_PASSWORD_PARAM: &str = "password";
+pub const VARIANT_COOKIE_PREFIX: &str = "variant_";
+
+pub const BULK_BODY_LIMIT: usize = 16 * 1024 * 1024;
+
+pub const URL_ALIAS_UNIQUE_CONSTRAINT: &str = "url_domain_id_alias_key";
+pub const URL_DEFAULT_ALIAS_UNIQUE_INDEX: &str = "url_default_domain_alias_key";
+pub const URL_OWNER_UNIQUE_CONSTRAINT: &str = "url_user_id_url_key";
+
+pub const QR_DEFAULT_SIZE: u32 = 256;
+pub const QR_MIN_SIZE: u32 = 64;
+pub const QR_MAX_SIZE: u32 = 2048;
+pub const QR_DEFAULT_MARGIN: u32 = 4;
+pub const QR_MAX_MARGIN: u32 = 16;
+
+pub const RULES_CACHE_NAMESPACE: &str = "rules:";
+pub const VARIANTS_CACHE_NAMESPACE: &str = "variants:";
@@ -1,4 +1,7 @@
+pub mod cache;
 pub mod constants;
 pub mod db;
 pub mod random;
+pub mod request;
+pub mod secret;
 pub mod url;
@@ -1,11 +1,8 @@
-use rand::{Rng, distributions::Alphanumeric, thread_rng};
+use rand::{seq::SliceRandom, thread_rng};
 
-pub async fn new_random_string(size: usize) -> Result<String, ()> {
-    let s: String = thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(size)
-        .map(char::from)
-        .collect();
-
-    Ok(s)
+pub async fn new_random_string(alphabet: &[char], size: usize) -> Result<String, ()> {
+    let mut rng = thread_rng();
+    (0..size)
+        .map(|_| alphabet.choose(&mut rng).copied().ok_or(()))
+        .collect()
 }
@@ -0,0 +1,177 @@
+use crate::app::config::ProxyConfig;
+use axum::http::HeaderMap;
+use axum::http::uri::Authority;
+use ipnet::IpNet;
+use std::net::{IpAddr, SocketAddr};
+
+/// Reverse proxies allowed to set `X-Forwarded-*` headers.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<IpNet>,
+}
+
+impl TrustedProxies {
+    pub fn from_config(config: &ProxyConfig) -> Self {
+        let networks = config
+            .trusted_proxies
+            .iter()
+            .filter_map(|proxy| {
+                proxy
+                    .parse::<IpNet>()
+                    .or_else(|_| proxy.parse::<IpAddr>().map(IpNet::from))
+                    .map_err(|e| eprintln!("❌ Invalid trusted proxy {}: {}", proxy, e))
+                    .ok()
+            })
+            .collect();
+        Self { networks }
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.networks.iter().any(|network| network.contains(&ip))
+    }
+}
+
+pub fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Client address. Forwarded headers are only honoured when the socket peer
+/// is a trusted proxy; `X-Forwarded-For` is then read from the nearest hop
+/// and the first address not added by one of our proxies is the client.
+pub fn client_ip(headers: &HeaderMap, peer: SocketAddr, proxies: &TrustedProxies) -> IpAddr {
+    let peer = peer.ip();
+    if !proxies.contains(peer) {
+        return peer;
+    }
+    if let Some(value) = header_value(headers, "x-forwarded-for") {
+        let hops: Vec<IpAddr> = value
+            .split(',')
+            .filter_map(|hop| hop.trim().parse().ok())
+            .collect();
+        return hops
+            .iter()
+            .rev()
+            .find(|hop| !proxies.contains(**hop))
+            .or(hops.first())
+            .copied()
+            .unwrap_or(peer);
+    }
+    header_value(headers, "x-real-ip")
+        .and_then(|ip| ip.parse().ok())
+        .unwrap_or(peer)
+}
+
+/// Host the request was addressed to, without the port. `X-Forwarded-Host`
+/// wins over `Host` when the peer is a trusted proxy.
+pub fn request_host(
+    headers: &HeaderMap,
+    peer: SocketAddr,
+    proxies: &TrustedProxies,
+) -> Option<String> {
+    proxies
+        .contains(peer.ip())
+        .then(|| header_value(headers, "x-forwarded-host"))
+        .flatten()
+        .and_then(|value| value.split(',').next().map(|host| host.trim().to_string()))
+        .or_else(|| header_value(headers, "host"))
+        .and_then(|host| host.parse::<Authority>().ok())
+        .map(|authority| {
+            authority
+                .host()
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_ascii_lowercase()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn proxies(networks: &[&str]) -> TrustedProxies {
+        TrustedProxies::from_config(&ProxyConfig {
+            trusted_proxies: networks.iter().map(|network| network.to_string()).collect(),
+        })
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn peer(ip: &str) -> SocketAddr {
+        SocketAddr::new(ip.parse().unwrap(), 40000)
+    }
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn trusted_proxies_accept_networks_and_addresses() {
+        let proxies = proxies(&["10.0.0.0/8", "192.0.2.1", "not an address"]);
+        assert!(proxies.contains(ip("10.1.2.3")));
+        assert!(proxies.contains(ip("192.0.2.1")));
+        assert!(!proxies.contains(ip("192.0.2.2")));
+    }
+
+    #[test]
+    fn client_ip_ignores_forwarded_headers_from_untrusted_peers() {
+        let headers = headers(&[("x-forwarded-for", "1.1.1.1"), ("x-real-ip", "2.2.2.2")]);
+        assert_eq!(
+            client_ip(&headers, peer("203.0.113.9"), &TrustedProxies::default()),
+            ip("203.0.113.9")
+        );
+    }
+
+    #[test]
+    fn client_ip_takes_the_nearest_untrusted_hop() {
+        let proxies = proxies(&["10.0.0.0/8"]);
+        let forwarded = headers(&[("x-forwarded-for", "6.6.6.6, 198.51.100.7, 10.0.0.2")]);
+        assert_eq!(
+            client_ip(&forwarded, peer("10.0.0.1"), &proxies),
+            ip("198.51.100.7")
+        );
+
+        let only_proxies = headers(&[("x-forwarded-for", "10.0.0.3, 10.0.0.2")]);
+        assert_eq!(
+            client_ip(&only_proxies, peer("10.0.0.1"), &proxies),
+            ip("10.0.0.3")
+        );
+        let real_ip = headers(&[("x-real-ip", "198.51.100.8")]);
+        assert_eq!(
+            client_ip(&real_ip, peer("10.0.0.1"), &proxies),
+            ip("198.51.100.8")
+        );
+    }
+
+    #[test]
+    fn request_host_only_trusts_forwarded_host_from_proxies() {
+        let proxies = proxies(&["10.0.0.0/8"]);
+        let forwarded = headers(&[
+            ("host", "internal:8080"),
+            ("x-forwarded-host", "Links.Example.com, other.example"),
+        ]);
+        assert_eq!(
+            request_host(&forwarded, peer("10.0.0.1"), &proxies).as_deref(),
+            Some("links.example.com")
+        );
+        assert_eq!(
+            request_host(&forwarded, peer("203.0.113.9"), &proxies).as_deref(),
+            Some("internal")
+        );
+        let ipv6 = headers(&[("host", "[::1]:3000")]);
+        assert_eq!(
+            request_host(&ipv6, peer("203.0.113.9"), &proxies).as_deref(),
+            Some("::1")
+        );
+    }
+}
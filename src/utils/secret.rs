@@ -0,0 +1,11 @@
+/// Reads a configured value that is given inline, as `env:NAME` or as
+/// `file:PATH`.
+pub fn load_material(value: &str) -> Result<String, String> {
+    if let Some(name) = value.strip_prefix("env:") {
+        std::env::var(name).map_err(|_| format!("environment variable {} is not set", name))
+    } else if let Some(path) = value.strip_prefix("file:") {
+        std::fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path, err))
+    } else {
+        Ok(value.to_string())
+    }
+}
@@ -1,5 +1,4 @@
 use regex::Regex;
-use std::env;
 use teloxide::types::Message;
 use url::Url;
 pub fn extract_first_valid_url_from_message(msg: &Message) -> Option<String> {
@@ -25,22 +24,3 @@ pub fn extract_first_valid_url_from_message(msg: &Message) -> Option<String> {
 
     None
 }
-
-pub fn generate_google_oauth_url() -> Result<String, String> {
-    let client_id = env::var("GOOGLE_CLIENT_ID")
-        .map_err(|_| "GOOGLE_CLIENT_ID environment variable not found".to_string())?;
-
-    let mut url = Url::parse("https://accounts.google.com/o/oauth2/v2/auth")
-        .map_err(|e| format!("Failed to parse URL: {}", e))?;
-
-    let google_uri = env::var("GOOGLE_URI_REDIRECT")
-        .map_err(|_| "GOOGLE_URI_REDIRECT environment variable not found".to_string())?;
-    url.query_pairs_mut()
-        .append_pair("client_id", &client_id)
-        .append_pair("response_type", "code")
-        .append_pair("redirect_uri", &google_uri)
-        .append_pair("scope", "openid email profile")
-        .append_pair("access_type", "offline");
-
-    Ok(url.to_string())
-}